@@ -15,6 +15,7 @@ pub struct BufferTheme {
     pub buffer_fg: ratatui::style::Color,
     pub cursor_line_bg: ratatui::style::Color,
     pub search_bg: ratatui::style::Color,
+    pub visual_bg: ratatui::style::Color,
     pub line_nr: ratatui::style::Color,
     pub cur_line_nr: ratatui::style::Color,
     pub border_fg: ratatui::style::Color,
@@ -42,6 +43,19 @@ pub fn update_viewport_by_direction(
     update::viewport::update_by_direction(viewport, buffer, direction);
 }
 
+pub fn update_cursor_by_entry_find(
+    viewport: &mut ViewPort,
+    buffer: &mut TextBuffer,
+    count: &usize,
+    direction: &message::CursorDirection,
+) -> bool {
+    let is_find = update::find::line(count, direction, &mut viewport.cursor, buffer);
+    if is_find {
+        update::viewport::update_by_cursor(viewport, buffer);
+    }
+    is_find
+}
+
 pub fn update_viewport_by_buffer(viewport: &mut ViewPort, mode: &Mode, buffer: &TextBuffer) {
     update::cursor::set_to_inbound_position(&mut viewport.cursor, buffer, mode);
     update::viewport::update_by_cursor(viewport, buffer);
@@ -131,5 +131,7 @@ pub enum ViewPortDirection {
     CenterOnCursor,
     HalfPageDown,
     HalfPageUp,
+    LineDown,
+    LineUp,
     TopOnCursor,
 }
@@ -16,6 +16,7 @@ pub enum Mode {
     #[default]
     Navigation,
     Normal,
+    Visual,
 }
 
 impl Mode {
@@ -31,6 +32,7 @@ impl Display for Mode {
             Mode::Insert => "insert".to_string(),
             Mode::Navigation => "navigation".to_string(),
             Mode::Normal => "normal".to_string(),
+            Mode::Visual => "visual".to_string(),
         };
 
         write!(f, "{}", content)
@@ -143,6 +145,7 @@ impl Default for CursorPosition {
 pub struct BufferLine {
     pub prefix: Option<String>,
     pub content: Ansi,
+    pub suffix: Option<String>,
     pub search_char_position: Option<Vec<(usize, usize)>>,
     pub signs: Vec<Sign>,
 }
@@ -155,6 +158,13 @@ impl BufferLine {
         }
     }
 
+    pub fn get_displayed_content(&self) -> Ansi {
+        match &self.suffix {
+            Some(suffix) => Ansi::new(&format!("{}{}", self.content, suffix)),
+            None => self.content.clone(),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.content.is_empty()
     }
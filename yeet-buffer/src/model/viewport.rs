@@ -23,6 +23,7 @@ pub struct ViewPort {
     pub show_border: bool,
     pub sign_column_width: usize,
     pub vertical_index: usize,
+    pub visual_anchor: Option<usize>,
     pub width: u16,
     pub wrap: bool,
     pub x: u16,
@@ -63,6 +64,12 @@ impl ViewPort {
         self.sign_column_width + self.get_line_number_width() + self.prefix_column_width
     }
 
+    pub fn get_visual_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.visual_anchor?;
+        let cursor = self.cursor.vertical_index;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
     pub fn set(&mut self, settings: &WindowSettings) {
         self.sign_column_width = settings.sign_column_width;
     }
@@ -111,6 +118,22 @@ mod tests {
         assert_eq!(vp.get_offset_width(&bl), 8);
     }
 
+    #[test]
+    fn visual_range_orders_anchor_and_cursor() {
+        let mut vp = ViewPort {
+            visual_anchor: Some(5),
+            ..Default::default()
+        };
+        vp.cursor.vertical_index = 2;
+        assert_eq!(vp.get_visual_range(), Some((2, 5)));
+
+        vp.cursor.vertical_index = 7;
+        assert_eq!(vp.get_visual_range(), Some((5, 7)));
+
+        vp.visual_anchor = None;
+        assert_eq!(vp.get_visual_range(), None);
+    }
+
     #[test]
     fn offset_width_includes_prefix_column() {
         let vp = ViewPort {
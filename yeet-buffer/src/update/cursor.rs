@@ -65,15 +65,7 @@ pub fn update_by_direction(
             }
             CursorDirection::LastFindBackward => {
                 if let Some(find) = buffer.last_find.clone() {
-                    let find = match find {
-                        CursorDirection::FindBackward(find) => CursorDirection::FindForward(find),
-                        CursorDirection::FindForward(find) => CursorDirection::FindBackward(find),
-                        CursorDirection::TillBackward(find) => CursorDirection::TillForward(find),
-                        CursorDirection::TillForward(find) => CursorDirection::TillBackward(find),
-                        _ => unreachable!(),
-                    };
-
-                    find::char(&find, cursor, buffer);
+                    find::char(&find::invert(&find), cursor, buffer);
                 }
             }
             CursorDirection::LastFindForward => {
@@ -284,6 +276,7 @@ fn get_index_correction(mode: &Mode) -> usize {
         Mode::Insert => 0,
         Mode::Navigation => 1,
         Mode::Normal => 1,
+        Mode::Visual => 1,
     }
 }
 
@@ -5,6 +5,64 @@ use crate::{
 
 use super::cursor;
 
+pub fn line(
+    count: &usize,
+    direction: &CursorDirection,
+    cursor: &mut Cursor,
+    model: &mut TextBuffer,
+) -> bool {
+    let find = match direction {
+        CursorDirection::FindBackward(_)
+        | CursorDirection::FindForward(_)
+        | CursorDirection::TillBackward(_)
+        | CursorDirection::TillForward(_) => {
+            model.last_find = Some(direction.clone());
+            direction.clone()
+        }
+        CursorDirection::LastFindBackward => match &model.last_find {
+            Some(find) => invert(find),
+            None => return true,
+        },
+        CursorDirection::LastFindForward => match &model.last_find {
+            Some(find) => find.clone(),
+            None => return true,
+        },
+        _ => return false,
+    };
+
+    for _ in 0..*count {
+        let index = cursor.vertical_index;
+        let found = match &find {
+            CursorDirection::FindBackward(find) => find_line_backward(find, &model.lines, index),
+            CursorDirection::FindForward(find) => find_line_forward(find, &model.lines, index),
+            CursorDirection::TillBackward(find) => {
+                find_line_backward(find, &model.lines, index).map(|found| found + 1)
+            }
+            CursorDirection::TillForward(find) => {
+                find_line_forward(find, &model.lines, index).map(|found| found - 1)
+            }
+            _ => unreachable!(),
+        };
+
+        match found {
+            Some(found) => cursor.vertical_index = found,
+            None => break,
+        }
+    }
+
+    true
+}
+
+pub fn invert(find: &CursorDirection) -> CursorDirection {
+    match find {
+        CursorDirection::FindBackward(find) => CursorDirection::FindForward(*find),
+        CursorDirection::FindForward(find) => CursorDirection::FindBackward(*find),
+        CursorDirection::TillBackward(find) => CursorDirection::TillForward(*find),
+        CursorDirection::TillForward(find) => CursorDirection::TillBackward(*find),
+        _ => unreachable!(),
+    }
+}
+
 pub fn char(direction: &CursorDirection, cursor: &mut Cursor, model: &mut TextBuffer) {
     match direction {
         CursorDirection::FindBackward(find) => {
@@ -75,3 +133,93 @@ fn find_char_forward(find: &char, lines: &[BufferLine], cursor: &Cursor) -> Opti
         .position(|c| &c == find)
         .map(|i| index + i + 1)
 }
+
+fn starts_with(find: &char, line: &BufferLine) -> bool {
+    line.content.to_stripped_string().starts_with(*find)
+}
+
+fn find_line_backward(find: &char, lines: &[BufferLine], index: usize) -> Option<usize> {
+    lines
+        .iter()
+        .take(index)
+        .rposition(|line| starts_with(find, line))
+}
+
+fn find_line_forward(find: &char, lines: &[BufferLine], index: usize) -> Option<usize> {
+    lines
+        .iter()
+        .skip(index + 1)
+        .position(|line| starts_with(find, line))
+        .map(|i| index + i + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        message::CursorDirection,
+        model::{BufferLine, Cursor, TextBuffer},
+    };
+
+    fn make_buffer() -> TextBuffer {
+        TextBuffer {
+            lines: ["alpha", "beta", "bravo", "charlie", "delta"]
+                .map(BufferLine::from)
+                .to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn find(buffer: &mut TextBuffer, start: usize, direction: CursorDirection) -> usize {
+        let mut cursor = Cursor {
+            vertical_index: start,
+            ..Default::default()
+        };
+
+        assert!(super::line(&1, &direction, &mut cursor, buffer));
+
+        cursor.vertical_index
+    }
+
+    #[test]
+    fn find_line_moves_to_line_starting_with_char() {
+        let mut buffer = make_buffer();
+        assert_eq!(find(&mut buffer, 0, CursorDirection::FindForward('c')), 3);
+        assert_eq!(find(&mut buffer, 4, CursorDirection::FindBackward('b')), 2);
+    }
+
+    #[test]
+    fn till_line_stops_before_found_line() {
+        let mut buffer = make_buffer();
+        assert_eq!(find(&mut buffer, 0, CursorDirection::TillForward('d')), 3);
+        assert_eq!(find(&mut buffer, 4, CursorDirection::TillBackward('a')), 1);
+    }
+
+    #[test]
+    fn find_line_keeps_cursor_without_match() {
+        let mut buffer = make_buffer();
+        assert_eq!(find(&mut buffer, 1, CursorDirection::FindForward('x')), 1);
+        assert_eq!(find(&mut buffer, 1, CursorDirection::FindForward('a')), 1);
+    }
+
+    #[test]
+    fn find_line_repeats_last_find() {
+        let mut buffer = make_buffer();
+        assert_eq!(find(&mut buffer, 0, CursorDirection::FindForward('b')), 1);
+        assert_eq!(find(&mut buffer, 1, CursorDirection::LastFindForward), 2);
+        assert_eq!(find(&mut buffer, 2, CursorDirection::LastFindBackward), 1);
+    }
+
+    #[test]
+    fn find_line_ignores_other_directions() {
+        let mut buffer = make_buffer();
+        let mut cursor = Cursor::default();
+
+        assert!(!super::line(
+            &1,
+            &CursorDirection::Down,
+            &mut cursor,
+            &mut buffer
+        ));
+        assert_eq!(cursor.vertical_index, 0);
+    }
+}
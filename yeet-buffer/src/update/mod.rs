@@ -4,7 +4,7 @@ use crate::{
 };
 
 pub(crate) mod cursor;
-mod find;
+pub(crate) mod find;
 mod modification;
 pub mod viewport;
 mod word;
@@ -68,6 +68,18 @@ fn update_buffer(
             Vec::new()
         }
         BufferMessage::ChangeMode(from, to) => {
+            if let Some(viewport) = viewport.as_deref_mut() {
+                match to {
+                    Mode::Visual if !from.is_command() => {
+                        viewport.visual_anchor = Some(viewport.cursor.vertical_index);
+                    }
+                    Mode::Command(_) | Mode::Visual => {}
+                    Mode::Insert | Mode::Navigation | Mode::Normal => {
+                        viewport.visual_anchor = None;
+                    }
+                }
+            }
+
             if from == &Mode::Insert && to != &Mode::Insert {
                 buffer.undo.close_transaction();
 
@@ -69,7 +69,10 @@ fn update_by_cursor_wrap(viewport: &mut ViewPort, buffer: &TextBuffer) {
             break;
         }
         let line = &buffer.lines[i];
-        let line_height = wrap::visual_line_count(&line.content, viewport.get_content_width(line));
+        let line_height = wrap::visual_line_count(
+            &line.get_displayed_content(),
+            viewport.get_content_width(line),
+        );
         visual_rows += line_height;
     }
 
@@ -79,7 +82,10 @@ fn update_by_cursor_wrap(viewport: &mut ViewPort, buffer: &TextBuffer) {
 
     let cursor_line_height = {
         let line = &buffer.lines[cursor_line];
-        wrap::visual_line_count(&line.content, viewport.get_content_width(line))
+        wrap::visual_line_count(
+            &line.get_displayed_content(),
+            viewport.get_content_width(line),
+        )
     };
 
     let mut available = height.saturating_sub(cursor_line_height);
@@ -90,7 +96,10 @@ fn update_by_cursor_wrap(viewport: &mut ViewPort, buffer: &TextBuffer) {
             continue;
         }
         let line = &buffer.lines[i];
-        let line_height = wrap::visual_line_count(&line.content, viewport.get_content_width(line));
+        let line_height = wrap::visual_line_count(
+            &line.get_displayed_content(),
+            viewport.get_content_width(line),
+        );
         if line_height > available {
             break;
         }
@@ -165,6 +174,24 @@ pub fn update_by_direction(
                 .vertical_index
                 .saturating_sub(usize::from(index_offset));
         }
+        ViewPortDirection::LineDown => {
+            if viewport.vertical_index + usize::from(viewport.height) < buffer.lines.len() {
+                viewport.vertical_index += 1;
+            }
+
+            if viewport.cursor.vertical_index < viewport.vertical_index {
+                viewport.cursor.vertical_index = viewport.vertical_index;
+            }
+        }
+        ViewPortDirection::LineUp => {
+            viewport.vertical_index = viewport.vertical_index.saturating_sub(1);
+
+            let viewport_end_index =
+                viewport.vertical_index + usize::from(viewport.height.saturating_sub(1));
+            if viewport.cursor.vertical_index > viewport_end_index {
+                viewport.cursor.vertical_index = viewport_end_index;
+            }
+        }
         ViewPortDirection::TopOnCursor => {
             viewport.vertical_index = viewport.cursor.vertical_index;
         }
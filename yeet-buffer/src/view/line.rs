@@ -18,19 +18,25 @@ pub fn add_line_styles(
     theme: &BufferTheme,
 ) -> Ansi {
     let content_width = vp.get_content_width(line);
-    let ansi = line.content.skip_chars(vp.horizontal_index);
+    let ansi = line.get_displayed_content().skip_chars(vp.horizontal_index);
 
     let cursor_line_offset = cursor.vertical_index.checked_sub(vp.vertical_index);
     let is_cursor_line = cursor_line_offset == Some(*index);
     let use_cursor_line_bg = is_cursor_line && !vp.hide_cursor_line;
+    let is_visual_line = is_in_visual_range(vp, mode, index);
 
-    let bg = if use_cursor_line_bg {
+    let bg = if is_visual_line {
+        theme.visual_bg
+    } else if use_cursor_line_bg {
         theme.cursor_line_bg
     } else {
         theme.buffer_bg
     };
 
     let ansi = add_search_styles(line, &ansi, theme.search_bg, bg);
+    if is_visual_line {
+        return add_visual_styles(&ansi, content_width, theme);
+    }
 
     if !is_cursor_line {
         if cursor_line_offset.is_none() {
@@ -63,14 +69,20 @@ pub fn add_line_styles_wrap(
     let cursor_line_offset = cursor.vertical_index.checked_sub(vp.vertical_index);
     let is_cursor_line = cursor_line_offset == Some(*index);
     let use_cursor_line_bg = is_cursor_line && !vp.hide_cursor_line;
+    let is_visual_line = is_in_visual_range(vp, mode, index);
 
-    let bg = if use_cursor_line_bg {
+    let bg = if is_visual_line {
+        theme.visual_bg
+    } else if use_cursor_line_bg {
         theme.cursor_line_bg
     } else {
         theme.buffer_bg
     };
 
     let ansi = add_search_styles(line, &ansi, theme.search_bg, bg);
+    if is_visual_line {
+        return add_visual_styles(&ansi, content_width, theme);
+    }
 
     if !is_cursor_line {
         if cursor_line_offset.is_none() {
@@ -145,7 +157,7 @@ fn add_cursor_styles_wrap(
     let (code, reset) = match mode {
         Mode::Command(_) | Mode::Normal => (CURSOR_NORMAL_CODE, CURSOR_NORMAL_RESET),
         Mode::Insert => (CURSOR_INSERT_CODE, CURSOR_INSERT_RESET),
-        Mode::Navigation => ("", ""),
+        Mode::Navigation | Mode::Visual => ("", ""),
     };
 
     content.insert(cursor_index, code);
@@ -154,6 +166,31 @@ fn add_cursor_styles_wrap(
     content
 }
 
+fn is_in_visual_range(vp: &ViewPort, mode: &Mode, index: &usize) -> bool {
+    if mode != &Mode::Visual {
+        return false;
+    }
+
+    let index = index + vp.vertical_index;
+    vp.get_visual_range()
+        .is_some_and(|(start, end)| start <= index && index <= end)
+}
+
+fn add_visual_styles(ansi: &Ansi, content_width: usize, theme: &BufferTheme) -> Ansi {
+    let mut content = ansi.clone();
+    let line_length = content.count_chars().min(content_width);
+    let repeat_count = content_width.saturating_sub(line_length);
+
+    let visual_bg = style::color_to_ansi_bg(theme.visual_bg);
+    let reset_with_bg = format!("\x1b[0m{}", visual_bg);
+    content.replace_resets_with(&reset_with_bg);
+    content.prepend(&visual_bg);
+    content.append(" ".repeat(repeat_count).as_str());
+    content.append(&style::ansi_reset_with_bg(theme.buffer_bg));
+
+    content
+}
+
 fn add_search_styles(line: &BufferLine, ansi: &Ansi, search: Color, bg: Color) -> Ansi {
     if let Some(search_char_position) = &line.search_char_position {
         let search_bg = style::color_to_ansi_bg(search);
@@ -223,7 +260,7 @@ fn add_cursor_styles(
     let (code, reset) = match mode {
         Mode::Command(_) | Mode::Normal => (CURSOR_NORMAL_CODE, CURSOR_NORMAL_RESET),
         Mode::Insert => (CURSOR_INSERT_CODE, CURSOR_INSERT_RESET),
-        Mode::Navigation => ("", ""),
+        Mode::Navigation | Mode::Visual => ("", ""),
     };
 
     content.insert(cursor_index, code);
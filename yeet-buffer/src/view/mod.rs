@@ -70,8 +70,10 @@ fn get_rendered_lines(viewport: &ViewPort, buffer: &TextBuffer) -> Vec<BufferLin
     let mut visual_rows = 0;
 
     for line in buffer.lines.iter().skip(viewport.vertical_index) {
-        let line_visual_height =
-            wrap::visual_line_count(&line.content, viewport.get_content_width(line));
+        let line_visual_height = wrap::visual_line_count(
+            &line.get_displayed_content(),
+            viewport.get_content_width(line),
+        );
         if visual_rows + line_visual_height > height && !result.is_empty() {
             break;
         }
@@ -108,7 +110,7 @@ fn get_styled_lines<'a>(
     for (i, bl) in lines.into_iter().enumerate() {
         let corrected_index = i + vp.vertical_index;
         let content_width = vp.get_content_width(&bl);
-        let segments = wrap::wrap_line(&bl.content, content_width);
+        let segments = wrap::wrap_line(&bl.get_displayed_content(), content_width);
 
         for segment in &segments {
             if visual_row >= usize::from(vp.height) {
@@ -128,6 +130,7 @@ fn get_styled_lines<'a>(
 
             let mut segment_bl = BufferLine {
                 content: segment.content.clone(),
+                suffix: None,
                 search_char_position: None,
                 signs: Vec::new(),
                 prefix: None,
@@ -200,6 +203,7 @@ mod test {
             buffer_fg: Color::White,
             cursor_line_bg: Color::Rgb(128, 128, 128),
             search_bg: Color::Red,
+            visual_bg: Color::Blue,
             line_nr: Color::Rgb(128, 128, 128),
             cur_line_nr: Color::White,
             border_fg: Color::Black,
@@ -576,6 +580,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn suffix_rendered_after_content() {
+        let vp = directory_current_viewport(40, 10);
+        let lines = vec![BufferLine {
+            suffix: Some(" -> target".to_string()),
+            ..BufferLine::from("link")
+        }];
+
+        let styled = get_styled_lines(&vp, &Mode::Navigation, &vp.cursor, lines, &test_theme());
+
+        let text: String = styled[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(text.contains("link -> target"));
+        assert_eq!(styled[0].width(), usize::from(vp.width) - 1);
+    }
+
     #[test]
     fn plugin_ansi_in_content_applied_to_text() {
         use ratatui::style::Color;
@@ -712,4 +735,50 @@ mod test {
             continuation_width, first_line_width,
         );
     }
+
+    #[test]
+    fn visual_mode_highlights_selected_range() {
+        use ratatui::style::Color;
+
+        let mut vp = tasks_viewport(20, 10);
+        vp.visual_anchor = Some(0);
+        vp.cursor.vertical_index = 1;
+        let lines = vec![
+            BufferLine::from("first"),
+            BufferLine::from("second"),
+            BufferLine::from("third"),
+        ];
+
+        let styled = get_styled_lines(&vp, &Mode::Visual, &vp.cursor, lines, &test_theme());
+
+        for line in &styled[0..2] {
+            assert!(line
+                .spans
+                .iter()
+                .filter(|span| !span.content.is_empty())
+                .all(|span| span.style.bg == Some(Color::Blue)));
+            assert_eq!(line.width(), usize::from(vp.width));
+        }
+        assert!(styled[2]
+            .spans
+            .iter()
+            .all(|span| span.style.bg != Some(Color::Blue)));
+    }
+
+    #[test]
+    fn visual_range_ignored_outside_visual_mode() {
+        use ratatui::style::Color;
+
+        let mut vp = tasks_viewport(20, 10);
+        vp.visual_anchor = Some(0);
+        vp.cursor.vertical_index = 1;
+        let lines = vec![BufferLine::from("first"), BufferLine::from("second")];
+
+        let styled = get_styled_lines(&vp, &Mode::Navigation, &vp.cursor, lines, &test_theme());
+
+        assert!(styled
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .all(|span| span.style.bg != Some(Color::Blue)));
+    }
 }
@@ -140,6 +140,7 @@ mod test {
             buffer_fg: Color::White,
             cursor_line_bg: Color::Rgb(128, 128, 128),
             search_bg: Color::Red,
+            visual_bg: Color::Blue,
             line_nr: Color::Rgb(128, 128, 128),
             cur_line_nr: Color::White,
             border_fg: Color::Black,
@@ -11,11 +11,11 @@ use yeet_buffer::model::BufferLine;
 
 use crate::{
     error::AppError,
-    event::{Emitter, Message},
-    init::{history, mark, qfix},
-    model::{Buffer, Model},
+    event::{Emitter, LogSeverity, Message},
+    init::{history, journal, mark, qfix},
+    model::{Buffer, Model, Tasks},
     open,
-    task::Task,
+    task::{self, Task, TaskKind},
     terminal::TerminalWrapper,
     update::app,
 };
@@ -81,6 +81,14 @@ fn is_preview_action(action: &Action) -> bool {
     }
 }
 
+fn cancel_preview_tasks(tasks: &Tasks) {
+    for task in tasks.running.values() {
+        if task.kind == TaskKind::Preview {
+            task.token.cancel();
+        }
+    }
+}
+
 async fn execute(
     is_preview: bool,
     model: &mut Model,
@@ -121,12 +129,18 @@ async fn execute(
                 emitter.run(Task::EmitMessages(messages));
             }
             Action::Load(path, selection) => {
+                cancel_preview_tasks(&model.state.tasks);
+
                 if path.is_dir() {
                     emitter.run(Task::EnumerateDirectory(path, selection.clone()));
                 } else if let Ok(window) = model.app.current_window() {
                     if let Some((_, _, preview_vp)) = app::get_focused_directory_viewports(window) {
                         let rect = preview_content_rect(preview_vp);
-                        emitter.run(Task::LoadPreview(path.clone(), rect));
+                        emitter.run(Task::LoadPreview(
+                            path.clone(),
+                            rect,
+                            model.settings.preview_limit,
+                        ));
                     }
                 }
             }
@@ -136,15 +150,41 @@ async fn execute(
                     .await;
             }
             Action::Open(path) => {
-                // TODO: check with mime if suspend/resume is necessary?
-                emitter.suspend();
-                terminal.suspend();
+                // NOTE: detecting the mime type reads the file, thus only done for on_open hooks
+                let command = match &model.lua {
+                    Some(lua) if yeet_lua::has_on_open(lua) => {
+                        let mime = task::detect_mime(&path).await.ok();
+                        yeet_lua::invoke_on_open(lua, &path, mime.as_deref())
+                    }
+                    Some(_) | None => None,
+                };
+
+                let suspend = command.as_ref().is_none_or(|command| command.terminal);
+                if suspend {
+                    emitter.suspend();
+                    terminal.suspend();
+                }
 
                 // TODO: remove flickering (alternate screen leave and cli started)
-                open::path(&path).await?;
+                match &command {
+                    Some(command) => {
+                        if let Err(error) = open::with(command).await {
+                            tracing::error!("failed to start '{}': {:?}", command.program, error);
+                            emitter.run(Task::EmitMessages(vec![Message::Log(
+                                LogSeverity::Error,
+                                format!("Failed to start '{}': {}", command.program, error),
+                            )]));
+                        }
+                    }
+                    None => {
+                        open::path(&path).await?;
+                    }
+                }
 
-                emitter.resume();
-                terminal.resume()?;
+                if suspend {
+                    emitter.resume();
+                    terminal.resume()?;
+                }
             }
             Action::Quit(mode, stdout_result) => {
                 if let Some(stdout_result) = stdout_result {
@@ -165,6 +205,9 @@ async fn execute(
                             tracing::error!("Failed to save history to file: {:?}", error);
                         }
                         history::optimize_history_file()?;
+                        if let Err(error) = journal::save_journal_to_file(&model.state.journal) {
+                            tracing::error!("Failed to save journal to file: {:?}", error);
+                        }
                         if let Err(error) = mark::save_marks_to_file(&model.state.marks) {
                             tracing::error!("Failed to save marks to file: {:?}", error);
                         }
@@ -250,8 +293,43 @@ pub fn preview_content_rect(viewport: &yeet_buffer::model::viewport::ViewPort) -
 mod tests {
     use yeet_buffer::model::viewport::{LineNumber, ViewPort};
 
+    use tokio_util::sync::CancellationToken;
+
+    use crate::model::CurrentTask;
+
     use super::*;
 
+    fn add_task(tasks: &mut Tasks, identifier: &str, kind: TaskKind) -> CancellationToken {
+        let token = CancellationToken::new();
+        tasks.running.insert(
+            identifier.to_string(),
+            CurrentTask {
+                external_id: identifier.to_string(),
+                id: 1,
+                kind,
+                progress: None,
+                token: token.clone(),
+            },
+        );
+        token
+    }
+
+    #[test]
+    fn cancel_preview_tasks_only_cancels_previews() {
+        let mut tasks = Tasks::default();
+        let preview = add_task(
+            &mut tasks,
+            "LoadPreview(\"/a.tar\", 0x0)",
+            TaskKind::Preview,
+        );
+        let copy = add_task(&mut tasks, "CopyPath(\"/a\", \"/b\")", TaskKind::Other);
+
+        cancel_preview_tasks(&tasks);
+
+        assert!(preview.is_cancelled());
+        assert!(!copy.is_cancelled());
+    }
+
     #[test]
     fn preview_rect_equals_viewport_without_offsets() {
         let vp = ViewPort {
@@ -14,6 +14,8 @@ pub enum AppError {
     ExecutionFailed(String),
     #[error("File operation failed")]
     FileOperationFailed(#[from] std::io::Error),
+    #[error("Git operation failed: {0}")]
+    GitOperationFailed(String),
     #[error("Invalid mime type resolved")]
     InvalidMimeType,
     #[error("Invalid state: {0}")]
@@ -22,6 +24,8 @@ pub enum AppError {
     InvalidTargetPath,
     #[error("Loading navigation history failed")]
     LoadHistoryFailed,
+    #[error("Loading journal failed")]
+    LoadJournalFailed,
     #[error("Loading marks failed")]
     LoadMarkFailed,
     #[error("Loading quickfix failed")]
@@ -32,6 +36,8 @@ pub enum AppError {
     PreviewProtocolGenerationFailed,
     #[error("Loading image failed")]
     ImageOperationFailed(#[from] image::ImageError),
+    #[error("Task execution failed")]
+    TaskJoinFailed(#[from] tokio::task::JoinError),
     #[error("Tab with id {0} not found")]
     TabNotFound(usize),
     #[error("Terminal not initialized")]
@@ -21,13 +21,15 @@ use yeet_buffer::model::Mode;
 use yeet_keymap::{
     conversion,
     message::{KeySequence, KeymapMessage},
-    MessageResolver,
+    KeyMap, MessageResolver,
 };
+use yeet_lua::ImageMetadata;
 
 use crate::{
     error::AppError,
     init::junkyard::get_junkyard_path,
-    task::{Task, TaskManager},
+    model::git::GitStatus,
+    task::{Continuation, PreviewCache, Task, TaskKind, TaskManager},
 };
 
 #[derive(Debug)]
@@ -65,22 +67,32 @@ pub enum LogSeverity {
     Information,
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EnumerationEntry {
+    pub name: String,
+    pub link: Option<PathBuf>,
+}
+
 pub enum Message {
     Keymap(KeymapMessage),
-    EnumerationChanged(PathBuf, Vec<String>, Option<String>),
-    EnumerationFinished(PathBuf, Vec<String>, Option<String>),
+    EnumerationChanged(PathBuf, Vec<EnumerationEntry>, Option<String>),
+    EnumerationFinished(PathBuf, Vec<EnumerationEntry>, Option<String>),
     Log(LogSeverity, String),
     FdResult(Vec<PathBuf>),
+    GitStatusLoaded(PathBuf, Option<GitStatus>),
+    EditContentLoaded(PathBuf, String),
     HelpHighlighted(usize, Vec<String>),
+    QuickFixAdd(Vec<PathBuf>),
     QuickFixChanged,
     PathRemoved(PathBuf),
     PathsAdded(Vec<PathBuf>),
-    PreviewLoaded(Preview),
+    PreviewLoaded(Preview, Option<String>),
     Rerender,
     Resize(u16, u16),
     RgResult(Vec<PathBuf>),
-    TaskStarted(String, CancellationToken),
-    TaskEnded(String),
+    TaskStarted(String, TaskKind, CancellationToken),
+    TaskProgress(String, u64, u64),
+    TaskEnded(String, bool),
     ZoxideResult(PathBuf),
 }
 
@@ -96,16 +108,31 @@ impl std::fmt::Debug for Message {
             }
             Message::Log(severity, msg) => write!(f, "Log({:?}, {:?})", severity, msg),
             Message::FdResult(paths) => write!(f, "FdResult({:?})", paths),
+            Message::GitStatusLoaded(path, status) => {
+                let branch = status.as_ref().and_then(|status| status.branch.as_ref());
+                write!(f, "GitStatusLoaded({:?}, {:?})", path, branch)
+            }
+            Message::EditContentLoaded(path, _) => write!(f, "EditContentLoaded({:?})", path),
             Message::HelpHighlighted(id, _) => write!(f, "HelpHighlighted({})", id),
+            Message::QuickFixAdd(paths) => write!(f, "QuickFixAdd({:?})", paths),
             Message::QuickFixChanged => write!(f, "QuickFixChanged"),
             Message::PathRemoved(path) => write!(f, "PathRemoved({:?})", path),
             Message::PathsAdded(paths) => write!(f, "PathsAdded({:?})", paths),
-            Message::PreviewLoaded(preview) => write!(f, "PreviewLoaded({:?})", preview),
+            Message::PreviewLoaded(preview, mime) => {
+                write!(f, "PreviewLoaded({:?}, {:?})", preview, mime)
+            }
             Message::Rerender => write!(f, "Rerender"),
             Message::Resize(x, y) => write!(f, "Resize({}, {})", x, y),
             Message::RgResult(paths) => write!(f, "RgResult({:?})", paths),
-            Message::TaskStarted(identifier, _) => write!(f, "TaskStarted({})", identifier),
-            Message::TaskEnded(identifier) => write!(f, "TaskEnded({})", identifier),
+            Message::TaskStarted(identifier, kind, _) => {
+                write!(f, "TaskStarted({}, {:?})", identifier, kind)
+            }
+            Message::TaskProgress(identifier, done, total) => {
+                write!(f, "TaskProgress({}, {}, {})", identifier, done, total)
+            }
+            Message::TaskEnded(identifier, succeeded) => {
+                write!(f, "TaskEnded({}, {})", identifier, succeeded)
+            }
             Message::ZoxideResult(path) => write!(f, "ZoxideResult({:?})", path),
         }
     }
@@ -113,16 +140,30 @@ impl std::fmt::Debug for Message {
 
 pub enum Preview {
     Content(PathBuf, Vec<String>),
-    Image(PathBuf, Protocol),
+    Continued(PathBuf, Vec<String>, Option<Continuation>),
+    ContinuedMarkdown(PathBuf, Vec<String>, Vec<String>, Option<Continuation>),
+    Hex(PathBuf, Vec<u8>, usize),
+    Image(PathBuf, Protocol, Option<ImageMetadata>),
+    ImageSymbols(PathBuf, Vec<String>, Option<ImageMetadata>),
+    Markdown(PathBuf, Vec<String>, Vec<String>, Option<Continuation>),
     None(PathBuf),
+    Partial(PathBuf, Vec<String>, Continuation),
 }
 
 impl std::fmt::Debug for Preview {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Preview::Content(path, _) => write!(f, "Content({:?})", path),
-            Preview::Image(path, _) => write!(f, "Image({:?})", path),
+            Preview::Continued(path, _, _) => write!(f, "Continued({:?})", path),
+            Preview::ContinuedMarkdown(path, _, _, _) => {
+                write!(f, "ContinuedMarkdown({:?})", path)
+            }
+            Preview::Hex(path, _, _) => write!(f, "Hex({:?})", path),
+            Preview::Image(path, _, _) => write!(f, "Image({:?})", path),
+            Preview::ImageSymbols(path, _, _) => write!(f, "ImageSymbols({:?})", path),
+            Preview::Markdown(path, _, _, _) => write!(f, "Markdown({:?})", path),
             Preview::None(path) => write!(f, "None({:?})", path),
+            Preview::Partial(path, _, _) => write!(f, "Partial({:?})", path),
         }
     }
 }
@@ -133,8 +174,16 @@ impl PartialEq for Preview {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Preview::Content(p1, _), Preview::Content(p2, _)) => p1 == p2,
-            (Preview::Image(p1, _), Preview::Image(p2, _)) => p1 == p2,
+            (Preview::Continued(p1, _, _), Preview::Continued(p2, _, _)) => p1 == p2,
+            (Preview::ContinuedMarkdown(p1, _, _, _), Preview::ContinuedMarkdown(p2, _, _, _)) => {
+                p1 == p2
+            }
+            (Preview::Hex(p1, _, _), Preview::Hex(p2, _, _)) => p1 == p2,
+            (Preview::Image(p1, _, _), Preview::Image(p2, _, _)) => p1 == p2,
+            (Preview::ImageSymbols(p1, _, _), Preview::ImageSymbols(p2, _, _)) => p1 == p2,
+            (Preview::Markdown(p1, _, _, _), Preview::Markdown(p2, _, _, _)) => p1 == p2,
             (Preview::None(p1), Preview::None(p2)) => p1 == p2,
+            (Preview::Partial(p1, _, _), Preview::Partial(p2, _, _)) => p1 == p2,
             _ => false,
         }
     }
@@ -151,7 +200,11 @@ pub struct Emitter {
 }
 
 impl Emitter {
-    pub fn start(cancellation: CancellationToken, syntax_theme_name: String) -> Self {
+    pub fn start(
+        cancellation: CancellationToken,
+        syntax_theme_name: String,
+        keymap: KeyMap,
+    ) -> Self {
         let (sender, receiver) = mpsc::channel(1);
         let internal_sender = sender.clone();
 
@@ -163,7 +216,7 @@ impl Emitter {
         })
         .expect("Failed to create watcher");
 
-        let resolver = Arc::new(Mutex::new(MessageResolver::default()));
+        let resolver = Arc::new(Mutex::new(MessageResolver::new(keymap)));
 
         let (task_sender, mut task_receiver) = mpsc::channel(1);
         let tasks = TaskManager::new(
@@ -172,6 +225,7 @@ impl Emitter {
             cancellation.child_token(),
             syntax_theme_name,
         );
+        let preview_cache = tasks.preview_cache.clone();
 
         tokio::spawn(async move {
             loop {
@@ -181,6 +235,7 @@ impl Emitter {
                 tokio::select! {
                     Some(Ok(event)) = notify_event => {
                         if let Some(messages) = handle_notify_event(event) {
+                            invalidate_preview_cache(&preview_cache, &messages).await;
                             let _ = internal_sender.send(Envelope {
                                 messages,
                                 sequence: KeySequence::None,
@@ -258,6 +313,21 @@ impl Emitter {
     }
 }
 
+async fn invalidate_preview_cache(cache: &Arc<Mutex<PreviewCache>>, messages: &[Message]) {
+    let mut cache = cache.lock().await;
+    for message in messages {
+        match message {
+            Message::PathRemoved(path) => cache.invalidate(path),
+            Message::PathsAdded(paths) => {
+                for path in paths {
+                    cache.invalidate(path);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn start_crossterm_listener(
     cancellation: CancellationToken,
     resolver_mutex: Arc<Mutex<MessageResolver>>,
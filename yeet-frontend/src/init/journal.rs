@@ -0,0 +1,284 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use csv::StringRecord;
+
+use crate::{
+    error::AppError,
+    model::{
+        journal::{Journal, JournalOperation, JournalTransaction},
+        junkyard::{FileEntry, FileEntryStatus},
+    },
+};
+
+#[tracing::instrument]
+pub fn load_journal_from_file(journal: &mut Journal) -> Result<(), AppError> {
+    let journal_path = get_journal_path()?;
+    if !Path::new(&journal_path).exists() {
+        tracing::debug!("journal file does not exist on path {}", journal_path);
+
+        return Ok(());
+    }
+
+    // TODO: change to tokio fs
+    let journal_file = File::open(journal_path)?;
+    read_journal(journal_file, journal)?;
+
+    tracing::trace!("journal file read");
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(journal))]
+pub fn save_journal_to_file(journal: &Journal) -> Result<(), AppError> {
+    let journal_path = get_journal_path()?;
+    let journal_directory = match Path::new(&journal_path).parent() {
+        Some(path) => path,
+        None => return Err(AppError::LoadJournalFailed),
+    };
+
+    fs::create_dir_all(journal_directory)?;
+
+    let journal_writer = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(journal_path)?;
+
+    tracing::trace!("journal file opened for writing");
+
+    write_journal(journal_writer, journal)?;
+
+    tracing::trace!("journal file written");
+
+    Ok(())
+}
+
+fn read_journal(reader: impl Read, journal: &mut Journal) -> Result<(), AppError> {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut current: Option<(String, String)> = None;
+    for result in csv_reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(_) => return Err(AppError::LoadJournalFailed),
+        };
+
+        let (stack, index) = match (record.get(0), record.get(1)) {
+            (Some(stack), Some(index)) => (stack.to_owned(), index.to_owned()),
+            _ => continue,
+        };
+
+        let transactions = match stack.as_str() {
+            "undo" => &mut journal.undo,
+            "redo" => &mut journal.redo,
+            _ => continue,
+        };
+
+        let operation = match to_operation(&record) {
+            Some(it) => it,
+            None => continue,
+        };
+
+        let key = Some((stack, index));
+        match transactions.last_mut() {
+            Some(transaction) if current == key => transaction.operations.push(operation),
+            _ => transactions.push(JournalTransaction {
+                loaded: true,
+                operations: vec![operation],
+            }),
+        }
+        current = key;
+    }
+
+    Ok(())
+}
+
+fn write_journal(writer: impl Write, journal: &Journal) -> Result<(), AppError> {
+    let mut csv_writer = csv::WriterBuilder::new().flexible(true).from_writer(writer);
+
+    let stacks = [("undo", &journal.undo), ("redo", &journal.redo)];
+    for (stack, transactions) in stacks {
+        for (index, transaction) in transactions.iter().enumerate() {
+            let index = index.to_string();
+            for operation in transaction.operations.iter() {
+                let fields = match to_fields(operation) {
+                    Some(it) => it,
+                    None => continue,
+                };
+
+                let mut record = vec![stack, index.as_str()];
+                record.extend(fields);
+                if let Err(error) = csv_writer.write_record(record) {
+                    tracing::error!("writing journal operation failed: {:?}", error);
+                }
+            }
+        }
+    }
+
+    csv_writer.flush()?;
+
+    Ok(())
+}
+
+fn to_fields(operation: &JournalOperation) -> Option<Vec<&str>> {
+    let fields = match operation {
+        JournalOperation::Copy(source, target) => vec!["copy", source.to_str()?, target.to_str()?],
+        JournalOperation::Create(path) => vec!["create", path.to_str()?],
+        JournalOperation::Link(source, target) => vec!["link", source.to_str()?, target.to_str()?],
+        JournalOperation::Rename(source, target) => {
+            vec!["rename", source.to_str()?, target.to_str()?]
+        }
+        JournalOperation::Replace(entry) => vec![
+            "replace",
+            entry.id.as_str(),
+            entry.cache.to_str()?,
+            entry.target.to_str()?,
+        ],
+        JournalOperation::Restore(entry, path) => vec![
+            "restore",
+            entry.id.as_str(),
+            entry.cache.to_str()?,
+            entry.target.to_str()?,
+            path.to_str()?,
+        ],
+        JournalOperation::Symlink(link, target) => {
+            vec!["symlink", link.to_str()?, target.to_str()?]
+        }
+        JournalOperation::Trash(entry) => vec![
+            "trash",
+            entry.id.as_str(),
+            entry.cache.to_str()?,
+            entry.target.to_str()?,
+        ],
+    };
+
+    Some(fields)
+}
+
+fn to_operation(record: &StringRecord) -> Option<JournalOperation> {
+    let path = |index: usize| record.get(index).map(PathBuf::from);
+    let entry = || {
+        Some(FileEntry {
+            id: record.get(3)?.to_owned(),
+            cache: path(4)?,
+            status: FileEntryStatus::default(),
+            target: path(5)?,
+        })
+    };
+
+    let operation = match record.get(2)? {
+        "copy" => JournalOperation::Copy(path(3)?, path(4)?),
+        "create" => JournalOperation::Create(path(3)?),
+        "link" => JournalOperation::Link(path(3)?, path(4)?),
+        "rename" => JournalOperation::Rename(path(3)?, path(4)?),
+        "replace" => JournalOperation::Replace(entry()?),
+        "restore" => JournalOperation::Restore(entry()?, path(6)?),
+        "symlink" => JournalOperation::Symlink(path(3)?, path(4)?),
+        "trash" => JournalOperation::Trash(entry()?),
+        _ => return None,
+    };
+
+    Some(operation)
+}
+
+fn get_journal_path() -> Result<String, AppError> {
+    let cache_dir = match dirs::cache_dir() {
+        Some(cache_dir) => match cache_dir.to_str() {
+            Some(cache_dir_string) => cache_dir_string.to_string(),
+            None => return Err(AppError::LoadJournalFailed),
+        },
+        None => return Err(AppError::LoadJournalFailed),
+    };
+
+    Ok(format!("{}{}", cache_dir, "/yeet/journal"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::model::{
+        journal::{Journal, JournalOperation, JournalTransaction},
+        junkyard::{FileEntry, FileEntryStatus},
+    };
+
+    use super::{read_journal, write_journal};
+
+    fn entry(id: &str) -> FileEntry {
+        FileEntry {
+            id: id.to_owned(),
+            cache: PathBuf::from(format!("/cache/{}", id)),
+            status: FileEntryStatus::default(),
+            target: PathBuf::from("/dir/file"),
+        }
+    }
+
+    #[test]
+    fn journal_survives_write_and_read() {
+        let journal = Journal {
+            undo: vec![
+                JournalTransaction {
+                    loaded: true,
+                    operations: vec![
+                        JournalOperation::Copy(PathBuf::from("/a"), PathBuf::from("/b, c")),
+                        JournalOperation::Create(PathBuf::from("/new/")),
+                    ],
+                },
+                JournalTransaction {
+                    loaded: true,
+                    operations: vec![
+                        JournalOperation::Trash(entry("1%file")),
+                        JournalOperation::Replace(entry("3%file")),
+                        JournalOperation::Restore(entry("2%file"), PathBuf::from("/other")),
+                    ],
+                },
+            ],
+            redo: vec![JournalTransaction {
+                loaded: true,
+                operations: vec![
+                    JournalOperation::Link(PathBuf::from("/a"), PathBuf::from("/b")),
+                    JournalOperation::Rename(PathBuf::from("/c"), PathBuf::from("/d")),
+                    JournalOperation::Symlink(PathBuf::from("../e"), PathBuf::from("/f")),
+                ],
+            }],
+            pending: Vec::new(),
+        };
+
+        let mut content = Vec::new();
+        write_journal(&mut content, &journal).unwrap();
+
+        let mut read = Journal::default();
+        read_journal(content.as_slice(), &mut read).unwrap();
+
+        assert_eq!(read, journal);
+    }
+
+    #[test]
+    fn read_journal_skips_unknown_operations() {
+        let content = "undo,0,create,/a\nundo,0,explode,/b\nundo,1,create,/c\n";
+
+        let mut journal = Journal::default();
+        read_journal(content.as_bytes(), &mut journal).unwrap();
+
+        assert_eq!(
+            journal.undo,
+            vec![
+                JournalTransaction {
+                    loaded: true,
+                    operations: vec![JournalOperation::Create(PathBuf::from("/a"))],
+                },
+                JournalTransaction {
+                    loaded: true,
+                    operations: vec![JournalOperation::Create(PathBuf::from("/c"))],
+                },
+            ]
+        );
+    }
+}
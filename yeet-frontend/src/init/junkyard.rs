@@ -1,6 +1,6 @@
 use std::{
     fs::File,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     time,
 };
 
@@ -12,7 +12,7 @@ use crate::{
     error::AppError,
     event::Emitter,
     model::junkyard::{FileEntry, JunkYard},
-    task::Task,
+    task::{is_cross_device, remove_recursive, ProgressRead, ProgressWrite, Task},
     update::junkyard::add_or_update_junkyard_entry,
 };
 
@@ -25,7 +25,10 @@ pub fn get_junkyard_path() -> Result<PathBuf, AppError> {
     Ok(yard_dir)
 }
 
-pub async fn cache_and_compress(entry: FileEntry) -> Result<(), AppError> {
+pub async fn cache_and_compress(
+    entry: FileEntry,
+    progress: impl FnMut(u64),
+) -> Result<(), AppError> {
     let cache_path = get_junk_cache_path().await?;
 
     let added_at = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
@@ -40,8 +43,17 @@ pub async fn cache_and_compress(entry: FileEntry) -> Result<(), AppError> {
 
     if let Some(file_name) = entry.target.file_name() {
         let target_file = target_path.join(file_name);
-        fs::rename(entry.target, target_file.clone()).await?;
-        compress_with_archive_name(&target_file, &entry.id).await?;
+        match fs::rename(&entry.target, &target_file).await {
+            Ok(()) => compress_with_archive_name(&target_file, &entry.id, progress).await?,
+            Err(error) if is_cross_device(&error) => {
+                compress_with_archive_name(&entry.target, &entry.id, progress).await?;
+                if !get_junk_path().await?.join(&entry.id).exists() {
+                    return Err(AppError::InvalidTargetPath);
+                }
+                remove_recursive(&entry.target)?;
+            }
+            Err(error) => return Err(AppError::FileOperationFailed(error)),
+        }
     }
 
     fs::remove_dir_all(target_path).await?;
@@ -49,8 +61,8 @@ pub async fn cache_and_compress(entry: FileEntry) -> Result<(), AppError> {
     Ok(())
 }
 
-pub async fn compress(entry: FileEntry) -> Result<(), AppError> {
-    compress_with_archive_name(&entry.target, &entry.id).await
+pub async fn compress(entry: FileEntry, progress: impl FnMut(u64)) -> Result<(), AppError> {
+    compress_with_archive_name(&entry.target, &entry.id, progress).await
 }
 
 pub async fn delete(entry: FileEntry) -> Result<(), AppError> {
@@ -76,24 +88,96 @@ pub async fn init_junkyard(junk: &mut JunkYard, emitter: &mut Emitter) -> Result
     Ok(())
 }
 
-pub fn restore(entry: FileEntry, path: PathBuf) -> Result<(), AppError> {
-    let archive_file = File::open(entry.cache)?;
+pub fn restore(
+    entry: FileEntry,
+    target: PathBuf,
+    progress: impl FnMut(u64),
+) -> Result<(), AppError> {
+    let archive_file = ProgressRead::new(File::open(entry.cache)?, progress);
     let archive_decoder = GzDecoder::new(archive_file);
     let mut archive = Archive::new(archive_decoder);
-    archive.unpack(path)?;
+
+    let mut directories = Vec::new();
+    for archive_entry in archive.entries()? {
+        let mut archive_entry = archive_entry?;
+        let path = archive_entry.path()?.into_owned();
+        let destination = match get_restore_destination(&path, &target) {
+            Some(it) => it,
+            None => {
+                tracing::warn!("skipping invalid archive path: {:?}", path);
+                continue;
+            }
+        };
+
+        if archive_entry.header().entry_type().is_dir() {
+            std::fs::create_dir_all(&destination)?;
+            directories.push((archive_entry.header().mode().ok(), destination));
+            continue;
+        }
+
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        archive_entry.unpack(&destination)?;
+    }
+
+    for (mode, directory) in directories.into_iter().rev() {
+        if let Some(mode) = mode {
+            set_mode(&directory, mode)?;
+        }
+    }
 
     Ok(())
 }
 
-async fn compress_with_archive_name(path: &Path, archive_name: &str) -> Result<(), AppError> {
+fn get_restore_destination(path: &Path, target: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    match components.next() {
+        Some(Component::Normal(_)) => {}
+        _ => return None,
+    }
+
+    let mut destination = target.to_path_buf();
+    for component in components {
+        match component {
+            Component::Normal(name) => destination.push(name),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    Some(destination)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_mode(path: &Path, mode: u32) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn set_mode(_path: &Path, _mode: u32) -> Result<(), AppError> {
+    Ok(())
+}
+
+async fn compress_with_archive_name(
+    path: &Path,
+    archive_name: &str,
+    progress: impl FnMut(u64),
+) -> Result<(), AppError> {
     let compress_path = get_junk_compress_path().await?.join(archive_name);
 
     let file = File::create(&compress_path)?;
     let encoder = GzEncoder::new(file, Compression::default());
-    let mut archive = tar::Builder::new(encoder);
+    let mut archive = tar::Builder::new(ProgressWrite::new(encoder, progress));
 
     if let Some(file_name) = path.file_name() {
-        if path.is_dir() {
+        if std::fs::symlink_metadata(path)?.is_symlink() {
+            archive.follow_symlinks(false);
+            archive.append_path_with_name(path, file_name)?;
+        } else if path.is_dir() {
             let archive_directory = format!("{}/", file_name.to_string_lossy());
             archive.append_dir_all(archive_directory, path)?;
         } else {
@@ -139,3 +223,92 @@ async fn get_junk_path() -> Result<PathBuf, AppError> {
     }
     Ok(junk_path)
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::{self, File},
+        path::{Path, PathBuf},
+    };
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use crate::model::junkyard::{FileEntry, FileEntryStatus};
+
+    use super::{get_restore_destination, restore};
+
+    #[test]
+    fn get_restore_destination_replaces_archived_name() {
+        let target = Path::new("/dir/file (1)");
+
+        assert_eq!(
+            get_restore_destination(Path::new("file/nested/a.txt"), target),
+            Some(PathBuf::from("/dir/file (1)/nested/a.txt"))
+        );
+        assert_eq!(
+            get_restore_destination(Path::new("../escape"), target),
+            None
+        );
+        assert_eq!(
+            get_restore_destination(Path::new("file/../escape"), target),
+            None
+        );
+    }
+
+    #[test]
+    fn restore_unpacks_archive_into_target_name() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("folder");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/file.txt"), "content").unwrap();
+
+        let archive_path = dir.path().join("archive");
+        let encoder = GzEncoder::new(File::create(&archive_path).unwrap(), Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        archive.append_dir_all("folder/", &source).unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+
+        let entry = FileEntry {
+            id: "archive".to_string(),
+            cache: archive_path,
+            status: FileEntryStatus::Ready,
+            target: source.clone(),
+        };
+        let target = dir.path().join("folder (1)");
+
+        restore(entry, target.clone(), |_| {}).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join("nested/file.txt")).unwrap(),
+            "content"
+        );
+        assert!(source.exists());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn restore_keeps_symlinks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink("../target", &link).unwrap();
+
+        let archive_path = dir.path().join("archive");
+        let encoder = GzEncoder::new(File::create(&archive_path).unwrap(), Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        archive.follow_symlinks(false);
+        archive.append_path_with_name(&link, "link").unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+
+        let entry = FileEntry {
+            id: "archive".to_string(),
+            cache: archive_path,
+            status: FileEntryStatus::Ready,
+            target: link,
+        };
+        let target = dir.path().join("restored");
+
+        restore(entry, target.clone(), |_| {}).unwrap();
+
+        assert_eq!(fs::read_link(target).unwrap(), PathBuf::from("../target"));
+    }
+}
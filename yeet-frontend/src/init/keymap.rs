@@ -0,0 +1,159 @@
+use yeet_buffer::model::{CommandMode, Mode, SearchDirection};
+use yeet_keymap::{
+    conversion,
+    message::{Binding, BindingKind, KeymapMessage},
+    KeyMap,
+};
+use yeet_lua::{KeymapAction, KeymapChange, LuaConfiguration};
+
+pub fn load_keymap(lua: Option<&LuaConfiguration>) -> KeyMap {
+    let mut keymap = KeyMap::default();
+    if let Some(lua) = lua {
+        apply_changes(&mut keymap, yeet_lua::read_keymap_changes(lua));
+    }
+    keymap
+}
+
+fn apply_changes(keymap: &mut KeyMap, changes: Vec<KeymapChange>) {
+    for change in changes {
+        let (mode, keys, action) = match change {
+            KeymapChange::Delete(mode, keys) => (mode, keys, None),
+            KeymapChange::Set(mode, keys, action) => (mode, keys, Some(action)),
+        };
+
+        let Some(modes) = get_modes(&mode) else {
+            tracing::error!("invalid keymap mode '{}', ignoring mapping", mode);
+            continue;
+        };
+
+        let keys: Vec<_> = conversion::from_keycode_string(&keys).into_iter().collect();
+        if keys.is_empty() {
+            tracing::error!("empty key sequence for mode '{}', ignoring mapping", mode);
+            continue;
+        }
+
+        for mode in modes {
+            match &action {
+                Some(action) => {
+                    let message = match action {
+                        KeymapAction::Command(command) => {
+                            KeymapMessage::ExecuteCommandString(command.clone())
+                        }
+                        KeymapAction::Function(id) => KeymapMessage::ExecuteLuaFunction(*id),
+                        KeymapAction::KeySequence(sequence) => {
+                            KeymapMessage::ExecuteKeySequence(sequence.clone())
+                        }
+                    };
+
+                    let binding = Binding {
+                        kind: BindingKind::Message(message),
+                        ..Default::default()
+                    };
+                    keymap.set(mode, keys.clone(), binding);
+                }
+                None => keymap.delete(&mode, &keys),
+            }
+        }
+    }
+}
+
+fn get_modes(mode: &str) -> Option<Vec<Mode>> {
+    let modes = match mode {
+        "command" => vec![
+            Mode::Command(CommandMode::Command),
+            Mode::Command(CommandMode::Search(SearchDirection::Up)),
+            Mode::Command(CommandMode::Search(SearchDirection::Down)),
+        ],
+        "insert" => vec![Mode::Insert],
+        "navigation" => vec![Mode::Navigation],
+        "normal" => vec![Mode::Normal],
+        "visual" => vec![Mode::Visual],
+        _ => return None,
+    };
+
+    Some(modes)
+}
+
+#[cfg(test)]
+mod test {
+    use yeet_buffer::{
+        message::{BufferMessage, CursorDirection},
+        model::Mode,
+    };
+    use yeet_keymap::{
+        conversion,
+        message::{KeySequence, KeymapMessage},
+        KeyMap, MessageResolver,
+    };
+    use yeet_lua::{KeymapAction, KeymapChange};
+
+    use super::apply_changes;
+
+    fn resolve(changes: Vec<KeymapChange>, mode: Mode, keys: &str) -> Vec<KeymapMessage> {
+        let mut keymap = KeyMap::default();
+        apply_changes(&mut keymap, changes);
+
+        let mut resolver = MessageResolver::new(keymap);
+        resolver.mode = mode;
+        match resolver.add_keys(conversion::from_keycode_string(keys)) {
+            Some((messages, KeySequence::Completed(_))) => messages,
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn apply_changes_overrides_default_binding() {
+        let changes = vec![KeymapChange::Set(
+            "navigation".to_string(),
+            "gt".to_string(),
+            KeymapAction::Command("tabp".to_string()),
+        )];
+
+        assert_eq!(
+            resolve(changes, Mode::Navigation, "gt"),
+            vec![KeymapMessage::ExecuteCommandString("tabp".to_string())]
+        );
+    }
+
+    #[test]
+    fn apply_changes_maps_functions_and_key_sequences() {
+        let changes = vec![
+            KeymapChange::Set(
+                "normal".to_string(),
+                "<C-x>".to_string(),
+                KeymapAction::Function(3),
+            ),
+            KeymapChange::Set(
+                "normal".to_string(),
+                "H".to_string(),
+                KeymapAction::KeySequence("0".to_string()),
+            ),
+        ];
+
+        assert_eq!(
+            resolve(changes.clone(), Mode::Normal, "<C-x>"),
+            vec![KeymapMessage::ExecuteLuaFunction(3)]
+        );
+        assert_eq!(
+            resolve(changes, Mode::Normal, "H"),
+            vec![KeymapMessage::Buffer(BufferMessage::MoveCursor(
+                1,
+                CursorDirection::LineStart
+            ))]
+        );
+    }
+
+    #[test]
+    fn apply_changes_deletes_binding_and_skips_invalid_modes() {
+        let changes = vec![
+            KeymapChange::Delete("navigation".to_string(), "gt".to_string()),
+            KeymapChange::Set(
+                "unknown".to_string(),
+                "gt".to_string(),
+                KeymapAction::Command("tabn".to_string()),
+            ),
+        ];
+
+        assert!(resolve(changes, Mode::Navigation, "gt").is_empty());
+    }
+}
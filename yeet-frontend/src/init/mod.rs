@@ -1,4 +1,6 @@
 pub mod history;
+pub mod journal;
 pub mod junkyard;
+pub mod keymap;
 pub mod mark;
 pub mod qfix;
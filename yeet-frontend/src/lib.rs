@@ -4,8 +4,8 @@ use action::{Action, ActionResult};
 use error::AppError;
 use event::{Emitter, Message, MessageSource};
 use init::{
-    history::load_history_from_file, junkyard::init_junkyard, mark::load_marks_from_file,
-    qfix::load_qfix_from_files,
+    history::load_history_from_file, journal::load_journal_from_file, junkyard::init_junkyard,
+    keymap::load_keymap, mark::load_marks_from_file, qfix::load_qfix_from_files,
 };
 use model::{qfix::CdoState, App, Model};
 use settings::Settings;
@@ -40,6 +40,7 @@ pub async fn run(
     let mut emitter = Emitter::start(
         cancellation.child_token(),
         settings.theme.syntax_theme.clone(),
+        load_keymap(lua.as_ref()),
     );
 
     let initial_path = get_initial_path(&settings.startup_path);
@@ -72,6 +73,14 @@ pub async fn run(
         )]));
     }
 
+    if load_journal_from_file(&mut model.state.journal).is_err() {
+        emitter.run(Task::EmitMessages(vec![Message::Keymap(
+            KeymapMessage::Print(vec![PrintContent::Error(
+                "Failed to load journal".to_string(),
+            )]),
+        )]));
+    }
+
     if load_marks_from_file(&mut model.state.marks).is_err() {
         emitter.run(Task::EmitMessages(vec![Message::Keymap(
             KeymapMessage::Print(vec![PrintContent::Error(
@@ -198,7 +207,10 @@ fn get_watcher_changes(app: &App, watches: &mut Vec<PathBuf>) -> Vec<Action> {
 
 #[tracing::instrument(skip(model, emitter))]
 fn get_command_from_stack(model: &mut Model, emitter: &Emitter, actions: &[Action]) -> Vec<Action> {
-    if model.state.remaining_keysequence.is_none() && model.state.qfix.cdo == CdoState::None {
+    if model.state.remaining_keysequence.is_none()
+        && model.state.pending_messages.is_empty()
+        && model.state.qfix.cdo == CdoState::None
+    {
         return Vec::new();
     }
 
@@ -237,6 +249,10 @@ fn get_command_from_stack(model: &mut Model, emitter: &Emitter, actions: &[Actio
         };
     }
 
+    if !model.state.pending_messages.is_empty() {
+        return update::api::take_pending_messages(&mut model.state.pending_messages);
+    }
+
     let (next_state, actions) = match &model.state.qfix.cdo {
         CdoState::Cnext(command) => (
             CdoState::Cdo(Some(model.state.qfix.current_index), command.to_owned()),
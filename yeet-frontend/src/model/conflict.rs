@@ -0,0 +1,24 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use super::junkyard::FileEntry;
+
+#[derive(Debug, Default)]
+pub struct Conflicts {
+    pub pending: Vec<Transfer>,
+    pub reserved: HashSet<PathBuf>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfer {
+    pub source: TransferSource,
+    pub target: PathBuf,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransferSource {
+    Copy(PathBuf),
+    Link(PathBuf),
+    Move(PathBuf),
+    Restore(FileEntry),
+    Symlink(PathBuf),
+}
@@ -0,0 +1,54 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use yeet_buffer::model::SignIdentifier;
+
+pub const GIT_CONFLICTED_SIGN_ID: SignIdentifier = "git_conflicted";
+pub const GIT_IGNORED_SIGN_ID: SignIdentifier = "git_ignored";
+pub const GIT_MODIFIED_SIGN_ID: SignIdentifier = "git_modified";
+pub const GIT_STAGED_SIGN_ID: SignIdentifier = "git_staged";
+pub const GIT_UNTRACKED_SIGN_ID: SignIdentifier = "git_untracked";
+
+pub const GIT_SIGN_IDS: [SignIdentifier; 5] = [
+    GIT_CONFLICTED_SIGN_ID,
+    GIT_IGNORED_SIGN_ID,
+    GIT_MODIFIED_SIGN_ID,
+    GIT_STAGED_SIGN_ID,
+    GIT_UNTRACKED_SIGN_ID,
+];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum GitEntryStatus {
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+impl GitEntryStatus {
+    pub fn sign_id(&self) -> SignIdentifier {
+        match self {
+            GitEntryStatus::Conflicted => GIT_CONFLICTED_SIGN_ID,
+            GitEntryStatus::Ignored => GIT_IGNORED_SIGN_ID,
+            GitEntryStatus::Modified => GIT_MODIFIED_SIGN_ID,
+            GitEntryStatus::Staged => GIT_STAGED_SIGN_ID,
+            GitEntryStatus::Untracked => GIT_UNTRACKED_SIGN_ID,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GitStatus {
+    pub branch: Option<String>,
+    pub entries: HashMap<PathBuf, GitEntryStatus>,
+    pub repository: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct GitRepositories {
+    pub outdated: HashSet<PathBuf>,
+    pub roots: HashMap<PathBuf, Option<PathBuf>>,
+}
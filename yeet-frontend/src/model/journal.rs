@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use super::junkyard::FileEntry;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Journal {
+    pub undo: Vec<JournalTransaction>,
+    pub redo: Vec<JournalTransaction>,
+    pub pending: Vec<PendingTransaction>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalTransaction {
+    pub loaded: bool,
+    pub operations: Vec<JournalOperation>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalOperation {
+    Copy(PathBuf, PathBuf),
+    Create(PathBuf),
+    Link(PathBuf, PathBuf),
+    Rename(PathBuf, PathBuf),
+    Replace(FileEntry),
+    Restore(FileEntry, PathBuf),
+    Symlink(PathBuf, PathBuf),
+    Trash(FileEntry),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransaction {
+    pub deferred: Vec<FileEntry>,
+    pub kind: PendingKind,
+    pub open: bool,
+    pub tasks: Vec<PendingTask>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PendingKind {
+    Record,
+    Redo,
+    Undo(JournalTransaction),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTask {
+    pub identifier: String,
+    pub operations: Vec<JournalOperation>,
+    pub status: PendingStatus,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PendingStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
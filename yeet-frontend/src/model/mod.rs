@@ -1,20 +1,39 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Display,
     path::{Path, PathBuf},
+    time::Instant,
 };
 
-use crate::{error::AppError, settings::Settings};
+use crate::{
+    error::AppError,
+    event::Message,
+    settings::Settings,
+    task::{self, Continuation, TaskKind},
+};
 use ratatui_image::protocol::Protocol;
 use tokio_util::sync::CancellationToken;
 use yeet_buffer::model::{
     viewport::{LineNumber, ViewPort},
-    Cursor, Mode, TextBuffer,
+    BufferLine, Cursor, Mode, TextBuffer,
+};
+use yeet_lua::{ImageMetadata, LuaConfiguration};
+
+use self::{
+    conflict::Conflicts,
+    git::{GitRepositories, GitStatus},
+    history::History,
+    journal::Journal,
+    junkyard::JunkYard,
+    mark::Marks,
+    qfix::QuickFix,
+    register::Register,
 };
-use yeet_lua::LuaConfiguration;
-
-use self::{history::History, junkyard::JunkYard, mark::Marks, qfix::QuickFix, register::Register};
 
+pub mod conflict;
+pub mod git;
 pub mod history;
+pub mod journal;
 pub mod junkyard;
 pub mod mark;
 pub mod qfix;
@@ -124,7 +143,9 @@ pub enum Window {
         second: Box<Window>,
         focus: SplitFocus,
     },
+    Diff(ViewPort),
     Directory(ViewPort, ViewPort, ViewPort),
+    Edit(ViewPort),
     Help(ViewPort),
     QuickFix(ViewPort),
     Tasks(ViewPort),
@@ -171,7 +192,9 @@ impl Window {
                 SplitFocus::First => first.focused_viewport(),
                 SplitFocus::Second => second.focused_viewport(),
             },
-            Window::Directory(_, vp, _)
+            Window::Diff(vp)
+            | Window::Directory(_, vp, _)
+            | Window::Edit(vp)
             | Window::Help(vp)
             | Window::QuickFix(vp)
             | Window::Tasks(vp) => vp,
@@ -193,15 +216,20 @@ impl Window {
                 SplitFocus::First => first.focused_window_mut(),
                 SplitFocus::Second => second.focused_window_mut(),
             },
-            Window::Directory(..) | Window::Help(_) | Window::QuickFix(_) | Window::Tasks(_) => {
-                self
-            }
+            Window::Diff(_)
+            | Window::Directory(..)
+            | Window::Edit(_)
+            | Window::Help(_)
+            | Window::QuickFix(_)
+            | Window::Tasks(_) => self,
         }
     }
 
     pub fn focused_viewport_mut(&mut self) -> &mut ViewPort {
         match self.focused_window_mut() {
-            Window::Directory(_, vp, _)
+            Window::Diff(vp)
+            | Window::Directory(_, vp, _)
+            | Window::Edit(vp)
             | Window::Help(vp)
             | Window::QuickFix(vp)
             | Window::Tasks(vp) => vp,
@@ -221,9 +249,27 @@ impl Window {
             Window::Directory(parent, current, preview) => {
                 HashSet::from([parent.buffer_id, current.buffer_id, preview.buffer_id])
             }
-            Window::Help(vp) | Window::QuickFix(vp) | Window::Tasks(vp) => {
-                HashSet::from([vp.buffer_id])
+            Window::Diff(vp)
+            | Window::Edit(vp)
+            | Window::Help(vp)
+            | Window::QuickFix(vp)
+            | Window::Tasks(vp) => HashSet::from([vp.buffer_id]),
+        }
+    }
+
+    pub fn preview_viewports(&self) -> Vec<&ViewPort> {
+        match self {
+            Window::Horizontal { first, second, .. } | Window::Vertical { first, second, .. } => {
+                let mut viewports = first.preview_viewports();
+                viewports.extend(second.preview_viewports());
+                viewports
             }
+            Window::Directory(_, _, preview) => vec![preview],
+            Window::Diff(_)
+            | Window::Edit(_)
+            | Window::Help(_)
+            | Window::QuickFix(_)
+            | Window::Tasks(_) => Vec::new(),
         }
     }
 
@@ -232,7 +278,11 @@ impl Window {
             Window::Horizontal { first, second, .. } | Window::Vertical { first, second, .. } => {
                 first.contains_tasks() || second.contains_tasks()
             }
-            Window::Directory(_, _, _) | Window::Help(_) | Window::QuickFix(_) => false,
+            Window::Diff(_)
+            | Window::Directory(_, _, _)
+            | Window::Edit(_)
+            | Window::Help(_)
+            | Window::QuickFix(_) => false,
             Window::Tasks(_) => true,
         }
     }
@@ -266,6 +316,64 @@ impl Window {
         }
     }
 
+    // NOTE: the window must contain the buffer, otherwise the last leaf of the split is closed
+    #[allow(clippy::result_large_err)]
+    pub fn close_buffer(self, buffer_id: usize) -> Result<(Window, Window), Window> {
+        match self {
+            Window::Horizontal {
+                first,
+                second,
+                focus,
+            } => Self::close_buffer_in_split(*first, *second, focus, buffer_id, |f, s, focus| {
+                Window::Horizontal {
+                    first: Box::new(f),
+                    second: Box::new(s),
+                    focus,
+                }
+            }),
+            Window::Vertical {
+                first,
+                second,
+                focus,
+            } => Self::close_buffer_in_split(*first, *second, focus, buffer_id, |f, s, focus| {
+                Window::Vertical {
+                    first: Box::new(f),
+                    second: Box::new(s),
+                    focus,
+                }
+            }),
+            leaf => Err(leaf),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn close_buffer_in_split(
+        first: Window,
+        second: Window,
+        focus: SplitFocus,
+        buffer_id: usize,
+        rebuild: impl FnOnce(Window, Window, SplitFocus) -> Window,
+    ) -> Result<(Window, Window), Window> {
+        let closing_is_first = first.buffer_ids().contains(&buffer_id);
+        let (closing, sibling) = if closing_is_first {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        match closing.close_buffer(buffer_id) {
+            Ok((kept, dropped)) => {
+                let (new_first, new_second) = if closing_is_first {
+                    (kept, sibling)
+                } else {
+                    (sibling, kept)
+                };
+                Ok((rebuild(new_first, new_second, focus), dropped))
+            }
+            Err(leaf) => Ok((sibling, leaf)),
+        }
+    }
+
     #[allow(clippy::result_large_err)]
     fn close_focused_in_split(
         first: Window,
@@ -298,7 +406,11 @@ impl Window {
                 current.wrap = wrap;
                 preview.wrap = wrap;
             }
-            Window::Help(vp) | Window::QuickFix(vp) | Window::Tasks(vp) => {
+            Window::Diff(vp)
+            | Window::Edit(vp)
+            | Window::Help(vp)
+            | Window::QuickFix(vp)
+            | Window::Tasks(vp) => {
                 vp.wrap = wrap;
             }
             Window::Horizontal { .. } | Window::Vertical { .. } => {
@@ -312,7 +424,11 @@ impl Window {
             Window::Horizontal { first, second, .. } | Window::Vertical { first, second, .. } => {
                 first.contains_quickfix() || second.contains_quickfix()
             }
-            Window::Directory(_, _, _) | Window::Help(_) | Window::Tasks(_) => false,
+            Window::Diff(_)
+            | Window::Directory(_, _, _)
+            | Window::Edit(_)
+            | Window::Help(_)
+            | Window::Tasks(_) => false,
             Window::QuickFix(_) => true,
         }
     }
@@ -326,7 +442,10 @@ impl Default for Window {
 
 #[derive(Default)]
 pub struct State {
+    pub conflicts: Conflicts,
+    pub git: GitRepositories,
     pub history: History,
+    pub journal: Journal,
     pub junk: JunkYard,
     pub marks: Marks,
     pub modes: ModeState,
@@ -334,7 +453,9 @@ pub struct State {
     pub qfix: QuickFix,
     pub register: Register,
     pub remaining_keysequence: Option<String>,
+    pub pending_messages: Vec<Message>,
     pub pending_path_events: Vec<PendingPathEvent>,
+    pub show_raw_markdown: bool,
     pub tasks: Tasks,
     pub watches: Vec<PathBuf>,
 }
@@ -364,13 +485,124 @@ pub struct Tasks {
     pub running: HashMap<String, CurrentTask>,
 }
 
+impl Tasks {
+    pub fn progress(&self) -> Option<TaskProgress> {
+        self.running
+            .values()
+            .filter_map(|task| task.progress.as_ref())
+            .cloned()
+            .reduce(|aggregate, progress| TaskProgress {
+                done: aggregate.done + progress.done.min(progress.total),
+                started: aggregate.started.min(progress.started),
+                total: aggregate.total + progress.total,
+            })
+    }
+}
+
 #[derive(Debug)]
 pub struct CurrentTask {
     pub external_id: String,
     pub id: u16,
+    pub kind: TaskKind,
+    pub progress: Option<TaskProgress>,
     pub token: CancellationToken,
 }
 
+impl Display for CurrentTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.progress {
+            Some(progress) => write!(f, "{:<4} {} {}", self.id, self.external_id, progress),
+            None => write!(f, "{:<4} {}", self.id, self.external_id),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TaskProgress {
+    pub done: u64,
+    pub started: Instant,
+    pub total: u64,
+}
+
+impl TaskProgress {
+    pub fn percent(&self) -> u64 {
+        if self.total == 0 {
+            return 100;
+        }
+
+        (self.done.min(self.total) as u128 * 100 / self.total as u128) as u64
+    }
+
+    pub fn throughput(&self) -> u64 {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0;
+        }
+
+        (self.done as f64 / elapsed) as u64
+    }
+}
+
+impl Display for TaskProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}% {}/{} {}/s",
+            self.percent(),
+            format_bytes(self.done.min(self.total)),
+            format_bytes(self.total),
+            format_bytes(self.throughput())
+        )
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+pub fn get_image_metadata_lines(metadata: &ImageMetadata) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{}  {}x{}  {} ({} bit)  {}",
+        metadata.format,
+        metadata.width,
+        metadata.height,
+        metadata.color_type,
+        metadata.bits_per_pixel,
+        format_bytes(metadata.size)
+    )];
+
+    if let Some(camera) = &metadata.camera {
+        lines.push(format!("Camera: {}", camera));
+    }
+    if let Some(date) = &metadata.date {
+        lines.push(format!("Date: {}", date));
+    }
+    if let Some(orientation) = &metadata.orientation {
+        lines.push(format!("Orientation: {}", orientation));
+    }
+    lines
+}
+
+pub fn get_image_panel_height(metadata: Option<&ImageMetadata>) -> u16 {
+    match metadata {
+        Some(metadata) => get_image_metadata_lines(metadata).len() as u16 + 1,
+        None => 0,
+    }
+}
+
 pub struct CommandLine {
     pub buffer: TextBuffer,
     pub key_sequence: String,
@@ -394,7 +626,9 @@ impl Default for CommandLine {
 }
 
 pub enum Buffer {
+    Diff(DiffBuffer),
     Directory(DirectoryBuffer),
+    Edit(EditBuffer),
     Image(PreviewImageBuffer),
     Content(ContentBuffer),
     Help(HelpBuffer),
@@ -407,7 +641,9 @@ pub enum Buffer {
 impl Buffer {
     pub fn buffer_type_for_lua(&self) -> &'static str {
         match self {
+            Buffer::Diff(_) => "diff",
             Buffer::Directory(_) => "directory",
+            Buffer::Edit(_) => "edit",
             Buffer::Content(_) => "content",
             Buffer::Image(_) => "image",
             Buffer::Help(_) => "help",
@@ -418,10 +654,41 @@ impl Buffer {
         }
     }
 
+    pub fn mime(&self) -> Option<&str> {
+        match self {
+            Buffer::Directory(_) => Some(task::MIME_DIRECTORY),
+            Buffer::Content(it) => it.mime.as_deref(),
+            Buffer::Image(it) => it.mime.as_deref(),
+            Buffer::Diff(_)
+            | Buffer::Edit(_)
+            | Buffer::Help(_)
+            | Buffer::QuickFix(_)
+            | Buffer::Tasks(_)
+            | Buffer::PathReference(_)
+            | Buffer::Empty => None,
+        }
+    }
+
+    pub fn image_metadata(&self) -> Option<&ImageMetadata> {
+        match self {
+            Buffer::Content(it) => it.image_metadata.as_deref(),
+            Buffer::Image(it) => it.image_metadata.as_deref(),
+            Buffer::Diff(_)
+            | Buffer::Directory(_)
+            | Buffer::Edit(_)
+            | Buffer::Help(_)
+            | Buffer::QuickFix(_)
+            | Buffer::Tasks(_)
+            | Buffer::PathReference(_)
+            | Buffer::Empty => None,
+        }
+    }
+
     pub fn resolve_path(&self) -> Option<&Path> {
         match self {
             Buffer::Directory(it) => it.resolve_path(),
             Buffer::Content(it) => it.resolve_path(),
+            Buffer::Edit(it) => it.resolve_path(),
             Buffer::Image(it) => it.resolve_path(),
             Buffer::PathReference(path) => {
                 if path.as_os_str().is_empty() {
@@ -430,9 +697,56 @@ impl Buffer {
                     Some(path.as_path())
                 }
             }
-            Buffer::Help(_) | Buffer::QuickFix(_) | Buffer::Tasks(_) | Buffer::Empty => None,
+            Buffer::Diff(_)
+            | Buffer::Help(_)
+            | Buffer::QuickFix(_)
+            | Buffer::Tasks(_)
+            | Buffer::Empty => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DiffBuffer {
+    pub buffer: TextBuffer,
+    pub target: usize,
+}
+
+#[derive(Default)]
+pub struct EditBuffer {
+    pub buffer: TextBuffer,
+    pub close_after_save: bool,
+    pub crlf: bool,
+    pub path: PathBuf,
+    pub saving: Option<String>,
+    pub trailing_newline: bool,
+}
+
+impl EditBuffer {
+    pub fn resolve_path(&self) -> Option<&Path> {
+        if self.path.as_os_str().is_empty() {
+            None
+        } else {
+            Some(self.path.as_path())
         }
     }
+
+    pub fn to_file_content(&self) -> String {
+        let line_ending = if self.crlf { "\r\n" } else { "\n" };
+        let mut content = self
+            .buffer
+            .lines
+            .iter()
+            .map(|line| line.content.to_stripped_string())
+            .collect::<Vec<_>>()
+            .join(line_ending);
+
+        if self.trailing_newline {
+            content.push_str(line_ending);
+        }
+
+        content
+    }
 }
 
 #[derive(Default)]
@@ -454,6 +768,10 @@ pub struct TasksBuffer {
 pub struct ContentBuffer {
     pub path: PathBuf,
     pub buffer: TextBuffer,
+    pub continuation: Option<Continuation>,
+    pub mime: Option<String>,
+    pub alternate_lines: Option<Vec<BufferLine>>,
+    pub image_metadata: Option<Box<ImageMetadata>>,
 }
 
 impl ContentBuffer {
@@ -469,6 +787,8 @@ impl ContentBuffer {
 pub struct PreviewImageBuffer {
     pub path: PathBuf,
     pub protocol: Protocol,
+    pub mime: Option<String>,
+    pub image_metadata: Option<Box<ImageMetadata>>,
 }
 
 impl PreviewImageBuffer {
@@ -484,6 +804,7 @@ impl PreviewImageBuffer {
 #[derive(Default)]
 pub struct DirectoryBuffer {
     pub buffer: TextBuffer,
+    pub git: GitStatus,
     pub path: PathBuf,
     pub state: DirectoryBufferState,
 }
@@ -540,6 +861,85 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn edit_buffer_to_file_content_keeps_trailing_newline() {
+        let mut buffer = EditBuffer {
+            buffer: TextBuffer::from_lines(vec![BufferLine::from("a"), BufferLine::from("b")]),
+            close_after_save: false,
+            crlf: false,
+            path: PathBuf::from("/tmp/a.txt"),
+            saving: None,
+            trailing_newline: true,
+        };
+        assert_eq!(buffer.to_file_content(), "a\nb\n");
+
+        buffer.trailing_newline = false;
+        assert_eq!(buffer.to_file_content(), "a\nb");
+    }
+
+    #[test]
+    fn edit_buffer_to_file_content_keeps_crlf_line_endings() {
+        let buffer = EditBuffer {
+            buffer: TextBuffer::from_lines(vec![BufferLine::from("a"), BufferLine::from("b")]),
+            close_after_save: false,
+            crlf: true,
+            path: PathBuf::from("/tmp/a.txt"),
+            saving: None,
+            trailing_newline: true,
+        };
+        assert_eq!(buffer.to_file_content(), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn get_image_metadata_lines_skips_missing_exif_fields() {
+        let metadata = ImageMetadata {
+            width: 4000,
+            height: 3000,
+            format: "JPEG".to_string(),
+            color_type: "Rgb8".to_string(),
+            bits_per_pixel: 24,
+            size: 2 * 1024 * 1024,
+            camera: Some("Canon EOS R6".to_string()),
+            date: None,
+            orientation: Some("rotated 90° clockwise".to_string()),
+        };
+
+        assert_eq!(
+            get_image_metadata_lines(&metadata),
+            vec![
+                "JPEG  4000x3000  Rgb8 (24 bit)  2.0 MiB",
+                "Camera: Canon EOS R6",
+                "Orientation: rotated 90° clockwise",
+            ]
+        );
+        assert_eq!(get_image_panel_height(Some(&metadata)), 4);
+        assert_eq!(get_image_panel_height(None), 0);
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn task_progress_percent_is_clamped() {
+        let progress = TaskProgress {
+            done: 150,
+            started: Instant::now(),
+            total: 100,
+        };
+        assert_eq!(progress.percent(), 100);
+
+        let progress = TaskProgress {
+            done: 25,
+            started: Instant::now(),
+            total: 100,
+        };
+        assert_eq!(progress.percent(), 25);
+    }
+
     #[test]
     fn split_focus_default_is_first() {
         assert_eq!(SplitFocus::default(), SplitFocus::First);
@@ -927,6 +1327,40 @@ mod test {
         assert!(!ids.contains(&20));
     }
 
+    #[test]
+    fn close_buffer_closes_unfocused_window_and_keeps_focus() {
+        let tree = Window::Horizontal {
+            first: Box::new(Window::Vertical {
+                first: Box::new(Window::Edit(ViewPort {
+                    buffer_id: 10,
+                    ..Default::default()
+                })),
+                second: Box::new(Window::Tasks(ViewPort {
+                    buffer_id: 20,
+                    ..Default::default()
+                })),
+                focus: SplitFocus::Second,
+            }),
+            second: Box::new(Window::Directory(
+                ViewPort::default(),
+                ViewPort {
+                    buffer_id: 30,
+                    ..Default::default()
+                },
+                ViewPort::default(),
+            )),
+            focus: SplitFocus::Second,
+        };
+        let (kept, dropped) = tree.close_buffer(10).ok().unwrap();
+        assert!(matches!(dropped, Window::Edit(_)));
+        assert!(matches!(
+            &kept,
+            Window::Horizontal { first, focus: SplitFocus::Second, .. }
+                if matches!(first.as_ref(), Window::Tasks(_))
+        ));
+        assert_eq!(kept.focused_viewport().buffer_id, 30);
+    }
+
     #[test]
     fn close_focused_leaf_returns_err() {
         let leaf = Window::Directory(
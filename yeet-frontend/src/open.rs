@@ -2,10 +2,36 @@ use std::{
     ffi::{OsStr, OsString},
     io,
     path::Path,
-    process::ExitStatus,
+    process::{ExitStatus, Stdio},
 };
 
 use tokio::process::Command;
+use yeet_lua::OpenCommand;
+
+pub async fn with(command: &OpenCommand) -> Result<Option<ExitStatus>, io::Error> {
+    let mut cmd = Command::new(&command.program);
+    cmd.args(&command.args);
+
+    if command.terminal {
+        return cmd.spawn()?.wait().await.map(Some);
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // NOTE: the program keeps running detached, waiting in the background reaps it on exit
+    let program = command.program.clone();
+    tokio::spawn(async move {
+        if let Err(error) = child.wait().await {
+            tracing::error!("failed to wait for '{}': {:?}", program, error);
+        }
+    });
+
+    Ok(None)
+}
 
 #[cfg(all(unix, not(target_os = "macos")))]
 pub async fn path(path: &Path) -> Result<ExitStatus, io::Error> {
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
 use yeet_buffer::model::viewport::WindowSettings;
 
@@ -6,9 +6,11 @@ use crate::theme::Theme;
 
 #[derive(Debug)]
 pub struct Settings {
+    pub conflict_policy: ConflictPolicy,
     pub current: WindowSettings,
     pub parent: WindowSettings,
     pub preview: WindowSettings,
+    pub preview_limit: PreviewLimit,
     pub plugin_concurrency: usize,
     pub selection_to_file_on_open: Option<PathBuf>,
     pub selection_to_stdout_on_open: bool,
@@ -21,12 +23,14 @@ pub struct Settings {
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            conflict_policy: ConflictPolicy::default(),
             current: WindowSettings {
                 sign_column_width: 2,
             },
             parent: WindowSettings::default(),
             plugin_concurrency: 4,
             preview: WindowSettings::default(),
+            preview_limit: PreviewLimit::default(),
             selection_to_file_on_open: None,
             selection_to_stdout_on_open: false,
             show_mark_signs: true,
@@ -36,3 +40,41 @@ impl Default for Settings {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PreviewLimit {
+    pub bytes: usize,
+    pub lines: usize,
+}
+
+impl Default for PreviewLimit {
+    fn default() -> Self {
+        Self {
+            bytes: 1024 * 1024,
+            lines: 1000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ConflictPolicy {
+    Overwrite,
+    #[default]
+    Prompt,
+    Rename,
+    Skip,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            "prompt" => Ok(ConflictPolicy::Prompt),
+            "rename" => Ok(ConflictPolicy::Rename),
+            "skip" => Ok(ConflictPolicy::Skip),
+            _ => Err(format!("unknown conflict policy '{}'", value)),
+        }
+    }
+}
@@ -0,0 +1,647 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use flate2::read::GzDecoder;
+use tokio_util::sync::CancellationToken;
+
+use crate::{error::AppError, event::Preview, model::format_bytes};
+
+use super::mime;
+
+const EOCD_SIGNATURE: u32 = 0x06054b50;
+const EOCD_SIZE: u64 = 22;
+const EOCD64_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const EOCD64_LOCATOR_SIZE: usize = 20;
+const EOCD64_SIGNATURE: u32 = 0x06064b50;
+const EOCD64_SIZE: usize = 56;
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const UNIX_HOST: u8 = 3;
+const TAR_MAGIC_END: u64 = 262;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+#[derive(Debug, PartialEq)]
+struct ArchiveEntry {
+    path: String,
+    kind: char,
+    mode: u32,
+    size: Option<u64>,
+    link: Option<String>,
+}
+
+pub async fn load(
+    path: &Path,
+    kind: ArchiveKind,
+    cancellation: &CancellationToken,
+) -> Option<Preview> {
+    let source = path.to_path_buf();
+    let token = cancellation.clone();
+    let result = tokio::task::spawn_blocking(move || list(&source, kind, &token)).await;
+
+    match result {
+        Ok(Ok(Some(entries))) => Some(Preview::Content(
+            path.to_path_buf(),
+            entries.iter().map(to_line).collect(),
+        )),
+        Ok(Ok(None)) => None,
+        Ok(Err(err)) => {
+            tracing::error!("reading archive failed: {:?} {:?}", path, err);
+            Some(Preview::None(path.to_path_buf()))
+        }
+        Err(err) => {
+            tracing::error!("reading archive failed: {:?} {:?}", path, err);
+            Some(Preview::None(path.to_path_buf()))
+        }
+    }
+}
+
+pub async fn decompress(path: &Path, length: usize) -> Result<Vec<u8>, AppError> {
+    let source = path.to_path_buf();
+    let bytes = tokio::task::spawn_blocking(move || {
+        let mut bytes = Vec::new();
+        GzDecoder::new(BufReader::new(File::open(source)?))
+            .take(length as u64)
+            .read_to_end(&mut bytes)?;
+
+        Ok::<_, io::Error>(bytes)
+    })
+    .await??;
+
+    Ok(bytes)
+}
+
+pub fn get_kind(path: &Path, mime: &str) -> Option<ArchiveKind> {
+    match mime {
+        "application/x-tar" => Some(ArchiveKind::Tar),
+        mime::GZIP if is_tar_name(path) || contains_tar(path) => Some(ArchiveKind::TarGz),
+        mime::OCTET_STREAM => get_kind_from_extension(path),
+        _ if mime::is_zip_container(mime) => Some(ArchiveKind::Zip),
+        _ => None,
+    }
+}
+
+fn get_kind_from_extension(path: &Path) -> Option<ArchiveKind> {
+    let name = get_lowercase_name(path);
+    if is_tar_name(path) {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else if name.ends_with(".zip") || name.ends_with(".jar") {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+fn is_tar_name(path: &Path) -> bool {
+    let name = get_lowercase_name(path);
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+fn get_lowercase_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn contains_tar(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+
+    let mut header = Vec::new();
+    match GzDecoder::new(BufReader::new(file))
+        .take(TAR_MAGIC_END)
+        .read_to_end(&mut header)
+    {
+        Ok(_) => infer::archive::is_tar(&header),
+        Err(_) => false,
+    }
+}
+
+fn list(
+    path: &Path,
+    kind: ArchiveKind,
+    cancellation: &CancellationToken,
+) -> Result<Option<Vec<ArchiveEntry>>, AppError> {
+    let file = File::open(path)?;
+    match kind {
+        ArchiveKind::Tar => list_tar(BufReader::new(file), cancellation),
+        ArchiveKind::TarGz => list_tar(GzDecoder::new(BufReader::new(file)), cancellation),
+        ArchiveKind::Zip => list_zip(file, cancellation),
+    }
+}
+
+fn list_tar<R: Read>(
+    reader: R,
+    cancellation: &CancellationToken,
+) -> Result<Option<Vec<ArchiveEntry>>, AppError> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        if cancellation.is_cancelled() {
+            return Ok(None);
+        }
+
+        let entry = entry?;
+        let header = entry.header();
+        let entry_type = header.entry_type();
+        let kind = if entry_type.is_dir() {
+            'd'
+        } else if entry_type.is_symlink() {
+            'l'
+        } else {
+            '-'
+        };
+
+        entries.push(ArchiveEntry {
+            path: entry.path()?.to_string_lossy().to_string(),
+            kind,
+            mode: header.mode().unwrap_or_default(),
+            size: (kind == '-').then(|| header.size().unwrap_or_default()),
+            link: entry
+                .link_name()?
+                .filter(|_| kind == 'l')
+                .map(|link| link.to_string_lossy().to_string()),
+        });
+    }
+
+    Ok(Some(entries))
+}
+
+fn list_zip<R: Read + Seek>(
+    mut reader: R,
+    cancellation: &CancellationToken,
+) -> Result<Option<Vec<ArchiveEntry>>, AppError> {
+    let length = reader.seek(SeekFrom::End(0))?;
+    let tail_length = length.min(EOCD_SIZE + u16::MAX as u64);
+    reader.seek(SeekFrom::Start(length - tail_length))?;
+
+    let mut tail = vec![0; tail_length as usize];
+    reader.read_exact(&mut tail)?;
+
+    let eocd = (0..tail.len().saturating_sub(EOCD_SIZE as usize - 1))
+        .rev()
+        .find(|index| read_u32(&tail, *index) == Some(EOCD_SIGNATURE))
+        .ok_or_else(|| invalid_zip("end of central directory not found"))?;
+
+    let mut count = read_u16(&tail, eocd + 10).unwrap_or_default() as u64;
+    let mut directory_size = read_u32(&tail, eocd + 12).unwrap_or_default() as u64;
+    let mut directory_offset = read_u32(&tail, eocd + 16).unwrap_or_default() as u64;
+
+    if count == u16::MAX as u64
+        || directory_size == u32::MAX as u64
+        || directory_offset == u32::MAX as u64
+    {
+        let locator_offset = (length - tail_length + eocd as u64)
+            .checked_sub(EOCD64_LOCATOR_SIZE as u64)
+            .ok_or_else(|| invalid_zip("zip64 end of central directory locator not found"))?;
+
+        reader.seek(SeekFrom::Start(locator_offset))?;
+        let mut locator = [0; EOCD64_LOCATOR_SIZE];
+        reader.read_exact(&mut locator)?;
+        if read_u32(&locator, 0) != Some(EOCD64_LOCATOR_SIGNATURE) {
+            return Err(invalid_zip(
+                "zip64 end of central directory locator not found",
+            ));
+        }
+
+        let record_offset = read_u64(&locator, 8).unwrap_or_default();
+        if record_offset
+            .checked_add(EOCD64_SIZE as u64)
+            .is_none_or(|end| end > length)
+        {
+            return Err(invalid_zip("zip64 end of central directory out of bounds"));
+        }
+
+        reader.seek(SeekFrom::Start(record_offset))?;
+        let mut record = [0; EOCD64_SIZE];
+        reader.read_exact(&mut record)?;
+        if read_u32(&record, 0) != Some(EOCD64_SIGNATURE) {
+            return Err(invalid_zip("invalid zip64 end of central directory"));
+        }
+
+        count = read_u64(&record, 32).unwrap_or_default();
+        directory_size = read_u64(&record, 40).unwrap_or_default();
+        directory_offset = read_u64(&record, 48).unwrap_or_default();
+    }
+
+    if directory_offset
+        .checked_add(directory_size)
+        .is_none_or(|end| end > length)
+    {
+        return Err(invalid_zip("central directory out of bounds"));
+    }
+
+    reader.seek(SeekFrom::Start(directory_offset))?;
+    let mut directory = vec![0; directory_size as usize];
+    reader.read_exact(&mut directory)?;
+
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    for _ in 0..count {
+        if cancellation.is_cancelled() {
+            return Ok(None);
+        }
+
+        if read_u32(&directory, offset) != Some(CENTRAL_HEADER_SIGNATURE) {
+            return Err(invalid_zip("invalid central directory header"));
+        }
+
+        let host = directory.get(offset + 5).copied().unwrap_or_default();
+        let size = read_u32(&directory, offset + 24).unwrap_or_default();
+        let name_length = read_u16(&directory, offset + 28).unwrap_or_default() as usize;
+        let extra_length = read_u16(&directory, offset + 30).unwrap_or_default() as usize;
+        let comment_length = read_u16(&directory, offset + 32).unwrap_or_default() as usize;
+        let attributes = read_u32(&directory, offset + 38).unwrap_or_default();
+
+        let name_start = offset + CENTRAL_HEADER_SIZE;
+        let name = directory
+            .get(name_start..name_start + name_length)
+            .ok_or_else(|| invalid_zip("truncated central directory"))?;
+        let path = String::from_utf8_lossy(name).to_string();
+
+        let extra_start = name_start + name_length;
+        let size = match directory.get(extra_start..extra_start + extra_length) {
+            Some(extra) if size == u32::MAX => get_zip64_size(extra).unwrap_or(size as u64),
+            _ => size as u64,
+        };
+
+        let unix_mode = (host == UNIX_HOST).then_some(attributes >> 16);
+        let kind = match unix_mode.map(|mode| mode & 0o170000) {
+            Some(0o040000) => 'd',
+            Some(0o120000) => 'l',
+            _ if path.ends_with('/') => 'd',
+            _ => '-',
+        };
+
+        let mode = match unix_mode {
+            Some(mode) if mode != 0 => mode & 0o7777,
+            _ if kind == 'd' => 0o755,
+            _ => 0o644,
+        };
+
+        entries.push(ArchiveEntry {
+            path,
+            kind,
+            mode,
+            size: (kind == '-').then_some(size),
+            link: None,
+        });
+
+        offset = name_start + name_length + extra_length + comment_length;
+    }
+
+    Ok(Some(entries))
+}
+
+fn get_zip64_size(extra: &[u8]) -> Option<u64> {
+    let mut offset = 0;
+    while let (Some(id), Some(length)) = (read_u16(extra, offset), read_u16(extra, offset + 2)) {
+        if id == ZIP64_EXTRA_ID {
+            return read_u64(extra, offset + 4);
+        }
+        offset += 4 + length as usize;
+    }
+
+    None
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    let bytes = bytes.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn invalid_zip(message: &str) -> AppError {
+    AppError::FileOperationFailed(io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+fn to_line(entry: &ArchiveEntry) -> String {
+    let size = match entry.size {
+        Some(size) => format_bytes(size),
+        None => "-".to_string(),
+    };
+
+    let path = match &entry.link {
+        Some(link) => format!("{} -> {}", entry.path, link),
+        None => entry.path.clone(),
+    };
+
+    format!(
+        "{}{} {:>10}  {}",
+        entry.kind,
+        format_mode(entry.mode),
+        size,
+        path
+    )
+}
+
+fn format_mode(mode: u32) -> String {
+    (0..9)
+        .rev()
+        .map(|index| {
+            if (mode >> index) & 1 == 0 {
+                '-'
+            } else {
+                match index % 3 {
+                    2 => 'r',
+                    1 => 'w',
+                    _ => 'x',
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io::Write, path::Path};
+
+    use flate2::{write::GzEncoder, Compression};
+    use tokio_util::sync::CancellationToken;
+
+    use crate::task::mime;
+
+    use super::{get_kind, list, to_line, ArchiveEntry, ArchiveKind};
+
+    fn write_tar(path: &Path, compress: bool) {
+        let file = fs::File::create(path).unwrap();
+        let writer: Box<dyn Write> = if compress {
+            Box::new(GzEncoder::new(file, Compression::default()))
+        } else {
+            Box::new(file)
+        };
+
+        let mut builder = tar::Builder::new(writer);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_mode(0o755);
+        header.set_size(0);
+        builder
+            .append_data(&mut header, "src/", std::io::empty())
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(0o644);
+        header.set_size(5);
+        builder
+            .append_data(&mut header, "src/main.rs", "hello".as_bytes())
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_mode(0o777);
+        header.set_size(0);
+        builder
+            .append_link(&mut header, "latest", "src/main.rs")
+            .unwrap();
+
+        builder.into_inner().unwrap().flush().unwrap();
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8], u32)]) {
+        let mut content = Vec::new();
+        let mut directory = Vec::new();
+        for (name, data, mode) in entries {
+            let offset = content.len() as u32;
+
+            content.extend_from_slice(&0x04034b50u32.to_le_bytes());
+            content.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            content.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            content.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            content.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            content.extend_from_slice(&0u16.to_le_bytes());
+            content.extend_from_slice(name.as_bytes());
+            content.extend_from_slice(data);
+
+            directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            directory.extend_from_slice(&[20, 3, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+            directory.extend_from_slice(&(mode << 16).to_le_bytes());
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let directory_offset = content.len() as u32;
+        let count = entries.len() as u16;
+        content.extend_from_slice(&directory);
+        content.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        content.extend_from_slice(&[0, 0, 0, 0]);
+        content.extend_from_slice(&count.to_le_bytes());
+        content.extend_from_slice(&count.to_le_bytes());
+        content.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        content.extend_from_slice(&directory_offset.to_le_bytes());
+        content.extend_from_slice(&0u16.to_le_bytes());
+
+        fs::write(path, content).unwrap();
+    }
+
+    fn lines(path: &Path, kind: ArchiveKind) -> Vec<String> {
+        list(path, kind, &CancellationToken::new())
+            .unwrap()
+            .unwrap()
+            .iter()
+            .map(to_line)
+            .collect()
+    }
+
+    #[test]
+    fn get_kind_resolves_extensions_and_mime() {
+        let binary = mime::OCTET_STREAM;
+        assert_eq!(get_kind(Path::new("a.tar"), binary), Some(ArchiveKind::Tar));
+        assert_eq!(
+            get_kind(Path::new("a.TAR.GZ"), binary),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(
+            get_kind(Path::new("a.tgz"), mime::GZIP),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(get_kind(Path::new("a.zip"), binary), Some(ArchiveKind::Zip));
+        assert_eq!(
+            get_kind(Path::new("release"), "application/zip"),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(get_kind(Path::new("a.gz"), mime::GZIP), None);
+        assert_eq!(get_kind(Path::new("a.rs"), binary), None);
+    }
+
+    #[test]
+    fn get_kind_prefers_content_over_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("backup");
+        write_tar(&path, true);
+
+        assert_eq!(get_kind(&path, mime::GZIP), Some(ArchiveKind::TarGz));
+        assert_eq!(get_kind(Path::new("notes.zip"), mime::PLAIN_TEXT), None);
+        assert_eq!(
+            get_kind(Path::new("a.zip"), "application/x-tar"),
+            Some(ArchiveKind::Tar)
+        );
+    }
+
+    #[test]
+    fn list_tar_shows_modes_sizes_and_links() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("release.tar");
+        write_tar(&path, false);
+
+        assert_eq!(
+            lines(&path, ArchiveKind::Tar),
+            vec![
+                "drwxr-xr-x          -  src/",
+                "-rw-r--r--        5 B  src/main.rs",
+                "lrwxrwxrwx          -  latest -> src/main.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn list_tar_gz_decompresses_archive() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("release.tar.gz");
+        write_tar(&path, true);
+
+        assert_eq!(lines(&path, ArchiveKind::TarGz).len(), 3);
+    }
+
+    #[test]
+    fn list_zip_reads_central_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("release.zip");
+        write_zip(
+            &path,
+            &[("bin/", b"", 0o040755), ("bin/run", b"#!/bin/sh", 0o100750)],
+        );
+
+        assert_eq!(
+            lines(&path, ArchiveKind::Zip),
+            vec![
+                "drwxr-xr-x          -  bin/",
+                "-rwxr-x---        9 B  bin/run",
+            ]
+        );
+    }
+
+    #[test]
+    fn list_zip_rejects_central_directory_beyond_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("broken.zip");
+        let mut content = 0x06054b50u32.to_le_bytes().to_vec();
+        content.extend_from_slice(&[0, 0, 0, 0, 1, 0, 1, 0]);
+        content.extend_from_slice(&u32::MAX.to_le_bytes());
+        content.extend_from_slice(&0u32.to_le_bytes());
+        content.extend_from_slice(&0u16.to_le_bytes());
+        fs::write(&path, content).unwrap();
+
+        assert!(list(&path, ArchiveKind::Zip, &CancellationToken::new()).is_err());
+    }
+
+    #[test]
+    fn list_zip_reads_zip64_records() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("large.zip");
+        let name = b"large.bin";
+        let size = 5 * 1024 * 1024 * 1024u64;
+
+        let mut directory = 0x02014b50u32.to_le_bytes().to_vec();
+        directory.extend_from_slice(&[45, 3, 45, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        directory.extend_from_slice(&u32::MAX.to_le_bytes());
+        directory.extend_from_slice(&u32::MAX.to_le_bytes());
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&12u16.to_le_bytes());
+        directory.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        directory.extend_from_slice(&(0o100644u32 << 16).to_le_bytes());
+        directory.extend_from_slice(&0u32.to_le_bytes());
+        directory.extend_from_slice(name);
+        directory.extend_from_slice(&0x0001u16.to_le_bytes());
+        directory.extend_from_slice(&8u16.to_le_bytes());
+        directory.extend_from_slice(&size.to_le_bytes());
+
+        let mut content = directory.clone();
+        let record_offset = content.len() as u64;
+        content.extend_from_slice(&0x06064b50u32.to_le_bytes());
+        content.extend_from_slice(&44u64.to_le_bytes());
+        content.extend_from_slice(&[45, 3, 45, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        content.extend_from_slice(&1u64.to_le_bytes());
+        content.extend_from_slice(&1u64.to_le_bytes());
+        content.extend_from_slice(&(directory.len() as u64).to_le_bytes());
+        content.extend_from_slice(&0u64.to_le_bytes());
+        content.extend_from_slice(&0x07064b50u32.to_le_bytes());
+        content.extend_from_slice(&0u32.to_le_bytes());
+        content.extend_from_slice(&record_offset.to_le_bytes());
+        content.extend_from_slice(&1u32.to_le_bytes());
+        content.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        content.extend_from_slice(&[0, 0, 0, 0, 255, 255, 255, 255]);
+        content.extend_from_slice(&u32::MAX.to_le_bytes());
+        content.extend_from_slice(&u32::MAX.to_le_bytes());
+        content.extend_from_slice(&0u16.to_le_bytes());
+        fs::write(&path, content).unwrap();
+
+        let entries = list(&path, ArchiveKind::Zip, &CancellationToken::new())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "large.bin");
+        assert_eq!(entries[0].size, Some(size));
+    }
+
+    #[test]
+    fn list_zip_fails_without_central_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("broken.zip");
+        fs::write(&path, "not a zip").unwrap();
+
+        assert!(list(&path, ArchiveKind::Zip, &CancellationToken::new()).is_err());
+    }
+
+    #[test]
+    fn list_stops_when_cancelled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("release.tar");
+        write_tar(&path, false);
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+
+        assert_eq!(list(&path, ArchiveKind::Tar, &cancellation).unwrap(), None);
+    }
+
+    #[test]
+    fn to_line_formats_entry() {
+        let entry = ArchiveEntry {
+            path: "big.bin".to_string(),
+            kind: '-',
+            mode: 0o600,
+            size: Some(1536),
+            link: None,
+        };
+
+        assert_eq!(to_line(&entry), "-rw-------    1.5 KiB  big.bin");
+    }
+}
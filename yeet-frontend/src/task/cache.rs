@@ -0,0 +1,189 @@
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use lru::LruCache;
+use ratatui::layout::Rect;
+use tokio::fs;
+
+use crate::event::Preview;
+
+const CAPACITY: NonZeroUsize = NonZeroUsize::new(64).unwrap();
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PreviewCacheKey {
+    path: PathBuf,
+    modified: SystemTime,
+    size: u64,
+    rect: Rect,
+}
+
+impl PreviewCacheKey {
+    pub async fn new(path: &Path, rect: Rect) -> Option<Self> {
+        let metadata = fs::metadata(path).await.ok()?;
+
+        Some(Self {
+            path: path.to_path_buf(),
+            modified: metadata.modified().ok()?,
+            size: metadata.len(),
+            rect,
+        })
+    }
+}
+
+pub struct PreviewCache {
+    entries: LruCache<PreviewCacheKey, (Preview, String)>,
+}
+
+impl Default for PreviewCache {
+    fn default() -> Self {
+        Self::new(CAPACITY)
+    }
+}
+
+impl PreviewCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: LruCache::new(capacity),
+        }
+    }
+
+    pub fn get(&mut self, key: &PreviewCacheKey) -> Option<(Preview, String)> {
+        self.entries
+            .get(key)
+            .and_then(|(preview, mime)| Some((clone_cacheable(preview)?, mime.clone())))
+    }
+
+    pub fn insert(&mut self, key: PreviewCacheKey, preview: &Preview, mime: &str) {
+        if let Some(preview) = clone_cacheable(preview) {
+            self.entries.put(key, (preview, mime.to_string()));
+        }
+    }
+
+    pub fn invalidate(&mut self, path: &Path) {
+        let keys: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| key.path.starts_with(path))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in keys {
+            self.entries.pop(&key);
+        }
+    }
+}
+
+fn clone_cacheable(preview: &Preview) -> Option<Preview> {
+    match preview {
+        Preview::Content(path, lines) => Some(Preview::Content(path.clone(), lines.clone())),
+        Preview::Image(path, protocol, metadata) => Some(Preview::Image(
+            path.clone(),
+            protocol.clone(),
+            metadata.clone(),
+        )),
+        Preview::ImageSymbols(path, lines, metadata) => Some(Preview::ImageSymbols(
+            path.clone(),
+            lines.clone(),
+            metadata.clone(),
+        )),
+        Preview::Markdown(path, raw, rendered, None) => Some(Preview::Markdown(
+            path.clone(),
+            raw.clone(),
+            rendered.clone(),
+            None,
+        )),
+        Preview::Continued(_, _, _)
+        | Preview::ContinuedMarkdown(_, _, _, _)
+        | Preview::Hex(_, _, _)
+        | Preview::Markdown(_, _, _, Some(_))
+        | Preview::None(_)
+        | Preview::Partial(_, _, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        num::NonZeroUsize,
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use ratatui::layout::Rect;
+
+    use crate::event::Preview;
+
+    use super::{PreviewCache, PreviewCacheKey};
+
+    fn key(path: &str, size: u64) -> PreviewCacheKey {
+        PreviewCacheKey {
+            path: PathBuf::from(path),
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(42),
+            size,
+            rect: Rect::new(0, 0, 80, 24),
+        }
+    }
+
+    fn content(path: &str) -> Preview {
+        Preview::Content(PathBuf::from(path), vec!["line".to_string()])
+    }
+
+    #[test]
+    fn get_returns_cached_content_for_matching_key_only() {
+        let mut cache = PreviewCache::default();
+        cache.insert(key("/a.rs", 10), &content("/a.rs"), "text/plain");
+
+        let (preview, mime) = cache.get(&key("/a.rs", 10)).expect("cached preview");
+        assert!(matches!(preview, Preview::Content(_, lines) if lines == vec!["line"]));
+        assert_eq!(mime, "text/plain");
+        assert!(cache.get(&key("/a.rs", 11)).is_none());
+    }
+
+    #[test]
+    fn insert_skips_previews_that_are_not_complete() {
+        let mut cache = PreviewCache::default();
+        cache.insert(
+            key("/a.bin", 10),
+            &Preview::Hex(PathBuf::from("/a.bin"), vec![0], 0),
+            "application/octet-stream",
+        );
+        cache.insert(
+            key("/b", 10),
+            &Preview::None(PathBuf::from("/b")),
+            "text/plain",
+        );
+
+        assert!(cache.get(&key("/a.bin", 10)).is_none());
+        assert!(cache.get(&key("/b", 10)).is_none());
+    }
+
+    #[test]
+    fn insert_evicts_least_recently_used_entry() {
+        let mut cache = PreviewCache::new(NonZeroUsize::new(2).unwrap());
+        cache.insert(key("/a", 1), &content("/a"), "text/plain");
+        cache.insert(key("/b", 1), &content("/b"), "text/plain");
+        cache.get(&key("/a", 1));
+        cache.insert(key("/c", 1), &content("/c"), "text/plain");
+
+        assert!(cache.get(&key("/a", 1)).is_some());
+        assert!(cache.get(&key("/b", 1)).is_none());
+        assert!(cache.get(&key("/c", 1)).is_some());
+    }
+
+    #[test]
+    fn invalidate_removes_path_and_children() {
+        let mut cache = PreviewCache::default();
+        cache.insert(key("/dir/a", 1), &content("/dir/a"), "text/plain");
+        cache.insert(key("/dir/sub/b", 1), &content("/dir/sub/b"), "text/plain");
+        cache.insert(key("/other", 1), &content("/other"), "text/plain");
+
+        cache.invalidate(&PathBuf::from("/dir"));
+
+        assert!(cache.get(&key("/dir/a", 1)).is_none());
+        assert!(cache.get(&key("/dir/sub/b", 1)).is_none());
+        assert!(cache.get(&key("/other", 1)).is_some());
+    }
+}
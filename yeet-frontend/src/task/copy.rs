@@ -0,0 +1,558 @@
+use std::{
+    fs::{self, File, Metadata, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
+
+use filetime::FileTime;
+use tokio_util::sync::CancellationToken;
+
+use super::progress::ProgressWrite;
+
+#[derive(Debug)]
+pub struct CopyFailure {
+    pub path: PathBuf,
+    pub error: io::Error,
+}
+
+pub fn copy_recursive(
+    source: &Path,
+    target: &Path,
+    cancellation: &CancellationToken,
+    mut progress: impl FnMut(u64),
+) -> Vec<CopyFailure> {
+    if target.starts_with(source) {
+        return vec![CopyFailure {
+            path: target.to_path_buf(),
+            error: io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot copy an entry into itself",
+            ),
+        }];
+    }
+
+    let mut failures = Vec::new();
+    let mut directories = Vec::new();
+    let mut pending = vec![(source.to_path_buf(), target.to_path_buf())];
+
+    while let Some((source, target)) = pending.pop() {
+        if cancellation.is_cancelled() {
+            tracing::warn!("copy canceled before {:?}", source);
+            failures.push(CopyFailure {
+                path: source,
+                error: io::Error::new(
+                    io::ErrorKind::Interrupted,
+                    "copy canceled, target is incomplete",
+                ),
+            });
+            break;
+        }
+
+        let metadata = match fs::symlink_metadata(&source) {
+            Ok(it) => it,
+            Err(error) => {
+                failures.push(CopyFailure {
+                    path: source,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        let file_type = metadata.file_type();
+        let result = if file_type.is_symlink() {
+            copy_symlink(&source, &target, &metadata)
+        } else if file_type.is_dir() {
+            match fs::create_dir(&target) {
+                Ok(()) => {
+                    match read_children(&source, &target) {
+                        Ok(children) => pending.extend(children),
+                        Err(error) => failures.push(CopyFailure {
+                            path: source.clone(),
+                            error,
+                        }),
+                    }
+
+                    directories.push((source.clone(), target, metadata));
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            }
+        } else if file_type.is_file() {
+            copy_file(&source, &target, &metadata, &mut progress)
+        } else {
+            tracing::warn!("skipping special file {:?}", source);
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "special files like fifos, sockets or devices are not copied",
+            ))
+        };
+
+        if let Err(error) = result {
+            failures.push(CopyFailure {
+                path: source,
+                error,
+            });
+        }
+    }
+
+    for (source, target, metadata) in directories.into_iter().rev() {
+        if let Err(error) = apply_metadata(&target, &metadata) {
+            failures.push(CopyFailure {
+                path: source,
+                error,
+            });
+        }
+    }
+
+    failures
+}
+
+pub fn is_cross_device(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::CrossesDevices
+}
+
+pub fn move_across_devices(
+    source: &Path,
+    target: &Path,
+    cancellation: &CancellationToken,
+    progress: impl FnMut(u64),
+) -> Vec<CopyFailure> {
+    if fs::symlink_metadata(target).is_ok() {
+        return vec![CopyFailure {
+            path: target.to_path_buf(),
+            error: io::Error::from(io::ErrorKind::AlreadyExists),
+        }];
+    }
+
+    let mut failures = copy_recursive(source, target, cancellation, progress);
+    if failures.is_empty() && cancellation.is_cancelled() {
+        failures.push(CopyFailure {
+            path: source.to_path_buf(),
+            error: io::Error::new(io::ErrorKind::Interrupted, "move canceled"),
+        });
+    }
+
+    if failures.is_empty() {
+        if let Err(failure) = verify(source, target) {
+            failures.push(failure);
+        }
+    }
+
+    if !failures.is_empty() {
+        if let Err(error) = remove_recursive(target) {
+            tracing::warn!(
+                "removing incomplete move target {:?} failed: {}",
+                target,
+                error
+            );
+        }
+        return failures;
+    }
+
+    if let Err(error) = remove_recursive(source) {
+        failures.push(CopyFailure {
+            path: source.to_path_buf(),
+            error,
+        });
+    }
+
+    failures
+}
+
+pub fn remove_recursive(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn verify(source: &Path, target: &Path) -> Result<(), CopyFailure> {
+    let mut pending = vec![(source.to_path_buf(), target.to_path_buf())];
+    while let Some((source, target)) = pending.pop() {
+        let is_equal = match (fs::symlink_metadata(&source), fs::symlink_metadata(&target)) {
+            (Ok(source_metadata), Ok(target_metadata)) => {
+                let source_type = source_metadata.file_type();
+                let target_type = target_metadata.file_type();
+                if source_type.is_symlink() {
+                    target_type.is_symlink()
+                        && fs::read_link(&source).ok() == fs::read_link(&target).ok()
+                } else if source_type.is_dir() {
+                    match read_children(&source, &target) {
+                        Ok(children) => pending.extend(children),
+                        Err(error) => {
+                            return Err(CopyFailure {
+                                path: source,
+                                error,
+                            })
+                        }
+                    }
+                    target_type.is_dir()
+                } else {
+                    target_type.is_file() && source_metadata.len() == target_metadata.len()
+                }
+            }
+            (Err(error), _) => {
+                return Err(CopyFailure {
+                    path: source,
+                    error,
+                })
+            }
+            (_, Err(error)) => {
+                return Err(CopyFailure {
+                    path: target,
+                    error,
+                })
+            }
+        };
+
+        if !is_equal {
+            return Err(CopyFailure {
+                path: target,
+                error: io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "copied entry does not match source",
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn read_children(source: &Path, target: &Path) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut children = Vec::new();
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        children.push((entry.path(), target.join(entry.file_name())));
+    }
+    Ok(children)
+}
+
+fn copy_file(
+    source: &Path,
+    target: &Path,
+    metadata: &Metadata,
+    progress: &mut impl FnMut(u64),
+) -> io::Result<()> {
+    let mut reader = File::open(source)?;
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(target)?;
+    let mut writer = ProgressWrite::new(file, progress);
+    io::copy(&mut reader, &mut writer)?;
+
+    apply_metadata(target, metadata)
+}
+
+fn copy_symlink(source: &Path, target: &Path, metadata: &Metadata) -> io::Result<()> {
+    let link = fs::read_link(source)?;
+    create_symlink(&link, source, target)?;
+
+    filetime::set_symlink_file_times(
+        target,
+        FileTime::from_last_access_time(metadata),
+        FileTime::from_last_modification_time(metadata),
+    )
+}
+
+fn apply_metadata(target: &Path, metadata: &Metadata) -> io::Result<()> {
+    fs::set_permissions(target, metadata.permissions())?;
+    filetime::set_file_times(
+        target,
+        FileTime::from_last_access_time(metadata),
+        FileTime::from_last_modification_time(metadata),
+    )
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn create_symlink(link: &Path, _source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(link, target)
+}
+
+#[cfg(target_os = "windows")]
+pub fn create_symlink(link: &Path, source: &Path, target: &Path) -> io::Result<()> {
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(link, target)
+    } else {
+        std::os::windows::fs::symlink_file(link, target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, io};
+
+    use filetime::FileTime;
+    use tokio_util::sync::CancellationToken;
+
+    use super::{copy_recursive, is_cross_device, move_across_devices, verify};
+
+    #[test]
+    fn copy_recursive_copies_single_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source.txt");
+        let target = dir.path().join("target.txt");
+        fs::write(&source, "content").unwrap();
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert!(failures.is_empty());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "content");
+    }
+
+    #[test]
+    fn copy_recursive_copies_nested_directories() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("project");
+        fs::create_dir_all(source.join("src/nested")).unwrap();
+        fs::write(source.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(source.join("src/nested/lib.rs"), "fn main() {}").unwrap();
+        let target = dir.path().join("copy");
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert!(failures.is_empty());
+        assert_eq!(
+            fs::read_to_string(target.join("Cargo.toml")).unwrap(),
+            "[package]"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("src/nested/lib.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn copy_recursive_rejects_target_inside_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("project");
+        fs::create_dir_all(source.join("src")).unwrap();
+
+        let failures = copy_recursive(
+            &source,
+            &source.join("src/project"),
+            &CancellationToken::new(),
+            |_| {},
+        );
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].error.kind(), io::ErrorKind::InvalidInput);
+        assert!(!source.join("src/project").exists());
+    }
+
+    #[test]
+    fn copy_recursive_reports_cancellation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a"), "a").unwrap();
+        let target = dir.path().join("target");
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let failures = copy_recursive(&source, &target, &cancellation, |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].error.kind(), io::ErrorKind::Interrupted);
+    }
+
+    #[test]
+    fn copy_recursive_reports_copied_bytes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a"), [0u8; 1024]).unwrap();
+        fs::write(source.join("b"), [0u8; 2048]).unwrap();
+        let target = dir.path().join("target");
+
+        let mut copied = 0;
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |bytes| {
+            copied += bytes
+        });
+
+        assert!(failures.is_empty());
+        assert_eq!(copied, 3072);
+    }
+
+    #[test]
+    fn copy_recursive_preserves_modification_time() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("file"), "").unwrap();
+
+        let mtime = FileTime::from_unix_time(1_000_000, 0);
+        filetime::set_file_mtime(source.join("file"), mtime).unwrap();
+        filetime::set_file_mtime(&source, mtime).unwrap();
+        let target = dir.path().join("target");
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert!(failures.is_empty());
+        let file_metadata = fs::metadata(target.join("file")).unwrap();
+        let dir_metadata = fs::metadata(&target).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&file_metadata), mtime);
+        assert_eq!(FileTime::from_last_modification_time(&dir_metadata), mtime);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn copy_recursive_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("script.sh"), "#!/bin/sh").unwrap();
+        fs::set_permissions(source.join("script.sh"), fs::Permissions::from_mode(0o750)).unwrap();
+        std::os::unix::fs::symlink("script.sh", source.join("link")).unwrap();
+        let target = dir.path().join("target");
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert!(failures.is_empty());
+        let link = fs::symlink_metadata(target.join("link")).unwrap();
+        assert!(link.file_type().is_symlink());
+        assert_eq!(
+            fs::read_link(target.join("link")).unwrap(),
+            std::path::PathBuf::from("script.sh")
+        );
+
+        let mode = fs::metadata(target.join("script.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o750);
+    }
+
+    #[test]
+    fn copy_recursive_reports_existing_target_as_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a"), "a").unwrap();
+        fs::write(source.join("b"), "b").unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, source);
+    }
+
+    #[test]
+    fn copy_recursive_keeps_existing_target_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source.txt");
+        let target = dir.path().join("target.txt");
+        fs::write(&source, "source").unwrap();
+        fs::write(&target, "target").unwrap();
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "target");
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn copy_recursive_skips_special_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("file"), "content").unwrap();
+        let _listener = std::os::unix::net::UnixListener::bind(source.join("socket")).unwrap();
+        let target = dir.path().join("target");
+
+        let failures = copy_recursive(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, source.join("socket"));
+        assert_eq!(failures[0].error.kind(), io::ErrorKind::Unsupported);
+        assert_eq!(fs::read_to_string(target.join("file")).unwrap(), "content");
+        assert!(fs::symlink_metadata(target.join("socket")).is_err());
+    }
+
+    #[test]
+    fn move_across_devices_moves_tree_and_removes_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/file"), "content").unwrap();
+        let target = dir.path().join("target");
+
+        let mut moved = 0;
+        let failures = move_across_devices(&source, &target, &CancellationToken::new(), |bytes| {
+            moved += bytes
+        });
+
+        assert!(failures.is_empty());
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(target.join("nested/file")).unwrap(),
+            "content"
+        );
+        assert_eq!(moved, 7);
+    }
+
+    #[test]
+    fn move_across_devices_keeps_existing_target_and_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::write(&source, "source").unwrap();
+        let target = dir.path().join("target");
+        fs::write(&target, "target").unwrap();
+
+        let failures = move_across_devices(&source, &target, &CancellationToken::new(), |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(fs::read_to_string(&source).unwrap(), "source");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "target");
+    }
+
+    #[test]
+    fn move_across_devices_keeps_source_when_canceled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::write(&source, "source").unwrap();
+        let target = dir.path().join("target");
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let failures = move_across_devices(&source, &target, &cancellation, |_| {});
+
+        assert_eq!(failures.len(), 1);
+        assert!(source.exists());
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn verify_detects_size_mismatch() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("file"), "content").unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("file"), "cont").unwrap();
+
+        let failure = verify(&source, &target).unwrap_err();
+
+        assert_eq!(failure.path, target.join("file"));
+    }
+
+    #[test]
+    fn is_cross_device_matches_exdev() {
+        assert!(is_cross_device(&std::io::Error::from(
+            std::io::ErrorKind::CrossesDevices
+        )));
+        assert!(!is_cross_device(&std::io::Error::from(
+            std::io::ErrorKind::NotFound
+        )));
+    }
+}
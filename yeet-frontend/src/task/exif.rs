@@ -0,0 +1,263 @@
+const TAG_MAKE: u16 = 0x010f;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Exif {
+    pub camera: Option<String>,
+    pub date: Option<String>,
+    pub orientation: Option<String>,
+}
+
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: usize,
+    offset: usize,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Option<Self> {
+        let little_endian = match bytes.get(..4)? {
+            [0x49, 0x49, 42, 0] => true,
+            [0x4d, 0x4d, 0, 42] => false,
+            _ => return None,
+        };
+
+        Some(Self {
+            bytes,
+            little_endian,
+        })
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes = self.bytes.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes = self.bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn entries(&self, offset: usize) -> Vec<Entry> {
+        let count = self.u16(offset).unwrap_or_default();
+        (0..usize::from(count))
+            .map_while(|index| {
+                let position = offset + 2 + index * 12;
+                Some(Entry {
+                    tag: self.u16(position)?,
+                    kind: self.u16(position + 2)?,
+                    count: usize::try_from(self.u32(position + 4)?).ok()?,
+                    offset: position + 8,
+                })
+            })
+            .collect()
+    }
+
+    fn ascii(&self, entry: &Entry) -> Option<String> {
+        if entry.kind != TYPE_ASCII {
+            return None;
+        }
+
+        let start = if entry.count <= 4 {
+            entry.offset
+        } else {
+            usize::try_from(self.u32(entry.offset)?).ok()?
+        };
+
+        let bytes = self.bytes.get(start..start.checked_add(entry.count)?)?;
+        let value = String::from_utf8_lossy(bytes);
+        let value = value.trim_end_matches('\0').trim();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_string())
+        }
+    }
+
+    fn short(&self, entry: &Entry) -> Option<u16> {
+        if entry.kind == TYPE_SHORT {
+            self.u16(entry.offset)
+        } else {
+            None
+        }
+    }
+
+    fn long(&self, entry: &Entry) -> Option<usize> {
+        if entry.kind == TYPE_LONG {
+            usize::try_from(self.u32(entry.offset)?).ok()
+        } else {
+            None
+        }
+    }
+}
+
+pub fn parse(chunk: &[u8]) -> Exif {
+    let Some(reader) = Reader::new(chunk) else {
+        return Exif::default();
+    };
+
+    let Some(offset) = reader.u32(4).and_then(|it| usize::try_from(it).ok()) else {
+        return Exif::default();
+    };
+
+    let mut make = None;
+    let mut model = None;
+    let mut date = None;
+    let mut orientation = None;
+    let mut exif_offset = None;
+    for entry in reader.entries(offset) {
+        match entry.tag {
+            TAG_MAKE => make = reader.ascii(&entry),
+            TAG_MODEL => model = reader.ascii(&entry),
+            TAG_DATE_TIME => date = reader.ascii(&entry),
+            TAG_ORIENTATION => orientation = reader.short(&entry).and_then(get_orientation),
+            TAG_EXIF_IFD => exif_offset = reader.long(&entry),
+            _ => {}
+        }
+    }
+
+    if let Some(offset) = exif_offset {
+        let original = reader
+            .entries(offset)
+            .iter()
+            .find(|entry| entry.tag == TAG_DATE_TIME_ORIGINAL)
+            .and_then(|entry| reader.ascii(entry));
+
+        if original.is_some() {
+            date = original;
+        }
+    }
+
+    let camera = match (make, model) {
+        (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (make, model) => make.or(model),
+    };
+
+    Exif {
+        camera,
+        date: date.map(format_date),
+        orientation,
+    }
+}
+
+fn get_orientation(value: u16) -> Option<String> {
+    let orientation = match value {
+        1 => "normal",
+        2 => "mirrored",
+        3 => "rotated 180°",
+        4 => "mirrored, rotated 180°",
+        5 => "mirrored, rotated 90° clockwise",
+        6 => "rotated 90° clockwise",
+        7 => "mirrored, rotated 270° clockwise",
+        8 => "rotated 270° clockwise",
+        _ => return None,
+    };
+
+    Some(orientation.to_string())
+}
+
+fn format_date(date: String) -> String {
+    match date.split_once(' ') {
+        Some((day, time)) if day.len() == 10 => format!("{} {}", day.replace(':', "-"), time),
+        _ => date,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, Exif};
+
+    fn entry(tag: u16, kind: u16, count: u32, value: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(tag.to_le_bytes());
+        bytes.extend(kind.to_le_bytes());
+        bytes.extend(count.to_le_bytes());
+        bytes.extend(value.to_le_bytes());
+        bytes
+    }
+
+    fn make_chunk() -> Vec<u8> {
+        let make = b"Canon\0";
+        let model = b"Canon EOS R6\0";
+        let date = b"2024:05:01 12:30:00\0";
+        let original = b"2024:04:30 08:15:00\0";
+
+        let ifd0 = 8;
+        let ifd0_size = 2 + 5 * 12 + 4;
+        let exif_ifd = ifd0 + ifd0_size;
+        let exif_ifd_size = 2 + 12 + 4;
+        let make_offset = exif_ifd + exif_ifd_size;
+        let model_offset = make_offset + make.len();
+        let date_offset = model_offset + model.len();
+        let original_offset = date_offset + date.len();
+
+        let mut bytes = vec![0x49, 0x49, 42, 0];
+        bytes.extend((ifd0 as u32).to_le_bytes());
+        bytes.extend(5u16.to_le_bytes());
+        bytes.extend(entry(0x010f, 2, make.len() as u32, make_offset as u32));
+        bytes.extend(entry(0x0110, 2, model.len() as u32, model_offset as u32));
+        bytes.extend(entry(0x0112, 3, 1, 6));
+        bytes.extend(entry(0x0132, 2, date.len() as u32, date_offset as u32));
+        bytes.extend(entry(0x8769, 4, 1, exif_ifd as u32));
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(entry(
+            0x9003,
+            2,
+            original.len() as u32,
+            original_offset as u32,
+        ));
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(make);
+        bytes.extend(model);
+        bytes.extend(date);
+        bytes.extend(original);
+        bytes
+    }
+
+    #[test]
+    fn parse_reads_camera_date_and_orientation() {
+        let exif = parse(&make_chunk());
+
+        assert_eq!(
+            exif,
+            Exif {
+                camera: Some("Canon EOS R6".to_string()),
+                date: Some("2024-04-30 08:15:00".to_string()),
+                orientation: Some("rotated 90° clockwise".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_ignores_invalid_and_truncated_chunks() {
+        assert_eq!(parse(b"not exif"), Exif::default());
+
+        let mut chunk = make_chunk();
+        chunk.truncate(40);
+        assert_eq!(parse(&chunk).camera, None);
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+use gix::{
+    bstr::{BString, ByteSlice},
+    index::{
+        entry::{stat, Mode, Stage, Stat},
+        fs::Metadata,
+    },
+    objs::tree::EntryMode,
+    traverse::tree::Recorder,
+    worktree::stack::state::ignore::Source,
+    AttributeStack, ObjectId, Repository,
+};
+
+use crate::{
+    error::AppError,
+    model::git::{GitEntryStatus, GitStatus},
+};
+
+pub async fn status(path: &Path, repository: Option<&Path>) -> Result<Option<GitStatus>, AppError> {
+    tracing::debug!("loading git status at {:?}", path);
+
+    let path = path.to_path_buf();
+    let repository = repository.map(Path::to_path_buf);
+    tokio::task::spawn_blocking(move || load(&path, repository.as_deref())).await?
+}
+
+fn load(path: &Path, repository: Option<&Path>) -> Result<Option<GitStatus>, AppError> {
+    let repo = match repository {
+        Some(root) => gix::open(root).map_err(gix_err)?,
+        None => match gix::discover(path) {
+            Ok(it) => it,
+            Err(gix::discover::Error::Discover(err)) => {
+                tracing::trace!("no git repository found for {:?}: {}", path, err);
+                return Ok(None);
+            }
+            Err(err) => return Err(gix_err(err)),
+        },
+    };
+
+    let Some(workdir) = repo.workdir() else {
+        return Ok(None);
+    };
+
+    let Some(prefix) = get_prefix(workdir, path) else {
+        return Ok(None);
+    };
+
+    let mut status = GitStatus {
+        branch: get_branch(&repo)?,
+        repository: workdir.to_path_buf(),
+        ..Default::default()
+    };
+
+    let index = repo.index_or_empty().map_err(gix_err)?;
+    let mut head = get_head_entries(&repo)?;
+    let mut tracked = HashSet::new();
+    for entry in index.entries() {
+        let relative = gix::path::from_bstr(entry.path(&index)).into_owned();
+        if !relative.starts_with(&prefix) {
+            continue;
+        }
+
+        let head_entry = head.remove(entry.path(&index));
+        let entry_status = if entry.stage() != Stage::Unconflicted {
+            Some(GitEntryStatus::Conflicted)
+        } else if is_modified(&repo, &index, entry, &workdir.join(&relative)) {
+            Some(GitEntryStatus::Modified)
+        } else if head_entry != Some((entry.id, entry.mode.to_tree_entry_mode())) {
+            Some(GitEntryStatus::Staged)
+        } else {
+            None
+        };
+
+        if let Some(entry_status) = entry_status {
+            insert_entry(&mut status, path, &prefix, &relative, entry_status);
+        }
+        tracked.insert(relative);
+    }
+
+    for relative in head.keys() {
+        let relative = gix::path::from_bstr(relative.as_bstr()).into_owned();
+        if relative.starts_with(&prefix) {
+            insert_entry(
+                &mut status,
+                path,
+                &prefix,
+                &relative,
+                GitEntryStatus::Staged,
+            );
+        }
+    }
+
+    let tracked_directories: HashSet<_> = tracked
+        .iter()
+        .flat_map(|relative| relative.ancestors().skip(1))
+        .map(Path::to_path_buf)
+        .collect();
+
+    let mut excludes = repo
+        .excludes(&index, None, Source::WorktreeThenIdMappingIfNotSkipped)
+        .map_err(gix_err)?;
+
+    let mut walk = Walk {
+        directory: path,
+        excludes: &mut excludes,
+        prefix: &prefix,
+        status: &mut status,
+        tracked: &tracked,
+        tracked_directories: &tracked_directories,
+        workdir,
+    };
+    walk.run(&prefix)?;
+
+    Ok(Some(status))
+}
+
+fn gix_err(err: impl Display) -> AppError {
+    AppError::GitOperationFailed(err.to_string())
+}
+
+fn get_prefix(workdir: &Path, path: &Path) -> Option<PathBuf> {
+    let workdir = fs::canonicalize(workdir).ok()?;
+    let path = fs::canonicalize(path).ok()?;
+    let prefix = path.strip_prefix(workdir).ok()?;
+
+    match prefix.components().next() {
+        Some(Component::Normal(name)) if name == ".git" => None,
+        _ => Some(prefix.to_path_buf()),
+    }
+}
+
+fn get_branch(repo: &Repository) -> Result<Option<String>, AppError> {
+    let branch = match repo.head_name().map_err(gix_err)? {
+        Some(name) => name.shorten().to_string(),
+        None => "HEAD".to_string(),
+    };
+
+    Ok(Some(branch))
+}
+
+fn get_head_entries(
+    repo: &Repository,
+) -> Result<HashMap<BString, (ObjectId, Option<EntryMode>)>, AppError> {
+    let id = repo.head_tree_id_or_empty().map_err(gix_err)?;
+    let tree = repo.find_tree(id).map_err(gix_err)?;
+
+    let mut recorder = Recorder::default();
+    tree.traverse()
+        .breadthfirst(&mut recorder)
+        .map_err(gix_err)?;
+
+    Ok(recorder
+        .records
+        .into_iter()
+        .filter(|entry| !entry.mode.is_tree())
+        .map(|entry| (entry.filepath, (entry.oid, Some(entry.mode))))
+        .collect())
+}
+
+fn is_modified(
+    repo: &Repository,
+    index: &gix::index::State,
+    entry: &gix::index::Entry,
+    path: &Path,
+) -> bool {
+    if entry.mode.is_submodule() {
+        return false;
+    }
+
+    let metadata = match Metadata::from_path_no_follow(path) {
+        Ok(it) => it,
+        Err(_) => return true,
+    };
+
+    if entry
+        .mode
+        .change_to_match_fs(&metadata, cfg!(unix), cfg!(unix))
+        .is_some()
+    {
+        return true;
+    }
+
+    let options = stat::Options::default();
+    if let Ok(current) = Stat::from_fs(&metadata) {
+        if entry.stat.matches(&current, options) && !entry.stat.is_racy(index.timestamp(), options)
+        {
+            return false;
+        }
+    }
+
+    let content = if entry.mode == Mode::SYMLINK {
+        fs::read_link(path).map(|target| gix::path::into_bstr(target).to_vec())
+    } else {
+        fs::read(path)
+    };
+
+    match content {
+        Ok(content) => gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &content)
+            .map_or(true, |id| id != entry.id),
+        Err(_) => true,
+    }
+}
+
+struct Walk<'a, 'repo> {
+    directory: &'a Path,
+    excludes: &'a mut AttributeStack<'repo>,
+    prefix: &'a Path,
+    status: &'a mut GitStatus,
+    tracked: &'a HashSet<PathBuf>,
+    tracked_directories: &'a HashSet<PathBuf>,
+    workdir: &'a Path,
+}
+
+impl Walk<'_, '_> {
+    fn run(&mut self, relative: &Path) -> Result<(), AppError> {
+        let entries = match fs::read_dir(self.workdir.join(relative)) {
+            Ok(it) => it,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if name == ".git" {
+                continue;
+            }
+
+            let relative = relative.join(name);
+            if self.tracked.contains(&relative) {
+                continue;
+            }
+
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+            if is_dir && self.tracked_directories.contains(&relative) {
+                self.run(&relative)?;
+                continue;
+            }
+
+            let mode = is_dir.then_some(Mode::DIR);
+            let is_excluded = self.excludes.at_path(&relative, mode)?.is_excluded();
+            let entry_status = if is_excluded {
+                GitEntryStatus::Ignored
+            } else if is_dir && is_empty(&entry.path()) {
+                continue;
+            } else {
+                GitEntryStatus::Untracked
+            };
+
+            insert_entry(
+                self.status,
+                self.directory,
+                self.prefix,
+                &relative,
+                entry_status,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn is_empty(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+fn insert_entry(
+    status: &mut GitStatus,
+    directory: &Path,
+    prefix: &Path,
+    relative: &Path,
+    entry_status: GitEntryStatus,
+) {
+    let Some((entry, is_nested)) = resolve_entry(directory, prefix, relative) else {
+        return;
+    };
+
+    if is_nested && entry_status == GitEntryStatus::Ignored {
+        return;
+    }
+
+    status
+        .entries
+        .entry(entry)
+        .and_modify(|current| *current = (*current).max(entry_status))
+        .or_insert(entry_status);
+}
+
+fn resolve_entry(directory: &Path, prefix: &Path, relative: &Path) -> Option<(PathBuf, bool)> {
+    let relative = relative.strip_prefix(prefix).ok()?;
+
+    let mut components = relative.components();
+    let name = match components.next()? {
+        Component::Normal(name) => name,
+        _ => return None,
+    };
+
+    let is_nested = components.next().is_some();
+    Some((directory.join(name), is_nested))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use crate::model::git::{GitEntryStatus, GitStatus};
+
+    use super::{insert_entry, load};
+
+    #[test]
+    fn insert_entry_resolves_entries_relative_to_directory() {
+        let directory = Path::new("/repo/src");
+        let prefix = Path::new("src");
+        let entries = [
+            ("src/main.rs", GitEntryStatus::Modified),
+            ("src/lib.rs", GitEntryStatus::Staged),
+            ("src/scratch", GitEntryStatus::Untracked),
+            ("src/target", GitEntryStatus::Ignored),
+            ("src/model/cache.o", GitEntryStatus::Ignored),
+            ("src/model/mod.rs", GitEntryStatus::Staged),
+            ("src/model/git.rs", GitEntryStatus::Conflicted),
+            ("docs/index.md", GitEntryStatus::Modified),
+        ];
+
+        let mut status = GitStatus::default();
+        for (relative, entry_status) in entries {
+            insert_entry(
+                &mut status,
+                directory,
+                prefix,
+                Path::new(relative),
+                entry_status,
+            );
+        }
+
+        assert_eq!(status.entries.len(), 5);
+        let get = |name: &str| status.entries.get(&PathBuf::from("/repo/src").join(name));
+        assert_eq!(get("main.rs"), Some(&GitEntryStatus::Modified));
+        assert_eq!(get("lib.rs"), Some(&GitEntryStatus::Staged));
+        assert_eq!(get("scratch"), Some(&GitEntryStatus::Untracked));
+        assert_eq!(get("target"), Some(&GitEntryStatus::Ignored));
+        assert_eq!(get("model"), Some(&GitEntryStatus::Conflicted));
+    }
+
+    #[test]
+    fn load_reports_untracked_and_ignored_entries() {
+        let root = tempfile::tempdir().unwrap();
+        gix::init(root.path()).unwrap();
+        fs::write(root.path().join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.path().join("main.rs"), "fn main() {}\n").unwrap();
+        fs::create_dir(root.path().join("target")).unwrap();
+        fs::write(root.path().join("target/out"), "").unwrap();
+        fs::create_dir(root.path().join("empty")).unwrap();
+
+        let status = load(root.path(), None).unwrap().unwrap();
+
+        let get = |name: &str| status.entries.get(&root.path().join(name));
+        assert_eq!(get("main.rs"), Some(&GitEntryStatus::Untracked));
+        assert_eq!(get(".gitignore"), Some(&GitEntryStatus::Untracked));
+        assert_eq!(get("target"), Some(&GitEntryStatus::Ignored));
+        assert_eq!(get("empty"), None);
+        assert!(status.branch.is_some());
+    }
+
+    #[test]
+    fn load_skips_directories_outside_of_repositories() {
+        let root = tempfile::tempdir().unwrap();
+
+        assert_eq!(load(root.path(), None).unwrap(), None);
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use ratatui::layout::Rect;
+use tokio::{fs::File, io::AsyncReadExt};
+
+use crate::event::Preview;
+
+const GROUP_SIZE: usize = 8;
+const MAX_BYTES_PER_LINE: usize = 32;
+
+pub async fn load(path: &Path, rect: &Rect) -> Preview {
+    let (bytes_per_line, length) = get_dimensions(rect);
+
+    match read(path, length as u64).await {
+        Ok(bytes) => Preview::Hex(path.to_path_buf(), bytes, bytes_per_line),
+        Err(err) => {
+            tracing::error!("reading file failed: {:?} {:?}", path, err);
+            Preview::None(path.to_path_buf())
+        }
+    }
+}
+
+pub fn from_bytes(path: &Path, mut bytes: Vec<u8>, rect: &Rect) -> Preview {
+    let (bytes_per_line, length) = get_dimensions(rect);
+    bytes.truncate(length);
+
+    Preview::Hex(path.to_path_buf(), bytes, bytes_per_line)
+}
+
+fn get_dimensions(rect: &Rect) -> (usize, usize) {
+    let bytes_per_line = get_bytes_per_line(rect.width);
+    (
+        bytes_per_line,
+        bytes_per_line * usize::from(rect.height.max(1)),
+    )
+}
+
+fn get_line_width(bytes_per_line: usize) -> usize {
+    4 * bytes_per_line + bytes_per_line.div_ceil(GROUP_SIZE) + 10
+}
+
+fn get_bytes_per_line(width: u16) -> usize {
+    let mut bytes_per_line = GROUP_SIZE;
+    while bytes_per_line < MAX_BYTES_PER_LINE
+        && get_line_width(bytes_per_line + GROUP_SIZE) <= usize::from(width)
+    {
+        bytes_per_line += GROUP_SIZE;
+    }
+    bytes_per_line
+}
+
+async fn read(path: &Path, length: u64) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    File::open(path)
+        .await?
+        .take(length)
+        .read_to_end(&mut bytes)
+        .await?;
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::layout::Rect;
+
+    use crate::event::Preview;
+
+    use super::{from_bytes, get_bytes_per_line, get_line_width, load};
+
+    #[test]
+    fn from_bytes_truncates_to_rect() {
+        let preview = from_bytes(
+            std::path::Path::new("/dir/data.gz"),
+            vec![0; 100],
+            &Rect::new(0, 0, 10, 2),
+        );
+
+        assert!(matches!(preview, Preview::Hex(_, bytes, 8) if bytes.len() == 16));
+    }
+
+    #[test]
+    fn get_bytes_per_line_fits_width() {
+        assert_eq!(get_line_width(16), 76);
+        assert_eq!(get_bytes_per_line(10), 8);
+        assert_eq!(get_bytes_per_line(76), 16);
+        assert_eq!(get_bytes_per_line(75), 8);
+        assert_eq!(get_bytes_per_line(500), 32);
+    }
+
+    #[tokio::test]
+    async fn load_reads_only_bytes_fitting_rect() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, [0u8; 1024]).unwrap();
+
+        let preview = load(&path, &Rect::new(0, 0, 76, 3)).await;
+
+        match preview {
+            Preview::Hex(_, bytes, bytes_per_line) => {
+                assert_eq!(bytes_per_line, 16);
+                assert_eq!(bytes.len(), 48);
+            }
+            _ => panic!("expected hex preview"),
+        }
+    }
+}
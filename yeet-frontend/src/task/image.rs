@@ -1,47 +1,133 @@
-use std::{path::Path, process::Stdio, str};
+use std::{fs::File, io::BufReader, path::Path, process::Stdio, str};
 
-use image::ImageReader;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
 use ratatui::layout::Rect;
 use ratatui_image::{picker::Picker, Resize};
 use tokio::process::Command;
+use yeet_lua::ImageMetadata;
 
-use crate::{error::AppError, event::Preview};
+use crate::{
+    error::AppError,
+    event::Preview,
+    model::{get_image_metadata_lines, get_image_panel_height},
+};
+
+use super::exif;
 
 #[tracing::instrument]
 pub async fn load(picker: &mut Option<Picker>, path: &Path, rect: &Rect) -> Preview {
-    let picker = match picker {
-        Some(pckr) => pckr,
-        None => return load_with_chafa(path, rect).await,
+    let decode = picker.is_some();
+    let image_path = path.to_path_buf();
+    let (metadata, image) =
+        match tokio::task::spawn_blocking(move || read_image(&image_path, decode)).await {
+            Ok(Ok(it)) => it,
+            Ok(Err(err)) => {
+                tracing::warn!("reading image failed: {:?}", err);
+                (None, None)
+            }
+            Err(err) => {
+                tracing::error!("reading image failed: {:?}", err);
+                (None, None)
+            }
+        };
+
+    let rect = Rect {
+        height: rect
+            .height
+            .saturating_sub(get_image_panel_height(metadata.as_ref())),
+        ..*rect
     };
 
-    match load_with_ratatui_image(picker, path, rect).await {
-        Ok(preview) => preview,
+    let (Some(picker), Some(image)) = (picker, image) else {
+        return load_with_chafa(path, &rect, metadata).await;
+    };
+
+    tracing::debug!("load image preview for path with ratatui image: {:?}", path);
+
+    match picker.new_protocol(image, rect, Resize::Fit(None)) {
+        Ok(protocol) => Preview::Image(path.to_path_buf(), protocol, metadata),
         Err(err) => {
-            tracing::error!("image preview failed: {:?}", err);
-            load_with_chafa(path, rect).await
+            tracing::error!("Generation of preview image protocol failed: {:?}", err);
+            load_with_chafa(path, &rect, metadata).await
         }
     }
 }
 
-async fn load_with_ratatui_image(
-    picker: &mut Picker,
+fn read_image(
     path: &Path,
-    rect: &Rect,
-) -> Result<Preview, AppError> {
-    tracing::debug!("load image preview for path with ratatui image: {:?}", path);
+    decode: bool,
+) -> Result<(Option<ImageMetadata>, Option<DynamicImage>), AppError> {
+    let file = File::open(path)?;
+    let size = file.metadata()?.len();
 
-    let image = ImageReader::open(path)?.decode()?;
+    let reader = ImageReader::new(BufReader::new(file)).with_guessed_format()?;
+    let Some(format) = reader.format() else {
+        return Ok((None, None));
+    };
 
-    match picker.new_protocol(image, *rect, Resize::Fit(None)) {
-        Ok(protocol) => Ok(Preview::Image(path.to_path_buf(), protocol)),
+    let mut decoder = reader.into_decoder()?;
+    let (width, height) = decoder.dimensions();
+    let color_type = decoder.original_color_type();
+    let exif = match decoder.exif_metadata() {
+        Ok(Some(chunk)) => exif::parse(&chunk),
+        Ok(None) => Default::default(),
         Err(err) => {
-            tracing::error!("Generation of preview image protocol failed: {:?}", err);
-            Err(AppError::PreviewProtocolGenerationFailed)
+            tracing::warn!("reading exif metadata failed: {:?}", err);
+            Default::default()
         }
-    }
+    };
+
+    let metadata = ImageMetadata {
+        width,
+        height,
+        format: get_format_label(format),
+        color_type: format!("{:?}", color_type),
+        bits_per_pixel: color_type.bits_per_pixel(),
+        size,
+        camera: exif.camera,
+        date: exif.date,
+        orientation: exif.orientation,
+    };
+
+    let image = if decode {
+        Some(DynamicImage::from_decoder(decoder)?)
+    } else {
+        None
+    };
+
+    Ok((Some(metadata), image))
 }
 
-async fn load_with_chafa(path: &Path, rect: &Rect) -> Preview {
+fn get_format_label(format: ImageFormat) -> String {
+    let label = match format {
+        ImageFormat::Avif => "AVIF",
+        ImageFormat::Bmp => "BMP",
+        ImageFormat::Dds => "DDS",
+        ImageFormat::Farbfeld => "FARBFELD",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::Hdr => "HDR",
+        ImageFormat::Ico => "ICO",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::OpenExr => "EXR",
+        ImageFormat::Png => "PNG",
+        ImageFormat::Pnm => "PNM",
+        ImageFormat::Qoi => "QOI",
+        ImageFormat::Tga => "TGA",
+        ImageFormat::Tiff => "TIFF",
+        ImageFormat::WebP => "WEBP",
+        _ => {
+            return format
+                .extensions_str()
+                .first()
+                .map(|extension| extension.to_uppercase())
+                .unwrap_or_default()
+        }
+    };
+
+    label.to_string()
+}
+
+async fn load_with_chafa(path: &Path, rect: &Rect, metadata: Option<ImageMetadata>) -> Preview {
     tracing::debug!("load image preview for path with chafa: {:?}", path);
 
     let result = Command::new("chafa")
@@ -76,11 +162,16 @@ async fn load_with_chafa(path: &Path, rect: &Rect) -> Preview {
                 tracing::warn!("chafa failed: image result is empty");
                 Preview::None(path.to_path_buf())
             } else {
-                let content = str::from_utf8(&output.stdout).map_or(vec![], |s| {
+                let mut content = str::from_utf8(&output.stdout).map_or(vec![], |s| {
                     s.lines().map(strip_non_sgr_escape_sequences).collect()
                 });
 
-                Preview::Content(path.to_path_buf(), content)
+                if let Some(metadata) = &metadata {
+                    content.push(String::new());
+                    content.extend(get_image_metadata_lines(metadata));
+                }
+
+                Preview::ImageSymbols(path.to_path_buf(), content, metadata)
             }
         }
         Err(err) => {
@@ -132,6 +223,41 @@ fn strip_non_sgr_escape_sequences(input: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn read_image_returns_dimensions_format_and_size() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("image.bin");
+        image::RgbaImage::new(3, 2)
+            .save_with_format(&path, image::ImageFormat::Png)
+            .unwrap();
+
+        let (metadata, image) = read_image(&path, false).unwrap();
+        let metadata = metadata.expect("metadata for png");
+
+        assert_eq!((metadata.width, metadata.height), (3, 2));
+        assert_eq!(metadata.format, "PNG");
+        assert_eq!(metadata.color_type, "Rgba8");
+        assert_eq!(metadata.bits_per_pixel, 32);
+        assert_eq!(metadata.size, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(metadata.camera, None);
+        assert!(image.is_none());
+    }
+
+    #[test]
+    fn read_image_decodes_with_the_same_reader() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("image.jpg");
+        image::RgbImage::new(4, 5)
+            .save_with_format(&path, image::ImageFormat::Jpeg)
+            .unwrap();
+
+        let (metadata, image) = read_image(&path, true).unwrap();
+
+        assert_eq!(metadata.unwrap().format, "JPEG");
+        let image = image.expect("decoded jpeg");
+        assert_eq!((image.width(), image.height()), (4, 5));
+    }
+
     #[test]
     fn preserves_sgr_sequences() {
         let input = "\x1b[38;2;255;100;50mhello\x1b[0m";
@@ -0,0 +1,372 @@
+use std::{mem, path::Path};
+
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Highlighter, Style, Theme},
+    parsing::{Scope, SyntaxSet},
+    util::{as_24_bit_terminal_escaped, LinesWithEndings},
+};
+use yeet_buffer::model::ansi::Ansi;
+
+const BOLD: &str = "\x1b[1m";
+const BOLD_UNDERLINE: &str = "\x1b[1;4m";
+const CODE_INDENT: &str = "  ";
+const ITALIC: &str = "\x1b[3m";
+const RESET: &str = "\x1b[0m";
+const UNDERLINE: &str = "\x1b[4m";
+
+struct Styles {
+    code: Style,
+    heading: Style,
+    link: Style,
+    list: Style,
+    quote: Style,
+    text: Style,
+}
+
+impl Styles {
+    fn new(theme: &Theme) -> Self {
+        let highlighter = Highlighter::new(theme);
+        let get_style = |scope: &str| match Scope::new(scope) {
+            Ok(scope) => highlighter.style_for_stack(&[scope]),
+            Err(_) => highlighter.get_default(),
+        };
+
+        Self {
+            code: get_style("markup.raw.inline"),
+            heading: get_style("markup.heading"),
+            link: get_style("markup.underline.link"),
+            list: get_style("punctuation.definition.list_item"),
+            quote: get_style("markup.quote"),
+            text: highlighter.get_default(),
+        }
+    }
+}
+
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("md") || extension.eq_ignore_ascii_case("markdown")
+        })
+}
+
+pub fn highlight(syntaxes: &SyntaxSet, theme: &Theme, content: &str) -> Vec<String> {
+    let syntax = syntaxes
+        .find_syntax_by_extension("md")
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut result = Vec::new();
+    for line in LinesWithEndings::from(content) {
+        let highlighted = match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => as_24_bit_terminal_escaped(&ranges[..], false),
+            Err(err) => {
+                tracing::error!("unable to highlight markdown line: {:?}", err);
+                line.to_string()
+            }
+        };
+        result.push(highlighted);
+    }
+    result
+}
+
+pub fn render(syntaxes: &SyntaxSet, theme: &Theme, lines: &[String]) -> Vec<String> {
+    let styles = Styles::new(theme);
+
+    let mut code_block: Option<(String, HighlightLines)> = None;
+    let mut result = Vec::new();
+    for line in lines {
+        let line = Ansi::new(line).to_stripped_string();
+        let line = line.as_str();
+        let trimmed = line.trim_start();
+        if let Some((fence, highlighter)) = &mut code_block {
+            if trimmed.starts_with(fence.as_str()) {
+                code_block = None;
+            } else {
+                let code = format!("{}\n", line);
+                let highlighted = match highlighter.highlight_line(&code, syntaxes) {
+                    Ok(ranges) => as_24_bit_terminal_escaped(&ranges[..], false),
+                    Err(err) => {
+                        tracing::error!("unable to highlight code line: {:?}", err);
+                        line.to_string()
+                    }
+                };
+                result.push(format!(
+                    "{}{}{}",
+                    CODE_INDENT,
+                    highlighted.trim_end_matches('\n'),
+                    RESET
+                ));
+            }
+            continue;
+        }
+
+        if let Some(fence) = get_fence(trimmed) {
+            let language = trimmed[fence.len()..]
+                .split_whitespace()
+                .next()
+                .unwrap_or_default();
+
+            let syntax = syntaxes
+                .find_syntax_by_token(language)
+                .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+            code_block = Some((fence.to_string(), HighlightLines::new(syntax, theme)));
+            continue;
+        }
+
+        result.push(render_line(line, &styles));
+    }
+    result
+}
+
+fn get_fence(line: &str) -> Option<&str> {
+    let character = match line.chars().next() {
+        Some(it @ ('`' | '~')) => it,
+        _ => return None,
+    };
+
+    let length = line.chars().take_while(|c| *c == character).count();
+    if length < 3 {
+        return None;
+    }
+
+    Some(&line[..length])
+}
+
+fn render_line(line: &str, styles: &Styles) -> String {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if let Some((level, text)) = get_heading(trimmed) {
+        let attributes = if level == 1 { BOLD_UNDERLINE } else { BOLD };
+        return paint(&styles.heading, attributes, text);
+    }
+
+    if let Some(text) = trimmed.strip_prefix('>') {
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        return format!(
+            "{}{}{}",
+            indent,
+            paint(&styles.quote, "", "│ "),
+            render_inline(text, styles)
+        );
+    }
+
+    if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        return format!(
+            "{}{}{}",
+            indent,
+            paint(&styles.list, "", "• "),
+            render_inline(text, styles)
+        );
+    }
+
+    if let Some((marker, text)) = get_ordered_list_item(trimmed) {
+        return format!(
+            "{}{}{}",
+            indent,
+            paint(&styles.list, "", marker),
+            render_inline(text, styles)
+        );
+    }
+
+    format!("{}{}", indent, render_inline(trimmed, styles))
+}
+
+fn get_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+
+    let text = &line[level..];
+    if !text.is_empty() && !text.starts_with(' ') {
+        return None;
+    }
+
+    Some((level, text.trim().trim_end_matches('#').trim_end()))
+}
+
+fn get_ordered_list_item(line: &str) -> Option<(&str, &str)> {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+
+    let rest = &line[digits..];
+    if rest.starts_with(". ") || rest.starts_with(") ") {
+        Some(line.split_at(digits + 2))
+    } else {
+        None
+    }
+}
+
+fn render_inline(line: &str, styles: &Styles) -> String {
+    let mut result = String::new();
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(character) = rest.chars().next() {
+        if let Some((span, remaining)) = get_span(rest, styles) {
+            result.push_str(&paint(&styles.text, "", &mem::take(&mut text)));
+            result.push_str(&span);
+            rest = remaining;
+        } else {
+            text.push(character);
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+    result.push_str(&paint(&styles.text, "", &text));
+    result
+}
+
+fn get_span<'a>(line: &'a str, styles: &Styles) -> Option<(String, &'a str)> {
+    if let Some(inner) = line.strip_prefix('`') {
+        let end = inner.find('`')?;
+        return Some((paint(&styles.code, "", &inner[..end]), &inner[end + 1..]));
+    }
+
+    if let Some(inner) = line.strip_prefix("![").or_else(|| line.strip_prefix('[')) {
+        let end = inner.find("](")?;
+        let target = &inner[end + 2..];
+        let close = target.find(')')?;
+        return Some((
+            paint(&styles.link, UNDERLINE, &inner[..end]),
+            &target[close + 1..],
+        ));
+    }
+
+    if let Some(inner) = line.strip_prefix("**") {
+        let end = find_emphasis_end(inner, "**")?;
+        return Some((paint(&styles.text, BOLD, &inner[..end]), &inner[end + 2..]));
+    }
+
+    if let Some(inner) = line.strip_prefix('*') {
+        let end = find_emphasis_end(inner, "*")?;
+        return Some((
+            paint(&styles.text, ITALIC, &inner[..end]),
+            &inner[end + 1..],
+        ));
+    }
+
+    None
+}
+
+fn find_emphasis_end(inner: &str, delimiter: &str) -> Option<usize> {
+    if inner.is_empty() || inner.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    inner
+        .match_indices(delimiter)
+        .map(|(end, _)| end)
+        .find(|end| *end > 0 && !inner[..*end].ends_with(char::is_whitespace))
+}
+
+fn paint(style: &Style, attributes: &str, text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        "{}{}{}",
+        attributes,
+        as_24_bit_terminal_escaped(&[(*style, text)], false),
+        RESET
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
+    use yeet_buffer::model::ansi::Ansi;
+
+    use super::{is_markdown, render};
+
+    fn render_stripped(content: &str) -> (Vec<String>, Vec<String>) {
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let theme = &themes.themes["base16-ocean.dark"];
+
+        let lines: Vec<_> = content.lines().map(str::to_string).collect();
+        let lines = render(&syntaxes, theme, &lines);
+        let stripped = lines
+            .iter()
+            .map(|line| Ansi::new(line).to_stripped_string())
+            .collect();
+
+        (lines, stripped)
+    }
+
+    #[test]
+    fn render_styles_headings_lists_and_quotes() {
+        let (lines, stripped) =
+            render_stripped("# Title #\n## Usage\n- item\n  * nested\n2. second\n> quoted\n");
+
+        assert_eq!(
+            stripped,
+            vec![
+                "Title",
+                "Usage",
+                "• item",
+                "  • nested",
+                "2. second",
+                "│ quoted"
+            ]
+        );
+        assert!(lines[0].starts_with("\x1b[1;4m"));
+        assert!(lines[1].starts_with("\x1b[1m"));
+    }
+
+    #[test]
+    fn render_replaces_inline_markup() {
+        let (lines, stripped) =
+            render_stripped("see [docs](https://example.com), `code` and **bold** *text*");
+
+        assert_eq!(stripped, vec!["see docs, code and bold text"]);
+        assert!(lines[0].contains("\x1b[4m"));
+        assert!(lines[0].contains("\x1b[3m"));
+    }
+
+    #[test]
+    fn render_keeps_delimiters_surrounded_by_whitespace() {
+        let (lines, stripped) = render_stripped("a * b * c and 2 ** 3 ** 4");
+
+        assert_eq!(stripped, vec!["a * b * c and 2 ** 3 ** 4"]);
+        assert!(!lines[0].contains("\x1b[3m"));
+        assert!(!lines[0].contains("\x1b[1m"));
+    }
+
+    #[test]
+    fn render_strips_highlighting_from_source_lines() {
+        let (_, stripped) = render_stripped("\x1b[38;2;1;2;3m# Title\x1b[0m");
+
+        assert_eq!(stripped, vec!["Title"]);
+    }
+
+    #[test]
+    fn render_highlights_fenced_code_blocks_without_fences() {
+        let (lines, stripped) =
+            render_stripped("```rust\nfn main() {}\n# not a heading\n```\ndone");
+
+        assert_eq!(
+            stripped,
+            vec!["  fn main() {}", "  # not a heading", "done"]
+        );
+        assert!(!lines[1].contains("\x1b[1m"));
+    }
+
+    #[test]
+    fn is_markdown_matches_extension_case_insensitive() {
+        assert!(is_markdown(Path::new("/repo/README.md")));
+        assert!(is_markdown(Path::new("/repo/notes.Markdown")));
+        assert!(!is_markdown(Path::new("/repo/md")));
+        assert!(!is_markdown(Path::new("/repo/main.rs")));
+    }
+}
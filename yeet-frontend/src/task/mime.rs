@@ -0,0 +1,183 @@
+use std::{path::Path, str};
+
+use tokio::{fs::File, io::AsyncReadExt};
+
+use crate::error::AppError;
+
+pub const DIRECTORY: &str = "inode/directory";
+pub const EMPTY: &str = "inode/x-empty";
+pub const GZIP: &str = "application/gzip";
+pub const OCTET_STREAM: &str = "application/octet-stream";
+pub const PLAIN_TEXT: &str = "text/plain";
+
+const SNIFF_LENGTH: u64 = 8192;
+
+const INTERPRETERS: [(&str, &str, &str); 12] = [
+    ("bash", "text/x-shellscript", "sh"),
+    ("dash", "text/x-shellscript", "sh"),
+    ("fish", "text/x-fish", "fish"),
+    ("ksh", "text/x-shellscript", "sh"),
+    ("lua", "text/x-lua", "lua"),
+    ("node", "text/javascript", "js"),
+    ("perl", "text/x-perl", "pl"),
+    ("php", "text/x-php", "php"),
+    ("python", "text/x-python", "py"),
+    ("ruby", "text/x-ruby", "rb"),
+    ("sh", "text/x-shellscript", "sh"),
+    ("zsh", "text/x-shellscript", "sh"),
+];
+
+const ZIP_CONTAINERS: [&str; 5] = [
+    "application/zip",
+    "application/java-archive",
+    "application/epub+zip",
+    "application/vnd.openxmlformats-officedocument.",
+    "application/vnd.oasis.opendocument.",
+];
+
+pub async fn detect(path: &Path) -> Result<String, AppError> {
+    if tokio::fs::metadata(path).await?.is_dir() {
+        return Ok(DIRECTORY.to_string());
+    }
+
+    let mut bytes = Vec::new();
+    File::open(path)
+        .await?
+        .take(SNIFF_LENGTH)
+        .read_to_end(&mut bytes)
+        .await?;
+
+    Ok(get_from_bytes(&bytes))
+}
+
+pub fn is_text(mime: &str) -> bool {
+    mime.starts_with("text/") || mime == EMPTY
+}
+
+pub fn is_zip_container(mime: &str) -> bool {
+    ZIP_CONTAINERS.iter().any(|container| {
+        mime == *container || container.ends_with('.') && mime.starts_with(container)
+    })
+}
+
+pub fn get_syntax_extension(mime: &str) -> Option<&'static str> {
+    INTERPRETERS
+        .iter()
+        .find(|(_, it, _)| *it == mime)
+        .map(|(_, _, extension)| *extension)
+}
+
+pub fn is_binary_content(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+
+    match str::from_utf8(bytes) {
+        Ok(_) => false,
+        Err(err) => err.error_len().is_some(),
+    }
+}
+
+pub fn get_from_bytes(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return EMPTY.to_string();
+    }
+
+    if let Some(mime) = get_from_shebang(bytes) {
+        return mime.to_string();
+    }
+
+    if let Some(kind) = infer::get(bytes).filter(|kind| kind.mime_type() != "text/x-shellscript") {
+        return kind.mime_type().to_ascii_lowercase();
+    }
+
+    if is_binary_content(bytes) {
+        OCTET_STREAM.to_string()
+    } else {
+        PLAIN_TEXT.to_string()
+    }
+}
+
+fn get_from_shebang(bytes: &[u8]) -> Option<&'static str> {
+    let line = bytes
+        .strip_prefix(b"#!")?
+        .split(|byte| *byte == b'\n')
+        .next()?;
+    let line = str::from_utf8(line).ok()?;
+
+    let mut arguments = line.split_whitespace();
+    let mut program = arguments.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = arguments.find(|argument| !argument.starts_with('-'))?;
+    }
+
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|(name, _, _)| *name == program)
+        .map(|(_, mime, _)| *mime)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_from_bytes, get_syntax_extension, is_binary_content, is_zip_container, EMPTY,
+        OCTET_STREAM, PLAIN_TEXT,
+    };
+
+    #[test]
+    fn is_binary_content_detects_null_and_invalid_utf8() {
+        assert!(is_binary_content(b"ELF\0\x01"));
+        assert!(is_binary_content(&[0xff, 0xfe, b'a']));
+        assert!(!is_binary_content("plain text ü".as_bytes()));
+        assert!(!is_binary_content(&"ü".as_bytes()[..1]));
+    }
+
+    #[test]
+    fn get_from_bytes_resolves_interpreter_from_shebang() {
+        assert_eq!(get_from_bytes(b"#!/usr/bin/env python3\n"), "text/x-python");
+        assert_eq!(
+            get_from_bytes(b"#!/usr/bin/env -S node --harmony\n"),
+            "text/javascript"
+        );
+        assert_eq!(
+            get_from_bytes(b"#!/bin/bash -e\necho"),
+            "text/x-shellscript"
+        );
+        assert_eq!(get_from_bytes(b"#!/opt/custom\n"), PLAIN_TEXT);
+    }
+
+    #[test]
+    fn get_from_bytes_sniffs_magic_bytes() {
+        assert_eq!(
+            get_from_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            "image/png"
+        );
+        assert_eq!(
+            get_from_bytes(&[0x1f, 0x8b, 0x08, 0x00]),
+            "application/gzip"
+        );
+    }
+
+    #[test]
+    fn get_from_bytes_falls_back_to_text_or_binary() {
+        assert_eq!(get_from_bytes(b""), EMPTY);
+        assert_eq!(get_from_bytes(b"hello\n"), PLAIN_TEXT);
+        assert_eq!(get_from_bytes(&[0x01, 0x00, 0x02]), OCTET_STREAM);
+    }
+
+    #[test]
+    fn is_zip_container_matches_zip_based_formats() {
+        assert!(is_zip_container("application/zip"));
+        assert!(is_zip_container(
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        ));
+        assert!(!is_zip_container("application/gzip"));
+    }
+
+    #[test]
+    fn get_syntax_extension_maps_script_types() {
+        assert_eq!(get_syntax_extension("text/x-python"), Some("py"));
+        assert_eq!(get_syntax_extension("text/plain"), None);
+    }
+}
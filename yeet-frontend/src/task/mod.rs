@@ -1,17 +1,13 @@
 use std::{
     fmt::{Debug, Display},
+    io,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use ratatui::layout::Rect;
 use ratatui_image::picker::Picker;
-use syntect::{
-    easy::HighlightLines,
-    highlighting::ThemeSet,
-    parsing::SyntaxSet,
-    util::{as_24_bit_terminal_escaped, LinesWithEndings},
-};
+use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
 use tokio::{
     fs,
     sync::{
@@ -22,27 +18,47 @@ use tokio::{
 use tokio_util::sync::CancellationToken;
 use yeet_keymap::{
     conversion,
-    message::{KeySequence, KeymapMessage},
+    message::{KeySequence, KeymapMessage, PrintContent},
     MessageResolver,
 };
 
 use crate::{
     error::AppError,
-    event::{Envelope, LogSeverity, Message, MessageSource},
+    event::{EnumerationEntry, Envelope, LogSeverity, Message, MessageSource, Preview},
     init::{
         junkyard::{self, cache_and_compress, compress, restore},
         mark::{load_marks_from_file, save_marks_to_file},
     },
     model::{junkyard::FileEntry, mark::Marks},
+    settings::PreviewLimit,
 };
 
+use self::{cache::PreviewCacheKey, progress::ProgressReporter};
+
+mod archive;
+mod cache;
 mod command;
+mod copy;
+mod exif;
+mod git;
+mod hex;
 mod image;
+mod markdown;
+mod mime;
+mod permission;
+mod progress;
 mod syntax;
 
+pub use cache::PreviewCache;
+pub use copy::{is_cross_device, remove_recursive};
+pub use mime::{detect as detect_mime, DIRECTORY as MIME_DIRECTORY};
+pub use permission::{OwnerChange, PermissionChange};
+pub use progress::{ProgressRead, ProgressWrite};
+pub use syntax::Continuation;
+
 pub enum Task {
     AddPath(PathBuf),
-    CopyPath(PathBuf, PathBuf),
+    CopyPath(PathBuf, PathBuf, Existing),
     DeleteMarks(Vec<char>),
     DeletePath(PathBuf),
     DeleteJunkYardEntry(FileEntry),
@@ -52,20 +68,51 @@ pub enum Task {
     ExecuteRg(PathBuf, String),
     ExecuteZoxide(String),
     HighlightHelp(usize, String),
-    LoadPreview(PathBuf, Rect),
+    LinkPath(PathBuf, PathBuf, Existing),
+    LoadEditContent(PathBuf),
+    LoadGitStatus(PathBuf, Option<PathBuf>),
+    LoadPreview(PathBuf, Rect, PreviewLimit),
+    LoadPreviewContinuation(PathBuf, Continuation, PreviewLimit),
     PluginSync(Vec<yeet_plugin::PluginSpec>, usize),
     PluginUpdate(Vec<yeet_plugin::PluginSpec>, usize),
-    RenamePath(PathBuf, PathBuf),
-    RestorePath(FileEntry, PathBuf),
+    RenamePath(PathBuf, PathBuf, Existing),
+    RenamePaths(Vec<(PathBuf, PathBuf)>),
+    RestorePath(FileEntry, PathBuf, Existing),
+    SaveEditContent(PathBuf, String),
+    SetOwner(Vec<PathBuf>, OwnerChange, bool),
+    SetPermissions(Vec<PathBuf>, PermissionChange, bool),
+    SymlinkPath(PathBuf, PathBuf, Existing),
     TrashPath(FileEntry),
     YankPath(FileEntry),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Existing {
+    Fail,
+    Overwrite(FileEntry),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TaskKind {
+    #[default]
+    Other,
+    Preview,
+}
+
 impl Task {
+    pub fn kind(&self) -> TaskKind {
+        match self {
+            Task::LoadPreview(_, _, _) | Task::LoadPreviewContinuation(_, _, _) => {
+                TaskKind::Preview
+            }
+            _ => TaskKind::Other,
+        }
+    }
+
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Task::AddPath(path) => write!(f, "AddPath({:?})", path),
-            Task::CopyPath(src, dst) => write!(f, "CopyPath({:?}, {:?})", src, dst),
+            Task::CopyPath(src, dst, _) => write!(f, "CopyPath({:?}, {:?})", src, dst),
             Task::DeleteMarks(marks) => write!(f, "DeleteMarks({:?})", marks),
             Task::DeletePath(path) => write!(f, "DeletePath({:?})", path),
             Task::DeleteJunkYardEntry(entry) => write!(f, "DeleteJunkYardEntry({:?})", entry),
@@ -75,11 +122,28 @@ impl Task {
             Task::ExecuteRg(base, params) => write!(f, "ExecuteRg({:?}, {:?})", base, params),
             Task::ExecuteZoxide(params) => write!(f, "ExecuteZoxide({:?})", params),
             Task::HighlightHelp(id, _) => write!(f, "HighlightHelp({})", id),
-            Task::LoadPreview(path, rect) => write!(f, "LoadPreview({:?}, {})", path, rect),
+            Task::LinkPath(src, dst, _) => write!(f, "LinkPath({:?}, {:?})", src, dst),
+            Task::LoadEditContent(path) => write!(f, "LoadEditContent({:?})", path),
+            Task::LoadGitStatus(path, _) => write!(f, "LoadGitStatus({:?})", path),
+            Task::LoadPreview(path, rect, _) => write!(f, "LoadPreview({:?}, {})", path, rect),
+            Task::LoadPreviewContinuation(path, continuation, _) => write!(
+                f,
+                "LoadPreviewContinuation({:?}, {})",
+                path, continuation.offset
+            ),
             Task::PluginSync(specs, _) => write!(f, "PluginSync({} plugins)", specs.len()),
             Task::PluginUpdate(specs, _) => write!(f, "PluginUpdate({} plugins)", specs.len()),
-            Task::RenamePath(old, new) => write!(f, "RenamePath({:?}, {:?})", old, new),
-            Task::RestorePath(entry, path) => write!(f, "RestorePath({:?}, {:?})", entry, path),
+            Task::RenamePath(old, new, _) => write!(f, "RenamePath({:?}, {:?})", old, new),
+            Task::RenamePaths(renames) => write!(f, "RenamePaths({:?})", renames),
+            Task::RestorePath(entry, path, _) => {
+                write!(f, "RestorePath({:?}, {:?})", entry, path)
+            }
+            Task::SaveEditContent(path, _) => write!(f, "SaveEditContent({:?})", path),
+            Task::SetOwner(paths, owner, _) => write!(f, "SetOwner({:?}, {:?})", paths, owner),
+            Task::SetPermissions(paths, mode, _) => {
+                write!(f, "SetPermissions({:?}, {:?})", paths, mode)
+            }
+            Task::SymlinkPath(link, dst, _) => write!(f, "SymlinkPath({:?}, {:?})", link, dst),
             Task::TrashPath(entry) => write!(f, "TrashPath({:?})", entry),
             Task::YankPath(entry) => write!(f, "YankPath({:?})", entry),
         }
@@ -104,7 +168,9 @@ impl PartialEq for Task {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Task::AddPath(p1), Task::AddPath(p2)) => p1 == p2,
-            (Task::CopyPath(s1, t1), Task::CopyPath(s2, t2)) => s1 == s2 && t1 == t2,
+            (Task::CopyPath(s1, t1, e1), Task::CopyPath(s2, t2, e2)) => {
+                s1 == s2 && t1 == t2 && e1 == e2
+            }
             (Task::DeleteMarks(m1), Task::DeleteMarks(m2)) => m1 == m2,
             (Task::DeletePath(p1), Task::DeletePath(p2)) => p1 == p2,
             (Task::DeleteJunkYardEntry(e1), Task::DeleteJunkYardEntry(e2)) => e1 == e2,
@@ -112,11 +178,37 @@ impl PartialEq for Task {
                 p1 == p2 && s1 == s2
             }
             (Task::HighlightHelp(i1, c1), Task::HighlightHelp(i2, c2)) => i1 == i2 && c1 == c2,
-            (Task::LoadPreview(p1, r1), Task::LoadPreview(p2, r2)) => p1 == p2 && r1 == r2,
+            (Task::LinkPath(s1, t1, e1), Task::LinkPath(s2, t2, e2)) => {
+                s1 == s2 && t1 == t2 && e1 == e2
+            }
+            (Task::LoadEditContent(p1), Task::LoadEditContent(p2)) => p1 == p2,
+            (Task::LoadGitStatus(p1, r1), Task::LoadGitStatus(p2, r2)) => p1 == p2 && r1 == r2,
+            (Task::LoadPreview(p1, r1, l1), Task::LoadPreview(p2, r2, l2)) => {
+                p1 == p2 && r1 == r2 && l1 == l2
+            }
+            (
+                Task::LoadPreviewContinuation(p1, c1, l1),
+                Task::LoadPreviewContinuation(p2, c2, l2),
+            ) => p1 == p2 && c1 == c2 && l1 == l2,
             (Task::PluginSync(_, _), Task::PluginSync(_, _)) => true,
             (Task::PluginUpdate(_, _), Task::PluginUpdate(_, _)) => true,
-            (Task::RenamePath(o1, n1), Task::RenamePath(o2, n2)) => o1 == o2 && n1 == n2,
-            (Task::RestorePath(e1, p1), Task::RestorePath(e2, p2)) => e1 == e2 && p1 == p2,
+            (Task::RenamePath(o1, n1, e1), Task::RenamePath(o2, n2, e2)) => {
+                o1 == o2 && n1 == n2 && e1 == e2
+            }
+            (Task::RenamePaths(r1), Task::RenamePaths(r2)) => r1 == r2,
+            (Task::RestorePath(e1, p1, x1), Task::RestorePath(e2, p2, x2)) => {
+                e1 == e2 && p1 == p2 && x1 == x2
+            }
+            (Task::SaveEditContent(p1, c1), Task::SaveEditContent(p2, c2)) => p1 == p2 && c1 == c2,
+            (Task::SetOwner(p1, o1, r1), Task::SetOwner(p2, o2, r2)) => {
+                p1 == p2 && o1 == o2 && r1 == r2
+            }
+            (Task::SetPermissions(p1, m1, r1), Task::SetPermissions(p2, m2, r2)) => {
+                p1 == p2 && m1 == m2 && r1 == r2
+            }
+            (Task::SymlinkPath(l1, t1, e1), Task::SymlinkPath(l2, t2, e2)) => {
+                l1 == l2 && t1 == t2 && e1 == e2
+            }
             (Task::TrashPath(e1), Task::TrashPath(e2)) => e1 == e2,
             (Task::YankPath(e1), Task::YankPath(e2)) => e1 == e2,
             _ => false,
@@ -125,6 +217,7 @@ impl PartialEq for Task {
 }
 
 pub struct TaskManager {
+    pub preview_cache: Arc<Mutex<PreviewCache>>,
     pub sender: mpsc::UnboundedSender<Task>,
 }
 
@@ -143,6 +236,8 @@ impl TaskManager {
 
         let resolver = resolver.clone();
         let syntax_theme_name = Arc::new(syntax_theme_name);
+        let preview_cache = Arc::new(Mutex::new(PreviewCache::default()));
+        let task_preview_cache = preview_cache.clone();
         let (task_sender, mut task_receiver) = mpsc::unbounded_channel::<Task>();
         tokio::spawn(async move {
             let highlighter = Arc::new(Mutex::new((
@@ -166,26 +261,36 @@ impl TaskManager {
                         let resolver = resolver.clone();
                         let highlighter = highlighter.clone();
                         let picker = picker.clone();
+                        let preview_cache = task_preview_cache.clone();
                         let syntax_theme_name = syntax_theme_name.clone();
 
                         tokio::spawn(async move {
                             let id = task.to_string();
-                            send_task_started(&sender.clone(), id.as_str(), child_token.clone()).await;
+                            send_task_started(
+                                &sender.clone(),
+                                id.as_str(),
+                                task.kind(),
+                                child_token.clone(),
+                            )
+                            .await;
 
-                            if let Err(err) = run_task(
+                            let result = run_task(
                                 &sender.clone(),
+                                id.as_str(),
                                 resolver,
                                 highlighter,
                                 picker,
+                                preview_cache,
                                 task,
                                 child_token,
                                 &syntax_theme_name,
-                            ).await
-                            {
+                            ).await;
+
+                            if let Err(err) = &result {
                                 tracing::error!("handling task failed: {:?}", err);
                             };
 
-                            send_task_finished(&sender, id.as_str()).await;
+                            send_task_finished(&sender, id.as_str(), result.is_ok()).await;
                         });
                     }
                 }
@@ -193,11 +298,25 @@ impl TaskManager {
         });
 
         Self {
+            preview_cache,
             sender: task_sender,
         }
     }
 }
 
+fn get_syntax_theme<'a>(
+    theme_set: &'a ThemeSet,
+    syntax_theme_name: &str,
+) -> &'a syntect::highlighting::Theme {
+    theme_set.themes.get(syntax_theme_name).unwrap_or_else(|| {
+        tracing::error!(
+            "syntax theme '{}' not found, falling back to 'base16-eighties.dark'",
+            syntax_theme_name
+        );
+        &theme_set.themes["base16-eighties.dark"]
+    })
+}
+
 #[cfg(target_os = "windows")]
 fn resolve_picker() -> Option<Picker> {
     // FIX: https://github.com/benjajaja/ratatui-image/issues/32
@@ -210,11 +329,14 @@ fn resolve_picker() -> Option<Picker> {
     Picker::from_query_stdio().ok()
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_task(
     sender: &Sender<Envelope>,
+    identifier: &str,
     resolver: Arc<Mutex<MessageResolver>>,
     highlighter: Arc<Mutex<(SyntaxSet, ThemeSet)>>,
     picker: Arc<Mutex<Option<Picker>>>,
+    preview_cache: Arc<Mutex<PreviewCache>>,
     task: Task,
     cancellation: CancellationToken,
     syntax_theme_name: &str,
@@ -241,24 +363,37 @@ async fn run_task(
                 }
             }
         }
-        Task::CopyPath(source, target) => {
-            if !source.exists() || target.exists() {
+        Task::CopyPath(source, target, existing) => {
+            if fs::symlink_metadata(&source).await.is_err() {
                 return Err(AppError::InvalidTargetPath);
             }
+            prepare_target(&source, &target, existing).await?;
+
+            let mut reporter =
+                ProgressReporter::new(sender, identifier, progress::tree_size(&source));
+            let failures = tokio::task::spawn_blocking(move || {
+                copy::copy_recursive(&source, &target, &cancellation, |bytes| {
+                    reporter.advance(bytes)
+                })
+            })
+            .await?;
 
-            fs::copy(source, target).await?;
+            if !failures.is_empty() {
+                emit_copy_failures(sender, "Copying", failures).await;
+                return Err(AppError::ExecutionFailed("copy".to_owned()));
+            }
         }
         Task::DeleteMarks(marks) => {
             let mut current = Marks::default();
             if let Err(err) = load_marks_from_file(&mut current) {
-                emit_error(sender, err).await;
+                emit_error(sender, &err).await;
             } else {
                 for mark in marks {
                     current.entries.remove(&mark);
                 }
 
                 if let Err(error) = save_marks_to_file(&current) {
-                    emit_error(sender, error).await;
+                    emit_error(sender, &error).await;
                 }
             }
         }
@@ -308,7 +443,7 @@ async fn run_task(
             drop(resolver);
 
             if let Err(error) = sender.send(envelope).await {
-                emit_error(sender, AppError::ActionSendFailed(error)).await;
+                emit_error(sender, &AppError::ActionSendFailed(error)).await;
             }
         }
         Task::EnumerateDirectory(path, selection) => {
@@ -332,7 +467,10 @@ async fn run_task(
                                     name.push('/');
                                 }
 
-                                cache.push(name);
+                                cache.push(EnumerationEntry {
+                                    name,
+                                    link: read_link(&path).await,
+                                });
 
                                 (true, path)
                             } else {
@@ -358,7 +496,15 @@ async fn run_task(
                         }
 
                         if !is_selection || entry.path() != selection_path {
-                            cache.push(content);
+                            cache.push(EnumerationEntry {
+                                name: content,
+                                link: match entry.file_type().await {
+                                    Ok(file_type) if file_type.is_symlink() => {
+                                        read_link(&entry.path()).await
+                                    }
+                                    _ => None,
+                                },
+                            });
                         }
 
                         if cache.len() >= cache_size {
@@ -396,7 +542,7 @@ async fn run_task(
                 }
             }
             Err(err) => {
-                emit_error(sender, err).await;
+                emit_error(sender, &err).await;
             }
         },
         Task::ExecuteRg(base, params) => match command::rg(base.as_path(), params).await {
@@ -410,7 +556,7 @@ async fn run_task(
                 }
             }
             Err(err) => {
-                emit_error(sender, err).await;
+                emit_error(sender, &err).await;
             }
         },
         Task::ExecuteZoxide(params) => match command::zoxide(params).await {
@@ -424,100 +570,253 @@ async fn run_task(
                 }
             }
             Err(err) => {
-                emit_error(sender, err).await;
+                emit_error(sender, &err).await;
             }
         },
         Task::HighlightHelp(buffer_id, content) => {
             let highlighter = highlighter.lock().await;
-            let (syntaxes, theme_set) = (&highlighter.0, &highlighter.1);
-            let theme = theme_set.themes.get(syntax_theme_name).unwrap_or_else(|| {
-                tracing::error!(
-                    "syntax theme '{}' not found, falling back to 'base16-eighties.dark'",
-                    syntax_theme_name
-                );
-                &theme_set.themes["base16-eighties.dark"]
-            });
-
-            let syntax = syntaxes
-                .find_syntax_by_extension("md")
-                .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
-
-            let mut hl = HighlightLines::new(syntax, theme);
-            let mut result = Vec::new();
-            for line in LinesWithEndings::from(&content) {
-                let highlighted = match hl.highlight_line(line, syntaxes) {
-                    Ok(ranges) => as_24_bit_terminal_escaped(&ranges[..], false),
-                    Err(err) => {
-                        tracing::error!("unable to highlight help line: {:?}", err);
-                        line.to_string()
-                    }
-                };
-                result.push(highlighted);
-            }
+            let theme = get_syntax_theme(&highlighter.1, syntax_theme_name);
+            let result = markdown::highlight(&highlighter.0, theme, &content);
 
             let msg = Message::HelpHighlighted(buffer_id, result);
             if let Err(error) = sender.send(to_envelope(vec![msg])).await {
                 tracing::error!("sending message failed: {:?}", error);
             }
         }
-        Task::LoadPreview(path, rect) => {
-            let mime = if let Some(mime) = infer::get_from_path(&path)? {
-                let kind = mime.mime_type().split('/').collect::<Vec<_>>();
-                if kind.len() != 2 {
-                    return Err(AppError::InvalidMimeType);
-                }
-                Some(kind[0].to_ascii_lowercase())
+        Task::LinkPath(source, target, existing) => {
+            if fs::symlink_metadata(&source).await.is_err() {
+                return Err(AppError::InvalidTargetPath);
+            }
+            prepare_target(&source, &target, existing).await?;
+
+            fs::hard_link(&source, &target).await?;
+        }
+        Task::LoadEditContent(path) => {
+            let bytes = fs::read(&path).await?;
+            let message = if mime::is_binary_content(&bytes) {
+                Message::Log(
+                    LogSeverity::Error,
+                    format!("Edit failed. {:?} is not a text file.", path),
+                )
             } else {
-                None
+                Message::EditContentLoaded(path, String::from_utf8_lossy(&bytes).to_string())
             };
 
-            let content = match mime.as_deref() {
-                Some("image") => {
-                    let mut picker = picker.lock().await;
+            let result = sender.send(to_envelope(vec![message])).await;
+            if let Err(error) = result {
+                tracing::error!("sending message failed: {:?}", error);
+            }
+        }
+        Task::LoadGitStatus(path, repository) => {
+            let status = git::status(&path, repository.as_deref()).await?;
+            let result = sender
+                .send(to_envelope(vec![Message::GitStatusLoaded(path, status)]))
+                .await;
+
+            if let Err(error) = result {
+                tracing::error!("sending message failed: {:?}", error);
+            }
+        }
+        Task::LoadPreview(path, rect, limit) => {
+            let cache_key = PreviewCacheKey::new(&path, rect).await;
+            let cached = match &cache_key {
+                Some(key) => preview_cache.lock().await.get(key),
+                None => None,
+            };
 
-                    image::load(&mut picker, &path, &rect).await
+            if let Some((content, mime)) = cached {
+                let result = sender
+                    .send(to_envelope(vec![Message::PreviewLoaded(
+                        content,
+                        Some(mime),
+                    )]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
                 }
-                _ => {
+
+                return Ok(());
+            }
+
+            let mime = mime::detect(&path).await?;
+            let content = if let Some(kind) = archive::get_kind(&path, &mime) {
+                match archive::load(&path, kind, &cancellation).await {
+                    Some(it) => it,
+                    None => return Ok(()),
+                }
+            } else if mime.starts_with("image/") {
+                let mut picker = picker.lock().await;
+
+                image::load(&mut picker, &path, &rect).await
+            } else if mime == mime::GZIP {
+                let bytes = archive::decompress(&path, limit.bytes).await?;
+                if mime::is_binary_content(&bytes) {
+                    hex::from_bytes(&path, bytes, &rect)
+                } else {
                     let highlighter = highlighter.lock().await;
-                    let (syntaxes, theme_set) = (&highlighter.0, &highlighter.1);
-                    let theme = theme_set.themes.get(syntax_theme_name).unwrap_or_else(|| {
-                        tracing::error!(
-                            "syntax theme '{}' not found, falling back to 'base16-eighties.dark'",
-                            syntax_theme_name
-                        );
-                        &theme_set.themes["base16-eighties.dark"]
-                    });
-
-                    syntax::highlight(syntaxes, theme, &path).await
+                    let theme = get_syntax_theme(&highlighter.1, syntax_theme_name);
+                    let content = String::from_utf8_lossy(&bytes).to_string();
+
+                    match syntax::highlight_decompressed(
+                        &highlighter.0,
+                        theme,
+                        &path,
+                        content,
+                        &cancellation,
+                    ) {
+                        Some(it) => it,
+                        None => return Ok(()),
+                    }
+                }
+            } else if mime::is_text(&mime) {
+                let highlighter = highlighter.lock().await;
+                let theme = get_syntax_theme(&highlighter.1, syntax_theme_name);
+
+                let preview =
+                    syntax::highlight(&highlighter.0, theme, &path, &mime, &limit, &cancellation);
+                match preview.await {
+                    Some(Preview::Content(path, lines)) if markdown::is_markdown(&path) => {
+                        let rendered = markdown::render(&highlighter.0, theme, &lines);
+                        Preview::Markdown(path, lines, rendered, None)
+                    }
+                    Some(Preview::Partial(path, lines, continuation))
+                        if markdown::is_markdown(&path) =>
+                    {
+                        let rendered = markdown::render(&highlighter.0, theme, &lines);
+                        Preview::Markdown(path, lines, rendered, Some(continuation))
+                    }
+                    Some(it) => it,
+                    None => return Ok(()),
+                }
+            } else {
+                hex::load(&path, &rect).await
+            };
+
+            if let Some(key) = cache_key {
+                preview_cache.lock().await.insert(key, &content, &mime);
+            }
+
+            let result = sender
+                .send(to_envelope(vec![Message::PreviewLoaded(
+                    content,
+                    Some(mime),
+                )]))
+                .await;
+
+            if let Err(error) = result {
+                tracing::error!("sending message failed: {:?}", error);
+            }
+        }
+        Task::LoadPreviewContinuation(path, continuation, limit) => {
+            let highlighter = highlighter.lock().await;
+            let theme = get_syntax_theme(&highlighter.1, syntax_theme_name);
+
+            let preview = syntax::resume(
+                &highlighter.0,
+                theme,
+                &path,
+                continuation,
+                &limit,
+                &cancellation,
+            );
+            let content = match preview.await {
+                Some(Preview::Continued(path, lines, continuation))
+                    if markdown::is_markdown(&path) =>
+                {
+                    let rendered = markdown::render(&highlighter.0, theme, &lines);
+                    Preview::ContinuedMarkdown(path, lines, rendered, continuation)
                 }
+                Some(it) => it,
+                None => return Ok(()),
             };
 
             let result = sender
-                .send(to_envelope(vec![Message::PreviewLoaded(content)]))
+                .send(to_envelope(vec![Message::PreviewLoaded(content, None)]))
                 .await;
 
             if let Err(error) = result {
                 tracing::error!("sending message failed: {:?}", error);
             }
         }
-        Task::RenamePath(old, new) => {
-            if !old.exists() || new.exists() {
+        Task::RenamePath(old, new, existing) => {
+            if !old.exists() {
                 return Err(AppError::InvalidTargetPath);
             }
+            prepare_target(&old, &new, existing).await?;
+
+            move_path(sender, identifier, old, new, &cancellation).await?;
+        }
+        Task::RenamePaths(renames) => {
+            for (old, new) in renames {
+                let result =
+                    move_path(sender, identifier, old.clone(), new.clone(), &cancellation).await;
+
+                if let Err(error) = result {
+                    let message = format!(
+                        "Renaming {:?} to {:?} failed, remaining renames were skipped: {}",
+                        old, new, error
+                    );
+                    let _ = sender
+                        .send(to_envelope(vec![Message::Log(LogSeverity::Error, message)]))
+                        .await;
+
+                    return Err(AppError::FileOperationFailed(error));
+                }
+            }
+        }
+        Task::RestorePath(entry, path, existing) => {
+            prepare_target(&entry.cache, &path, existing).await?;
 
-            fs::rename(old, new).await?;
+            let total = fs::metadata(&entry.cache).await?.len();
+            let mut reporter = ProgressReporter::new(sender, identifier, total);
+            restore(entry, path, |bytes| reporter.advance(bytes))?;
         }
-        Task::RestorePath(entry, path) => {
-            restore(entry, path)?;
+        Task::SaveEditContent(path, content) => {
+            if let Err(error) = fs::write(&path, content).await {
+                let error = error.into();
+                emit_error(sender, &error).await;
+                return Err(error);
+            }
+        }
+        Task::SetOwner(paths, owner, recursive) => {
+            let result = tokio::task::spawn_blocking(move || {
+                permission::set_owner(&paths, &owner, recursive)
+            })
+            .await?;
+
+            emit_permission_result(sender, "Changed ownership", result).await;
+        }
+        Task::SetPermissions(paths, mode, recursive) => {
+            let result = tokio::task::spawn_blocking(move || {
+                permission::set_permissions(&paths, &mode, recursive)
+            })
+            .await?;
+
+            emit_permission_result(sender, "Changed permissions", result).await;
+        }
+        Task::SymlinkPath(link, target, existing) => {
+            let source = match target.parent() {
+                Some(parent) => parent.join(&link),
+                None => link.clone(),
+            };
+            // NOTE: links may point to an ancestor of the target, e.g. `..`
+            replace_existing(&target, existing).await?;
+            copy::create_symlink(&link, &source, &target)?;
         }
         Task::TrashPath(entry) => {
-            if let Err(error) = cache_and_compress(entry).await {
-                emit_error(sender, error).await;
+            let total = progress::archive_size(&entry.target);
+            let mut reporter = ProgressReporter::new(sender, identifier, total);
+            if let Err(error) = cache_and_compress(entry, |bytes| reporter.advance(bytes)).await {
+                emit_error(sender, &error).await;
+                return Err(error);
             }
         }
         Task::YankPath(entry) => {
-            if let Err(error) = compress(entry).await {
-                emit_error(sender, error).await;
+            let total = progress::archive_size(&entry.target);
+            let mut reporter = ProgressReporter::new(sender, identifier, total);
+            if let Err(error) = compress(entry, |bytes| reporter.advance(bytes)).await {
+                emit_error(sender, &error).await;
             }
         }
         Task::PluginSync(specs, concurrency) => {
@@ -631,6 +930,7 @@ async fn run_task(
 async fn send_task_started(
     sender: &Sender<Envelope>,
     identifier: &str,
+    kind: TaskKind,
     cancellation: CancellationToken,
 ) {
     tracing::trace!("task started: {:?}", identifier);
@@ -642,6 +942,7 @@ async fn send_task_started(
     if let Err(err) = sender
         .send(to_envelope(vec![Message::TaskStarted(
             identifier.to_owned(),
+            kind,
             cancellation,
         )]))
         .await
@@ -650,7 +951,7 @@ async fn send_task_started(
     };
 }
 
-async fn send_task_finished(sender: &Sender<Envelope>, identifier: &str) {
+async fn send_task_finished(sender: &Sender<Envelope>, identifier: &str, succeeded: bool) {
     tracing::trace!("task ended: {:?}", identifier);
 
     if identifier == Task::EmitMessages(Vec::new()).to_string() {
@@ -658,14 +959,131 @@ async fn send_task_finished(sender: &Sender<Envelope>, identifier: &str) {
     }
 
     if let Err(err) = sender
-        .send(to_envelope(vec![Message::TaskEnded(identifier.to_owned())]))
+        .send(to_envelope(vec![Message::TaskEnded(
+            identifier.to_owned(),
+            succeeded,
+        )]))
         .await
     {
         tracing::error!("task ended send failed: {:?}", err);
     };
 }
 
-async fn emit_error(sender: &Sender<Envelope>, error: AppError) {
+async fn prepare_target(source: &Path, target: &Path, existing: Existing) -> Result<(), AppError> {
+    if target.starts_with(source) {
+        return Err(AppError::InvalidTargetPath);
+    }
+
+    replace_existing(target, existing).await
+}
+
+async fn replace_existing(target: &Path, existing: Existing) -> Result<(), AppError> {
+    if std::fs::symlink_metadata(target).is_err() {
+        return Ok(());
+    }
+
+    match existing {
+        Existing::Fail => Err(AppError::InvalidTargetPath),
+        Existing::Overwrite(entry) => {
+            tracing::info!("moving overwritten path to junk yard: {:?}", target);
+            cache_and_compress(entry, |_| {}).await
+        }
+    }
+}
+
+async fn move_path(
+    sender: &Sender<Envelope>,
+    identifier: &str,
+    source: PathBuf,
+    target: PathBuf,
+    cancellation: &CancellationToken,
+) -> io::Result<()> {
+    match fs::rename(&source, &target).await {
+        Ok(()) => Ok(()),
+        Err(error) if copy::is_cross_device(&error) => {
+            tracing::info!("moving {:?} across devices to {:?}", source, target);
+
+            let mut reporter =
+                ProgressReporter::new(sender, identifier, progress::tree_size(&source));
+            let cancellation = cancellation.clone();
+            let failed_path = source.clone();
+            let failures = tokio::task::spawn_blocking(move || {
+                copy::move_across_devices(&source, &target, &cancellation, |bytes| {
+                    reporter.advance(bytes)
+                })
+            })
+            .await
+            .map_err(io::Error::other)?;
+
+            if failures.is_empty() {
+                Ok(())
+            } else {
+                emit_copy_failures(sender, "Moving", failures).await;
+                Err(io::Error::other(format!(
+                    "moving {:?} across devices failed",
+                    failed_path
+                )))
+            }
+        }
+        Err(error) => Err(error),
+    }
+}
+
+async fn emit_permission_result(
+    sender: &Sender<Envelope>,
+    operation: &str,
+    (changed, failures): (usize, Vec<permission::PermissionFailure>),
+) {
+    let mut contents = if failures.is_empty() {
+        vec![PrintContent::Success(format!(
+            "{} of {} entries",
+            operation, changed
+        ))]
+    } else {
+        vec![PrintContent::Error(format!(
+            "{} of {} entries, {} failed",
+            operation,
+            changed,
+            failures.len()
+        ))]
+    };
+    contents.extend(
+        failures
+            .into_iter()
+            .map(|failure| PrintContent::Error(format!("{:?}: {}", failure.path, failure.error))),
+    );
+
+    let message = Message::Keymap(KeymapMessage::Print(contents));
+    let _ = sender.send(to_envelope(vec![message])).await;
+}
+
+async fn read_link(path: &Path) -> Option<PathBuf> {
+    fs::read_link(path).await.ok()
+}
+
+async fn emit_copy_failures(
+    sender: &Sender<Envelope>,
+    operation: &str,
+    failures: Vec<copy::CopyFailure>,
+) {
+    if failures.is_empty() {
+        return;
+    }
+
+    let messages = failures
+        .into_iter()
+        .map(|failure| {
+            Message::Log(
+                LogSeverity::Error,
+                format!("{} {:?} failed: {}", operation, failure.path, failure.error),
+            )
+        })
+        .collect();
+
+    let _ = sender.send(to_envelope(messages)).await;
+}
+
+async fn emit_error(sender: &Sender<Envelope>, error: &AppError) {
     tracing::error!("task failed: {:?}", error);
 
     let error = format!("Error: {:?}", error);
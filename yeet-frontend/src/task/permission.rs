@@ -0,0 +1,471 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const USER: u8 = 0b001;
+const GROUP: u8 = 0b010;
+const OTHER: u8 = 0b100;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PermissionChange {
+    Octal(u32),
+    Symbolic(Vec<SymbolicClause>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SymbolicClause {
+    who: u8,
+    operations: Vec<(Operator, String)>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operator {
+    Add,
+    Remove,
+    Set,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerChange {
+    pub user: Option<String>,
+    pub group: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct PermissionFailure {
+    pub path: PathBuf,
+    pub error: io::Error,
+}
+
+impl PermissionChange {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        if !mode.is_empty() && mode.len() <= 4 && mode.chars().all(|c| c.is_digit(8)) {
+            return u32::from_str_radix(mode, 8)
+                .map(PermissionChange::Octal)
+                .map_err(|err| format!("invalid mode {}: {}", mode, err));
+        }
+
+        let mut clauses = Vec::new();
+        for clause in mode.split(',') {
+            clauses.push(parse_clause(clause).ok_or(format!("invalid mode {}", mode))?);
+        }
+
+        Ok(PermissionChange::Symbolic(clauses))
+    }
+
+    pub fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            PermissionChange::Octal(octal) => return *octal,
+            PermissionChange::Symbolic(clauses) => clauses,
+        };
+
+        let mut result = mode & 0o7777;
+        for clause in clauses {
+            for (operator, permissions) in &clause.operations {
+                let bits = get_bits(clause.who, permissions, result, is_dir);
+                result = match operator {
+                    Operator::Add => result | bits,
+                    Operator::Remove => result & !bits,
+                    Operator::Set => {
+                        (result & !get_bits(clause.who, "rwxst", result, is_dir)) | bits
+                    }
+                };
+            }
+        }
+
+        result
+    }
+}
+
+impl OwnerChange {
+    pub fn parse(owner: &str) -> Result<Self, String> {
+        let (user, group) = match owner.split_once(':') {
+            Some((user, group)) => (user, group),
+            None => (owner, ""),
+        };
+
+        let to_option = |name: &str| (!name.is_empty()).then(|| name.to_string());
+        let change = OwnerChange {
+            user: to_option(user),
+            group: to_option(group),
+        };
+
+        if change.user.is_none() && change.group.is_none() {
+            Err(format!("invalid owner {}", owner))
+        } else {
+            Ok(change)
+        }
+    }
+}
+
+pub fn set_permissions(
+    paths: &[PathBuf],
+    change: &PermissionChange,
+    recursive: bool,
+) -> (usize, Vec<PermissionFailure>) {
+    walk(paths, recursive, |path, is_symlink| {
+        if is_symlink {
+            return Ok(false);
+        }
+        set_mode(path, change)?;
+        Ok(true)
+    })
+}
+
+pub fn set_owner(
+    paths: &[PathBuf],
+    change: &OwnerChange,
+    recursive: bool,
+) -> (usize, Vec<PermissionFailure>) {
+    let ids = resolve_ids(change);
+    let (uid, gid) = match ids {
+        Ok(it) => it,
+        Err(error) => {
+            let failures = paths
+                .iter()
+                .map(|path| PermissionFailure {
+                    path: path.clone(),
+                    error: io::Error::new(error.kind(), error.to_string()),
+                })
+                .collect();
+            return (0, failures);
+        }
+    };
+
+    walk(paths, recursive, |path, is_symlink| {
+        set_ids(path, uid, gid, is_symlink)?;
+        Ok(true)
+    })
+}
+
+fn walk(
+    paths: &[PathBuf],
+    recursive: bool,
+    mut change: impl FnMut(&Path, bool) -> io::Result<bool>,
+) -> (usize, Vec<PermissionFailure>) {
+    let mut changed = 0;
+    let mut failures = Vec::new();
+    let mut pending: Vec<(PathBuf, bool)> = paths.iter().map(|path| (path.clone(), true)).collect();
+
+    while let Some((path, is_root)) = pending.pop() {
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(it) => it,
+            Err(error) => {
+                failures.push(PermissionFailure { path, error });
+                continue;
+            }
+        };
+
+        let is_symlink = metadata.file_type().is_symlink();
+        match change(&path, is_symlink && !is_root) {
+            Ok(true) => changed += 1,
+            Ok(false) => {}
+            Err(error) => failures.push(PermissionFailure {
+                path: path.clone(),
+                error,
+            }),
+        }
+
+        if recursive && metadata.is_dir() {
+            match fs::read_dir(&path) {
+                Ok(entries) => {
+                    pending.extend(entries.flatten().map(|entry| (entry.path(), false)));
+                }
+                Err(error) => failures.push(PermissionFailure { path, error }),
+            }
+        }
+    }
+
+    (changed, failures)
+}
+
+fn parse_clause(clause: &str) -> Option<SymbolicClause> {
+    let mut who = 0;
+    let mut chars = clause.chars().peekable();
+    while let Some(character) = chars.peek() {
+        who |= match character {
+            'u' => USER,
+            'g' => GROUP,
+            'o' => OTHER,
+            'a' => USER | GROUP | OTHER,
+            _ => break,
+        };
+        chars.next();
+    }
+
+    if who == 0 {
+        who = USER | GROUP | OTHER;
+    }
+
+    let mut operations = Vec::new();
+    for character in chars {
+        let operator = match character {
+            '+' => Some(Operator::Add),
+            '-' => Some(Operator::Remove),
+            '=' => Some(Operator::Set),
+            'r' | 'w' | 'x' | 'X' | 's' | 't' => None,
+            _ => return None,
+        };
+
+        match (operator, operations.last_mut()) {
+            (Some(operator), _) => operations.push((operator, String::new())),
+            (None, Some((_, permissions))) => permissions.push(character),
+            (None, None) => return None,
+        }
+    }
+
+    if operations.is_empty() {
+        None
+    } else {
+        Some(SymbolicClause { who, operations })
+    }
+}
+
+fn get_bits(who: u8, permissions: &str, mode: u32, is_dir: bool) -> u32 {
+    let mut bits = 0;
+    for permission in permissions.chars() {
+        let base = match permission {
+            'r' => 0o4,
+            'w' => 0o2,
+            'x' => 0o1,
+            'X' if is_dir || mode & 0o111 != 0 => 0o1,
+            _ => 0,
+        };
+
+        if who & USER != 0 {
+            bits |= base << 6;
+        }
+        if who & GROUP != 0 {
+            bits |= base << 3;
+        }
+        if who & OTHER != 0 {
+            bits |= base;
+        }
+
+        if permission == 's' && who & USER != 0 {
+            bits |= 0o4000;
+        }
+        if permission == 's' && who & GROUP != 0 {
+            bits |= 0o2000;
+        }
+        if permission == 't' && who & OTHER != 0 {
+            bits |= 0o1000;
+        }
+    }
+
+    bits
+}
+
+#[cfg(not(target_os = "windows"))]
+fn resolve_ids(change: &OwnerChange) -> io::Result<(Option<u32>, Option<u32>)> {
+    use nix::unistd::{Group, User};
+
+    let uid = match &change.user {
+        Some(user) => Some(match user.parse() {
+            Ok(id) => id,
+            Err(_) => User::from_name(user)?
+                .ok_or_else(|| not_found("user", user))?
+                .uid
+                .as_raw(),
+        }),
+        None => None,
+    };
+    let gid = match &change.group {
+        Some(group) => Some(match group.parse() {
+            Ok(id) => id,
+            Err(_) => Group::from_name(group)?
+                .ok_or_else(|| not_found("group", group))?
+                .gid
+                .as_raw(),
+        }),
+        None => None,
+    };
+
+    Ok((uid, gid))
+}
+
+#[cfg(target_os = "windows")]
+fn resolve_ids(_change: &OwnerChange) -> io::Result<(Option<u32>, Option<u32>)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "changing ownership is not supported on windows",
+    ))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn not_found(kind: &str, name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} {} not found", kind, name),
+    )
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_mode(path: &Path, change: &PermissionChange) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::metadata(path)?;
+    let mode = change.apply(metadata.permissions().mode(), metadata.is_dir());
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(target_os = "windows")]
+fn set_mode(_path: &Path, _change: &PermissionChange) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "changing permissions is not supported on windows",
+    ))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_ids(path: &Path, uid: Option<u32>, gid: Option<u32>, is_symlink: bool) -> io::Result<()> {
+    if is_symlink {
+        std::os::unix::fs::lchown(path, uid, gid)
+    } else {
+        std::os::unix::fs::chown(path, uid, gid)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_ids(
+    _path: &Path,
+    _uid: Option<u32>,
+    _gid: Option<u32>,
+    _is_symlink: bool,
+) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "changing ownership is not supported on windows",
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{OwnerChange, PermissionChange};
+
+    fn apply(mode: &str, current: u32, is_dir: bool) -> u32 {
+        PermissionChange::parse(mode)
+            .expect("valid mode")
+            .apply(current, is_dir)
+    }
+
+    #[test]
+    fn parse_accepts_octal_modes() {
+        assert_eq!(
+            PermissionChange::parse("755"),
+            Ok(PermissionChange::Octal(0o755))
+        );
+        assert_eq!(
+            PermissionChange::parse("4750"),
+            Ok(PermissionChange::Octal(0o4750))
+        );
+        assert!(PermissionChange::parse("789").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_symbolic_modes() {
+        assert!(PermissionChange::parse("").is_err());
+        assert!(PermissionChange::parse("u").is_err());
+        assert!(PermissionChange::parse("ux").is_err());
+        assert!(PermissionChange::parse("u+q").is_err());
+        assert!(PermissionChange::parse("u+x,").is_err());
+    }
+
+    #[test]
+    fn apply_adds_and_removes_bits() {
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("go-r", 0o644, false), 0o600);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("a-w,u+w", 0o666, false), 0o644);
+        assert_eq!(apply("u+x-w", 0o644, false), 0o544);
+    }
+
+    #[test]
+    fn apply_sets_bits_for_who() {
+        assert_eq!(apply("g=r", 0o775, false), 0o745);
+        assert_eq!(apply("o=", 0o777, false), 0o770);
+    }
+
+    #[test]
+    fn apply_handles_special_bits() {
+        assert_eq!(apply("u+s", 0o755, false), 0o4755);
+        assert_eq!(apply("g+s", 0o755, true), 0o2755);
+        assert_eq!(apply("+t", 0o777, true), 0o1777);
+        assert_eq!(apply("755", 0o4777, false), 0o755);
+    }
+
+    #[test]
+    fn apply_sets_conditional_execute_for_directories() {
+        assert_eq!(apply("a+X", 0o644, false), 0o644);
+        assert_eq!(apply("a+X", 0o744, false), 0o755);
+        assert_eq!(apply("a+X", 0o644, true), 0o755);
+    }
+
+    #[test]
+    fn owner_parse_splits_user_and_group() {
+        assert_eq!(
+            OwnerChange::parse("alice:staff"),
+            Ok(OwnerChange {
+                user: Some("alice".to_string()),
+                group: Some("staff".to_string()),
+            })
+        );
+        assert_eq!(
+            OwnerChange::parse(":staff"),
+            Ok(OwnerChange {
+                user: None,
+                group: Some("staff".to_string()),
+            })
+        );
+        assert_eq!(
+            OwnerChange::parse("1000"),
+            Ok(OwnerChange {
+                user: Some("1000".to_string()),
+                group: None,
+            })
+        );
+        assert!(OwnerChange::parse(":").is_err());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn resolve_ids_accepts_names_and_numbers() {
+        let change = OwnerChange {
+            user: Some("root".to_string()),
+            group: Some("42".to_string()),
+        };
+        assert_eq!(super::resolve_ids(&change).unwrap(), (Some(0), Some(42)));
+
+        let change = OwnerChange {
+            user: Some("yeet-no-such-user".to_string()),
+            group: None,
+        };
+        let error = super::resolve_ids(&change).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn set_permissions_applies_recursively() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("file"), "").unwrap();
+        fs::set_permissions(folder.join("file"), fs::Permissions::from_mode(0o600)).unwrap();
+
+        let change = PermissionChange::parse("go+r").unwrap();
+        let (changed, failures) =
+            super::set_permissions(std::slice::from_ref(&folder), &change, true);
+
+        assert_eq!(changed, 2);
+        assert!(failures.is_empty());
+        let mode = fs::metadata(folder.join("file"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o644);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc::Sender;
+
+use crate::event::{Envelope, Message};
+
+use super::to_envelope;
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+const TAR_BLOCK_SIZE: u64 = 512;
+
+pub struct ProgressReporter {
+    done: u64,
+    identifier: String,
+    last_report: Option<Instant>,
+    sender: Sender<Envelope>,
+    total: u64,
+}
+
+impl ProgressReporter {
+    pub fn new(sender: &Sender<Envelope>, identifier: &str, total: u64) -> Self {
+        let mut reporter = Self {
+            done: 0,
+            identifier: identifier.to_owned(),
+            last_report: None,
+            sender: sender.clone(),
+            total,
+        };
+
+        reporter.report();
+        reporter
+    }
+
+    pub fn advance(&mut self, bytes: u64) {
+        self.done = self.done.saturating_add(bytes);
+        self.report();
+    }
+
+    fn report(&mut self) {
+        if self
+            .last_report
+            .is_some_and(|last| last.elapsed() < REPORT_INTERVAL)
+        {
+            return;
+        }
+
+        let message = Message::TaskProgress(self.identifier.clone(), self.done, self.total);
+        match self.sender.try_send(to_envelope(vec![message])) {
+            Ok(()) => self.last_report = Some(Instant::now()),
+            Err(err) => tracing::trace!("skipping progress report: {:?}", err),
+        }
+    }
+}
+
+pub struct ProgressRead<R, F: FnMut(u64)> {
+    inner: R,
+    progress: F,
+}
+
+impl<R, F: FnMut(u64)> ProgressRead<R, F> {
+    pub fn new(inner: R, progress: F) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressRead<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        (self.progress)(read as u64);
+        Ok(read)
+    }
+}
+
+pub struct ProgressWrite<W, F: FnMut(u64)> {
+    inner: W,
+    progress: F,
+}
+
+impl<W, F: FnMut(u64)> ProgressWrite<W, F> {
+    pub fn new(inner: W, progress: F) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl<W: Write, F: FnMut(u64)> Write for ProgressWrite<W, F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        (self.progress)(written as u64);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub fn tree_size(path: &Path) -> u64 {
+    walk_sizes(path, |size| size)
+}
+
+pub fn archive_size(path: &Path) -> u64 {
+    let entries = walk_sizes(path, |size| {
+        TAR_BLOCK_SIZE + size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE
+    });
+
+    entries + 2 * TAR_BLOCK_SIZE
+}
+
+fn walk_sizes(path: &Path, size_of_entry: impl Fn(u64) -> u64) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(it) => it,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            if let Ok(read_dir) = fs::read_dir(&path) {
+                pending.extend(read_dir.flatten().map(|entry| entry.path()));
+            }
+            total += size_of_entry(0);
+        } else if metadata.is_symlink() {
+            total += size_of_entry(0);
+        } else {
+            total += size_of_entry(metadata.len());
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        io::{Read, Write},
+    };
+
+    use super::{archive_size, tree_size, ProgressRead, ProgressWrite};
+
+    #[test]
+    fn tree_size_sums_nested_file_sizes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("a"), [0u8; 10]).unwrap();
+        fs::write(dir.path().join("nested/b"), [0u8; 32]).unwrap();
+
+        assert_eq!(tree_size(dir.path()), 42);
+    }
+
+    #[test]
+    fn archive_size_accounts_for_headers_and_padding() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("a");
+        fs::write(&file, [0u8; 10]).unwrap();
+
+        assert_eq!(archive_size(&file), 512 + 512 + 1024);
+    }
+
+    #[test]
+    fn progress_read_reports_read_bytes() {
+        let mut reported = 0;
+        let mut content = String::new();
+        ProgressRead::new("content".as_bytes(), |bytes| reported += bytes)
+            .read_to_string(&mut content)
+            .unwrap();
+
+        assert_eq!(content, "content");
+        assert_eq!(reported, 7);
+    }
+
+    #[test]
+    fn progress_write_reports_written_bytes() {
+        let mut reported = 0;
+        let mut target = Vec::new();
+        ProgressWrite::new(&mut target, |bytes| reported += bytes)
+            .write_all(b"content")
+            .unwrap();
+
+        assert_eq!(target, b"content");
+        assert_eq!(reported, 7);
+    }
+}
@@ -1,56 +1,236 @@
-use std::path::Path;
+use std::{
+    fmt::Debug,
+    io::{self, SeekFrom},
+    path::Path,
+};
 
 use syntect::{
     easy::HighlightLines,
-    highlighting::Theme,
-    parsing::{SyntaxReference, SyntaxSet},
+    highlighting::{HighlightState, Theme},
+    parsing::{ParseState, SyntaxReference, SyntaxSet},
     util::{as_24_bit_terminal_escaped, LinesWithEndings},
 };
-use tokio::fs;
-
-use crate::event::Preview;
-
-pub async fn highlight(syntaxes: &SyntaxSet, theme: &Theme, path: &Path) -> Preview {
-    match fs::read_to_string(path).await {
-        Ok(content) => {
-            let syntax = resolve_syntax(syntaxes, &content, path).await;
-            if let Some(syntax) = syntax {
-                tracing::debug!("syntax: {:?}", syntax.name);
-
-                let mut highlighter = HighlightLines::new(syntax, theme);
-                let mut result = vec![];
-                for line in LinesWithEndings::from(&content) {
-                    let highlighted = match highlighter.highlight_line(line, syntaxes) {
-                        Ok(ranges) => &as_24_bit_terminal_escaped(&ranges[..], false),
-                        Err(err) => {
-                            tracing::error!("unable to highlight line: {:?}", err);
-                            line
-                        }
-                    };
-                    result.push(highlighted.to_string());
-                }
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tokio_util::sync::CancellationToken;
 
-                Preview::Content(path.to_path_buf(), result)
-            } else {
-                tracing::debug!("unable to resolve syntax for: {:?}", path);
+use crate::{event::Preview, settings::PreviewLimit};
 
-                let content: Vec<_> = content.lines().map(|l| l.to_string()).collect();
+use super::mime;
 
-                Preview::Content(path.to_path_buf(), content)
-            }
+#[derive(Clone)]
+pub struct Continuation {
+    pub offset: u64,
+    state: Option<(HighlightState, ParseState)>,
+}
+
+impl Continuation {
+    #[cfg(test)]
+    pub fn new(offset: u64) -> Self {
+        Self {
+            offset,
+            state: None,
         }
+    }
+}
+
+impl Debug for Continuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Continuation({})", self.offset)
+    }
+}
+
+impl PartialEq for Continuation {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+struct Chunk {
+    content: String,
+    next_offset: Option<u64>,
+}
+
+pub async fn highlight(
+    syntaxes: &SyntaxSet,
+    theme: &Theme,
+    path: &Path,
+    mime: &str,
+    limit: &PreviewLimit,
+    cancellation: &CancellationToken,
+) -> Option<Preview> {
+    let chunk = match read_chunk(path, 0, limit).await {
+        Ok(chunk) => chunk,
         Err(err) => {
             tracing::error!("reading file failed: {:?} {:?}", path, err);
-            Preview::None(path.to_path_buf())
+            return Some(Preview::None(path.to_path_buf()));
         }
+    };
+
+    highlight_first_chunk(syntaxes, theme, path, path, mime, chunk, cancellation)
+}
+
+pub fn highlight_decompressed(
+    syntaxes: &SyntaxSet,
+    theme: &Theme,
+    path: &Path,
+    content: String,
+    cancellation: &CancellationToken,
+) -> Option<Preview> {
+    let name = path.file_stem().map(Path::new).unwrap_or(path);
+    let mime = mime::get_from_bytes(content.as_bytes());
+    let chunk = Chunk {
+        content,
+        next_offset: None,
+    };
+
+    highlight_first_chunk(syntaxes, theme, path, name, &mime, chunk, cancellation)
+}
+
+fn highlight_first_chunk(
+    syntaxes: &SyntaxSet,
+    theme: &Theme,
+    path: &Path,
+    name: &Path,
+    mime: &str,
+    chunk: Chunk,
+    cancellation: &CancellationToken,
+) -> Option<Preview> {
+    let state = match resolve_syntax(syntaxes, &chunk.content, name, mime) {
+        Some(syntax) => {
+            tracing::debug!("syntax: {:?}", syntax.name);
+            Some(HighlightLines::new(syntax, theme).state())
+        }
+        None => {
+            tracing::debug!("unable to resolve syntax for: {:?}", path);
+            None
+        }
+    };
+
+    let (lines, continuation) = highlight_chunk(syntaxes, theme, chunk, state, cancellation)?;
+
+    Some(match continuation {
+        Some(continuation) => Preview::Partial(path.to_path_buf(), lines, continuation),
+        None => Preview::Content(path.to_path_buf(), lines),
+    })
+}
+
+pub async fn resume(
+    syntaxes: &SyntaxSet,
+    theme: &Theme,
+    path: &Path,
+    continuation: Continuation,
+    limit: &PreviewLimit,
+    cancellation: &CancellationToken,
+) -> Option<Preview> {
+    let chunk = match read_chunk(path, continuation.offset, limit).await {
+        Ok(chunk) => chunk,
+        Err(err) => {
+            tracing::error!("reading file failed: {:?} {:?}", path, err);
+            return Some(Preview::Continued(path.to_path_buf(), Vec::new(), None));
+        }
+    };
+
+    let (lines, continuation) =
+        highlight_chunk(syntaxes, theme, chunk, continuation.state, cancellation)?;
+
+    Some(Preview::Continued(path.to_path_buf(), lines, continuation))
+}
+
+fn highlight_chunk(
+    syntaxes: &SyntaxSet,
+    theme: &Theme,
+    chunk: Chunk,
+    state: Option<(HighlightState, ParseState)>,
+    cancellation: &CancellationToken,
+) -> Option<(Vec<String>, Option<Continuation>)> {
+    let mut result = vec![];
+    let state = match state {
+        Some((highlight_state, parse_state)) => {
+            let mut highlighter = HighlightLines::from_state(theme, highlight_state, parse_state);
+            for line in LinesWithEndings::from(&chunk.content) {
+                if cancellation.is_cancelled() {
+                    return None;
+                }
+
+                let highlighted = match highlighter.highlight_line(line, syntaxes) {
+                    Ok(ranges) => &as_24_bit_terminal_escaped(&ranges[..], false),
+                    Err(err) => {
+                        tracing::error!("unable to highlight line: {:?}", err);
+                        line
+                    }
+                };
+                result.push(highlighted.to_string());
+            }
+            Some(highlighter.state())
+        }
+        None => {
+            if cancellation.is_cancelled() {
+                return None;
+            }
+
+            result.extend(chunk.content.lines().map(|l| l.to_string()));
+            None
+        }
+    };
+
+    let continuation = chunk
+        .next_offset
+        .map(|offset| Continuation { offset, state });
+
+    Some((result, continuation))
+}
+
+async fn read_chunk(path: &Path, offset: u64, limit: &PreviewLimit) -> io::Result<Chunk> {
+    let mut file = File::open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+
+    let mut bytes = Vec::new();
+    file.take(limit.bytes as u64 + 1)
+        .read_to_end(&mut bytes)
+        .await?;
+
+    let is_truncated = bytes.len() > limit.bytes;
+    bytes.truncate(limit.bytes);
+
+    let mut length = bytes
+        .split_inclusive(|byte| *byte == b'\n')
+        .take(limit.lines)
+        .take_while(|line| !is_truncated || line.ends_with(b"\n"))
+        .map(|line| line.len())
+        .sum::<usize>();
+
+    if length == 0 && !bytes.is_empty() {
+        length = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(err) => err.valid_up_to().max(1),
+        };
     }
+
+    let next_offset = (is_truncated || length < bytes.len()).then_some(offset + length as u64);
+    bytes.truncate(length);
+
+    Ok(Chunk {
+        content: String::from_utf8_lossy(&bytes).to_string(),
+        next_offset,
+    })
 }
 
-async fn resolve_syntax<'a>(
+fn resolve_syntax<'a>(
     syntaxes: &'a SyntaxSet,
     content: &str,
     path: &Path,
+    mime: &str,
 ) -> Option<&'a SyntaxReference> {
+    if let Some(extension) = mime::get_syntax_extension(mime) {
+        let syntax = syntaxes.find_syntax_by_extension(extension);
+        if syntax.is_some() {
+            return syntax;
+        }
+    }
+
     let name = path
         .file_name()
         .map(|n| n.to_string_lossy())
@@ -71,3 +251,210 @@ async fn resolve_syntax<'a>(
 
     syntaxes.find_syntax_by_first_line(content)
 }
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{event::Preview, settings::PreviewLimit, task::mime};
+
+    use super::{highlight, highlight_decompressed, read_chunk, resolve_syntax, resume};
+
+    fn limit(bytes: usize, lines: usize) -> PreviewLimit {
+        PreviewLimit { bytes, lines }
+    }
+
+    fn write(dir: &tempfile::TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    async fn load(path: &Path, limit: &PreviewLimit) -> Option<Preview> {
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let theme = &themes.themes["base16-eighties.dark"];
+
+        let mut preview = highlight(
+            &syntaxes,
+            theme,
+            path,
+            mime::PLAIN_TEXT,
+            limit,
+            &CancellationToken::new(),
+        )
+        .await;
+        let mut lines = Vec::new();
+        loop {
+            match preview {
+                Some(Preview::Partial(_, content, continuation))
+                | Some(Preview::Continued(_, content, Some(continuation))) => {
+                    lines.extend(content);
+                    preview = resume(
+                        &syntaxes,
+                        theme,
+                        path,
+                        continuation,
+                        limit,
+                        &CancellationToken::new(),
+                    )
+                    .await;
+                }
+                Some(Preview::Content(_, content)) | Some(Preview::Continued(_, content, None)) => {
+                    lines.extend(content);
+                    return Some(Preview::Content(path.to_path_buf(), lines));
+                }
+                other => return other,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn read_chunk_stops_at_line_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "log.txt", "a\nb\nc\n");
+
+        let chunk = read_chunk(&path, 0, &limit(1024, 2)).await.unwrap();
+
+        assert_eq!(chunk.content, "a\nb\n");
+        assert_eq!(chunk.next_offset, Some(4));
+    }
+
+    #[tokio::test]
+    async fn read_chunk_stops_at_last_complete_line_within_byte_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "log.txt", "first\nsecond\n");
+
+        let chunk = read_chunk(&path, 0, &limit(8, 100)).await.unwrap();
+
+        assert_eq!(chunk.content, "first\n");
+        assert_eq!(chunk.next_offset, Some(6));
+    }
+
+    #[tokio::test]
+    async fn read_chunk_splits_lines_longer_than_byte_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "log.txt", "üüü");
+
+        let chunk = read_chunk(&path, 0, &limit(3, 100)).await.unwrap();
+
+        assert_eq!(chunk.content, "ü");
+        assert_eq!(chunk.next_offset, Some(2));
+    }
+
+    #[tokio::test]
+    async fn read_chunk_without_remaining_content_has_no_next_offset() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "log.txt", "a\nb");
+
+        let chunk = read_chunk(&path, 2, &limit(1024, 100)).await.unwrap();
+
+        assert_eq!(chunk.content, "b");
+        assert_eq!(chunk.next_offset, None);
+    }
+
+    #[tokio::test]
+    async fn highlight_in_chunks_matches_full_highlight() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = "/* a\n multi line\n comment */\nfn main() {}\n";
+        let path = write(&dir, "main.rs", content);
+
+        let chunked = load(&path, &limit(1024, 1)).await;
+        let full = load(&path, &limit(1024, 100)).await;
+
+        match (chunked, full) {
+            (Some(Preview::Content(_, chunked)), Some(Preview::Content(_, full))) => {
+                assert_eq!(chunked.len(), 4);
+                assert_eq!(chunked, full);
+            }
+            _ => panic!("expected content previews"),
+        }
+    }
+
+    #[tokio::test]
+    async fn highlight_returns_partial_preview_when_limit_is_reached() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "log.txt", "a\nb\nc\n");
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let theme = &themes.themes["base16-eighties.dark"];
+
+        let preview = highlight(
+            &syntaxes,
+            theme,
+            &path,
+            mime::PLAIN_TEXT,
+            &limit(1024, 2),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        match preview {
+            Some(Preview::Partial(_, lines, continuation)) => {
+                assert_eq!(lines.len(), 2);
+                assert_eq!(continuation.offset, 4);
+            }
+            _ => panic!("expected partial preview"),
+        }
+    }
+
+    #[tokio::test]
+    async fn highlight_stops_when_cancelled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write(&dir, "main.rs", "fn main() {}\n");
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+
+        let preview = highlight(
+            &syntaxes,
+            &themes.themes["base16-eighties.dark"],
+            &path,
+            mime::PLAIN_TEXT,
+            &limit(1024, 100),
+            &cancellation,
+        )
+        .await;
+
+        assert!(preview.is_none());
+    }
+
+    #[test]
+    fn resolve_syntax_prefers_detected_script_type() {
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let content = "#!/usr/bin/env python3\nprint('hi')\n";
+
+        let syntax = resolve_syntax(&syntaxes, content, Path::new("run.txt"), "text/x-python");
+        assert_eq!(syntax.map(|it| it.name.as_str()), Some("Python"));
+
+        let syntax = resolve_syntax(&syntaxes, "", Path::new("main.rs"), mime::PLAIN_TEXT);
+        assert_eq!(syntax.map(|it| it.name.as_str()), Some("Rust"));
+    }
+
+    #[test]
+    fn highlight_decompressed_uses_inner_file_name() {
+        let syntaxes = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+
+        let preview = highlight_decompressed(
+            &syntaxes,
+            &themes.themes["base16-eighties.dark"],
+            Path::new("/dir/main.rs.gz"),
+            "fn main() {}\n".to_string(),
+            &CancellationToken::new(),
+        );
+
+        match preview {
+            Some(Preview::Content(path, lines)) => {
+                assert_eq!(path, Path::new("/dir/main.rs.gz"));
+                assert_eq!(lines.len(), 1);
+                assert!(lines[0].contains("\x1b["));
+            }
+            _ => panic!("expected content preview"),
+        }
+    }
+}
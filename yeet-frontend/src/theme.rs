@@ -18,6 +18,8 @@ pub mod tokens {
     pub const STATUSLINE_BORDER_BG: &str = "StatusLineBorderBg";
     pub const STATUSLINE_BORDER_FG: &str = "StatusLineBorderFg";
     pub const STATUSLINE_PERMISSIONS_FG: &str = "StatusLinePermissionsFg";
+    pub const STATUSLINE_PROGRESS_FG: &str = "StatusLineProgressFg";
+    pub const STATUSLINE_GIT_BRANCH_FG: &str = "StatusLineGitBranchFg";
 
     // Diff indicators
     pub const DIFF_ADDED: &str = "DiffAdded";
@@ -27,10 +29,13 @@ pub mod tokens {
     // Buffer
     pub const CURSOR_LINE_BG: &str = "CursorLineBg";
     pub const SEARCH_BG: &str = "SearchBg";
+    pub const VISUAL_BG: &str = "VisualBg";
     pub const LINE_NR: &str = "LineNr";
     pub const CUR_LINE_NR: &str = "CurLineNr";
     pub const BUFFER_BG: &str = "BufferBg";
     pub const BUFFER_FG: &str = "BufferFg";
+    pub const SYMLINK_TARGET_FG: &str = "SymlinkTargetFg";
+    pub const HEX_NON_PRINTABLE_FG: &str = "HexNonPrintableFg";
 
     // Directory window borders
     pub const DIRECTORY_BORDER_FG: &str = "DirectoryBorderFg";
@@ -47,6 +52,11 @@ pub mod tokens {
     // Signs
     pub const SIGN_QFIX: &str = "SignQfix";
     pub const SIGN_MARK: &str = "SignMark";
+    pub const SIGN_GIT_MODIFIED: &str = "SignGitModified";
+    pub const SIGN_GIT_STAGED: &str = "SignGitStaged";
+    pub const SIGN_GIT_UNTRACKED: &str = "SignGitUntracked";
+    pub const SIGN_GIT_IGNORED: &str = "SignGitIgnored";
+    pub const SIGN_GIT_CONFLICTED: &str = "SignGitConflicted";
 
     // Semantic status
     pub const ERROR_FG: &str = "ErrorFg";
@@ -86,10 +96,14 @@ impl Default for Theme {
             Color::Rgb(128, 128, 128),
         );
         colors.insert(tokens::SEARCH_BG.to_string(), Color::Red);
+        colors.insert(tokens::VISUAL_BG.to_string(), Color::Rgb(68, 85, 136));
         colors.insert(tokens::LINE_NR.to_string(), Color::Rgb(128, 128, 128));
         colors.insert(tokens::CUR_LINE_NR.to_string(), Color::White);
         colors.insert(tokens::BUFFER_FG.to_string(), Color::White);
+        colors.insert(tokens::SYMLINK_TARGET_FG.to_string(), Color::Cyan);
+        colors.insert(tokens::HEX_NON_PRINTABLE_FG.to_string(), Color::DarkGray);
         colors.insert(tokens::STATUSLINE_PERMISSIONS_FG.to_string(), Color::Gray);
+        colors.insert(tokens::STATUSLINE_PROGRESS_FG.to_string(), Color::LightBlue);
         colors.insert(tokens::STATUSLINE_BORDER_BG.to_string(), Color::Black);
         colors.insert(tokens::DIRECTORY_BORDER_FG.to_string(), Color::Black);
         colors.insert(tokens::DIRECTORY_BORDER_BG.to_string(), Color::Reset);
@@ -97,6 +111,12 @@ impl Default for Theme {
         colors.insert(tokens::SPLIT_BORDER_BG.to_string(), Color::Reset);
         colors.insert(tokens::SIGN_QFIX.to_string(), Color::Rgb(255, 85, 255));
         colors.insert(tokens::SIGN_MARK.to_string(), Color::Rgb(85, 255, 255));
+        colors.insert(tokens::SIGN_GIT_MODIFIED.to_string(), Color::Yellow);
+        colors.insert(tokens::SIGN_GIT_STAGED.to_string(), Color::Green);
+        colors.insert(tokens::SIGN_GIT_UNTRACKED.to_string(), Color::LightBlue);
+        colors.insert(tokens::SIGN_GIT_IGNORED.to_string(), Color::DarkGray);
+        colors.insert(tokens::SIGN_GIT_CONFLICTED.to_string(), Color::Red);
+        colors.insert(tokens::STATUSLINE_GIT_BRANCH_FG.to_string(), Color::Magenta);
         colors.insert(tokens::ERROR_FG.to_string(), Color::Rgb(255, 85, 85));
         colors.insert(tokens::WARNING_FG.to_string(), Color::Rgb(241, 250, 140));
         colors.insert(tokens::SUCCESS_FG.to_string(), Color::Rgb(80, 250, 123));
@@ -216,6 +236,7 @@ impl Theme {
             buffer_fg: self.color(tokens::BUFFER_FG),
             cursor_line_bg: self.color(tokens::CURSOR_LINE_BG),
             search_bg: self.color(tokens::SEARCH_BG),
+            visual_bg: self.color(tokens::VISUAL_BG),
             line_nr: self.color(tokens::LINE_NR),
             cur_line_nr: self.color(tokens::CUR_LINE_NR),
             border_fg: self.color(border_fg_token),
@@ -224,11 +245,15 @@ impl Theme {
     }
 
     pub fn sign_qfix_style(&self) -> String {
-        format!("\x1b[1m{}", self.ansi_fg(tokens::SIGN_QFIX))
+        self.sign_style(tokens::SIGN_QFIX)
     }
 
     pub fn sign_mark_style(&self) -> String {
-        format!("\x1b[1m{}", self.ansi_fg(tokens::SIGN_MARK))
+        self.sign_style(tokens::SIGN_MARK)
+    }
+
+    pub fn sign_style(&self, token: &str) -> String {
+        format!("\x1b[1m{}", self.ansi_fg(token))
     }
 }
 
@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+use yeet_lua::{ApiJunk, ApiRequest, ApiState, ApiTab, LuaConfiguration};
+
+use crate::{
+    action::Action,
+    event::Message,
+    model::{junkyard::FileEntryStatus, App, State, Window},
+    update::{app, junkyard, tab},
+};
+
+pub fn invoke<T>(
+    lua: &LuaConfiguration,
+    app: &App,
+    state: &mut State,
+    call: impl FnOnce() -> T,
+) -> (T, Vec<Action>) {
+    yeet_lua::set_api_state(lua, get_state(app, state));
+    let result = call();
+    let requests = yeet_lua::take_api_requests(lua);
+    yeet_lua::clear_api_state(lua);
+
+    let messages = get_messages(app, requests);
+    state.pending_messages.splice(0..0, messages);
+
+    (result, take_pending_messages(&mut state.pending_messages))
+}
+
+pub fn take_pending_messages(pending: &mut Vec<Message>) -> Vec<Action> {
+    let end = pending
+        .iter()
+        .position(|message| {
+            matches!(
+                message,
+                Message::Keymap(KeymapMessage::ExecuteKeySequence(_))
+            )
+        })
+        .map_or(pending.len(), |index| index + 1);
+
+    if end == 0 {
+        Vec::new()
+    } else {
+        vec![Action::EmitMessages(pending.drain(..end).collect())]
+    }
+}
+
+fn get_messages(app: &App, requests: Vec<ApiRequest>) -> Vec<Message> {
+    let current_path = app
+        .current_window()
+        .ok()
+        .and_then(|window| get_directory_path(app, window, 1));
+
+    requests
+        .into_iter()
+        .map(|request| match request {
+            ApiRequest::Execute(command) => {
+                Message::Keymap(KeymapMessage::ExecuteCommandString(command))
+            }
+            ApiRequest::FeedKeys(keys) => Message::Keymap(KeymapMessage::ExecuteKeySequence(keys)),
+            ApiRequest::Navigate(path) => Message::Keymap(KeymapMessage::NavigateToPath(
+                get_absolute_path(current_path.as_deref(), path),
+            )),
+            ApiRequest::Print(content) => {
+                Message::Keymap(KeymapMessage::Print(vec![PrintContent::Default(content)]))
+            }
+            ApiRequest::QuickFixAdd(paths) => Message::QuickFixAdd(
+                paths
+                    .into_iter()
+                    .map(|path| get_absolute_path(current_path.as_deref(), path))
+                    .collect(),
+            ),
+        })
+        .collect()
+}
+
+fn get_absolute_path(current_path: Option<&Path>, path: PathBuf) -> PathBuf {
+    match current_path {
+        Some(current_path) if path.is_relative() => current_path.join(path),
+        _ => path,
+    }
+}
+
+fn get_state(app: &App, state: &State) -> ApiState {
+    let mut marks: Vec<_> = state
+        .marks
+        .entries
+        .iter()
+        .map(|(mark, path)| (*mark, path.clone()))
+        .collect();
+    marks.sort();
+
+    let junkyard = junkyard::get_junkyard_transactions(&state.junk)
+        .into_iter()
+        .map(|(register, transaction)| ApiJunk {
+            register,
+            paths: transaction
+                .entries
+                .iter()
+                .map(|entry| entry.target.clone())
+                .collect(),
+            ready: transaction
+                .entries
+                .iter()
+                .all(|entry| entry.status == FileEntryStatus::Ready),
+        })
+        .collect();
+
+    let tabs = tab::ordered_tab_ids(app)
+        .into_iter()
+        .map(|id| ApiTab {
+            id,
+            path: app
+                .tabs
+                .get(&id)
+                .and_then(|window| get_directory_path(app, window, 1)),
+            current: id == app.current_tab_id,
+        })
+        .collect();
+
+    let window = app.current_window().ok();
+    ApiState {
+        mode: state.modes.current.to_string(),
+        current_path: window.and_then(|window| get_directory_path(app, window, 1)),
+        selection: window.and_then(|window| get_directory_path(app, window, 2)),
+        qfix: state.qfix.entries.clone(),
+        qfix_index: state.qfix.current_index,
+        marks,
+        junkyard,
+        tabs,
+    }
+}
+
+fn get_directory_path(app: &App, window: &Window, index: usize) -> Option<PathBuf> {
+    let (parent_id, current_id, preview_id) = app::get_focused_directory_buffer_ids(window)?;
+    let buffer_id = [parent_id, current_id, preview_id].get(index).copied()?;
+    app::get_buffer_path(app, buffer_id)
+        .ok()?
+        .map(|path| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_buffer::model::Mode;
+    use yeet_keymap::message::KeymapMessage;
+
+    use crate::{
+        action::Action,
+        event::Message,
+        model::{App, Buffer, DirectoryBuffer, State},
+    };
+
+    use super::{get_absolute_path, get_state, invoke, take_pending_messages};
+
+    #[test]
+    fn get_state_reads_paths_marks_and_tabs() {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let current_id = crate::update::app::get_focused_directory_buffer_ids(window)
+            .expect("directory window")
+            .1;
+        app.contents.buffers.insert(
+            current_id,
+            Buffer::Directory(DirectoryBuffer {
+                path: PathBuf::from("/home"),
+                ..Default::default()
+            }),
+        );
+
+        let mut state = State::default();
+        state.modes.current = Mode::Normal;
+        state.marks.entries.insert('b', PathBuf::from("/b"));
+        state.marks.entries.insert('a', PathBuf::from("/a"));
+        state.qfix.entries.push(PathBuf::from("/home/a.txt"));
+
+        let api = get_state(&app, &state);
+
+        assert_eq!(api.mode, "normal");
+        assert_eq!(api.current_path, Some(PathBuf::from("/home")));
+        assert_eq!(
+            api.marks,
+            vec![('a', PathBuf::from("/a")), ('b', PathBuf::from("/b"))]
+        );
+        assert_eq!(api.qfix, vec![PathBuf::from("/home/a.txt")]);
+        assert_eq!(api.tabs.len(), 1);
+        assert!(api.tabs[0].current);
+        assert_eq!(api.tabs[0].path, Some(PathBuf::from("/home")));
+        assert!(api.junkyard.is_empty());
+    }
+
+    #[test]
+    fn invoke_clears_state_after_call() {
+        let lua = yeet_lua::init_from_script("").unwrap();
+        let app = App::default();
+        let mut state = State::default();
+
+        let (mode, actions) = invoke(&lua, &app, &mut state, || {
+            lua.load("y.api.print('done') return y.api.get_mode()")
+                .eval::<Option<String>>()
+                .unwrap()
+        });
+
+        assert_eq!(mode, Some(state.modes.current.to_string()));
+        assert_eq!(actions.len(), 1);
+
+        let mode: Option<String> = lua.load("return y.api.get_mode()").eval().unwrap();
+        assert_eq!(mode, None);
+        lua.load("y.api.print('ignored')").exec().unwrap();
+        assert!(yeet_lua::take_api_requests(&lua).is_empty());
+    }
+
+    fn to_labels(actions: Vec<Action>) -> Vec<String> {
+        actions
+            .into_iter()
+            .flat_map(|action| match action {
+                Action::EmitMessages(messages) => messages,
+                _ => panic!("expected emitted messages"),
+            })
+            .map(|message| match message {
+                Message::Keymap(KeymapMessage::ExecuteKeySequence(keys)) => keys,
+                Message::Keymap(KeymapMessage::Print(content)) => content[0].to_string(),
+                _ => panic!("unexpected message"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn invoke_keeps_requests_after_feedkeys_pending() {
+        let lua = yeet_lua::init_from_script("").unwrap();
+        let app = App::default();
+        let mut state = State::default();
+
+        let (_, actions) = invoke(&lua, &app, &mut state, || {
+            lua.load(
+                r#"
+                y.api.print("first")
+                y.api.feedkeys("gg")
+                y.api.print("second")
+                y.api.feedkeys("j")
+                y.api.print("third")
+                "#,
+            )
+            .exec()
+            .unwrap()
+        });
+
+        assert_eq!(to_labels(actions), vec!["first", "gg"]);
+        assert_eq!(
+            to_labels(take_pending_messages(&mut state.pending_messages)),
+            vec!["second", "j"]
+        );
+        assert_eq!(
+            to_labels(take_pending_messages(&mut state.pending_messages)),
+            vec!["third"]
+        );
+        assert!(take_pending_messages(&mut state.pending_messages).is_empty());
+    }
+
+    #[test]
+    fn invoke_runs_nested_requests_before_pending_ones() {
+        let lua = yeet_lua::init_from_script("").unwrap();
+        let app = App::default();
+        let mut state = State::default();
+
+        invoke(&lua, &app, &mut state, || {
+            lua.load("y.api.feedkeys('x') y.api.print('outer')")
+                .exec()
+                .unwrap()
+        });
+        let (_, actions) = invoke(&lua, &app, &mut state, || {
+            lua.load("y.api.print('inner')").exec().unwrap()
+        });
+
+        assert_eq!(to_labels(actions), vec!["inner", "outer"]);
+    }
+
+    #[test]
+    fn get_absolute_path_joins_relative_paths_with_current_path() {
+        let current = PathBuf::from("/home");
+
+        assert_eq!(
+            get_absolute_path(Some(&current), PathBuf::from("a/b.txt")),
+            PathBuf::from("/home/a/b.txt")
+        );
+        assert_eq!(
+            get_absolute_path(Some(&current), PathBuf::from("/tmp")),
+            PathBuf::from("/tmp")
+        );
+        assert_eq!(
+            get_absolute_path(None, PathBuf::from("a")),
+            PathBuf::from("a")
+        );
+    }
+}
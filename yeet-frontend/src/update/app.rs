@@ -39,7 +39,11 @@ pub fn get_focused_directory_viewports(
             SplitFocus::Second => get_focused_directory_viewports(second),
         },
         Window::Directory(parent, current, preview) => Some((parent, current, preview)),
-        Window::QuickFix(_) | Window::Tasks(_) | Window::Help(_) => None,
+        Window::QuickFix(_)
+        | Window::Tasks(_)
+        | Window::Diff(_)
+        | Window::Edit(_)
+        | Window::Help(_) => None,
     }
 }
 
@@ -61,7 +65,11 @@ pub fn get_focused_directory_viewports_mut(
             SplitFocus::Second => get_focused_directory_viewports_mut(second),
         },
         Window::Directory(parent, current, preview) => Some((parent, current, preview)),
-        Window::QuickFix(_) | Window::Tasks(_) | Window::Help(_) => None,
+        Window::QuickFix(_)
+        | Window::Tasks(_)
+        | Window::Diff(_)
+        | Window::Edit(_)
+        | Window::Help(_) => None,
     }
 }
 
@@ -90,7 +98,11 @@ pub fn get_viewport_by_buffer_id_mut(
                 None
             }
         }
-        Window::QuickFix(vp) | Window::Tasks(vp) | Window::Help(vp) => {
+        Window::QuickFix(vp)
+        | Window::Tasks(vp)
+        | Window::Diff(vp)
+        | Window::Edit(vp)
+        | Window::Help(vp) => {
             if vp.buffer_id == buffer_id {
                 Some(vp)
             } else {
@@ -155,7 +167,11 @@ pub fn resolve_buffer(
                 Buffer::Content(it) => Some(format!("{}:Content:{}", buf_id, it.path.display())),
                 Buffer::Image(it) => Some(format!("{}:Image:{}", buf_id, it.path.display())),
                 Buffer::PathReference(p) => Some(format!("{}:PathRef:{}", buf_id, p.display())),
-                Buffer::QuickFix(_) | Buffer::Tasks(_) | Buffer::Help(_) => None,
+                Buffer::QuickFix(_)
+                | Buffer::Tasks(_)
+                | Buffer::Diff(_)
+                | Buffer::Edit(_)
+                | Buffer::Help(_) => None,
                 Buffer::Empty => None,
             };
             path_str
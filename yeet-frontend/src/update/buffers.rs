@@ -16,7 +16,7 @@ pub fn update(app: &mut App) {
         .filter_map(|(id, buffer)| {
             if matches!(
                 buffer,
-                Buffer::Image(_) | Buffer::Tasks(_) | Buffer::QuickFix(_)
+                Buffer::Edit(_) | Buffer::Image(_) | Buffer::Tasks(_) | Buffer::QuickFix(_)
             ) && !referenced.contains(id)
             {
                 Some(*id)
@@ -65,6 +65,8 @@ mod test {
             Buffer::Image(PreviewImageBuffer {
                 path: PathBuf::from("/tmp/image.png"),
                 protocol: Protocol::Sixel(Sixel::default()),
+                mime: None,
+                image_metadata: None,
             }),
         );
 
@@ -85,6 +87,8 @@ mod test {
             Buffer::Image(PreviewImageBuffer {
                 path: PathBuf::from("/tmp/preview.png"),
                 protocol: Protocol::Sixel(Sixel::default()),
+                mime: None,
+                image_metadata: None,
             }),
         );
 
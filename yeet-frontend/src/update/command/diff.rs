@@ -0,0 +1,329 @@
+use std::mem;
+
+use yeet_buffer::model::{
+    ansi::Ansi, undo::consolidate_modifications, viewport::ViewPort, BufferLine, Mode, TextBuffer,
+};
+use yeet_lua::LuaConfiguration;
+
+use crate::{
+    action::Action,
+    event::{LogSeverity, Message},
+    model::{junkyard::JunkYard, App, Buffer, DiffBuffer, SplitFocus, Window},
+    theme::{tokens, Theme},
+    update::{app, hook, save, save::Operation},
+};
+
+pub fn open(app: &mut App, lua: Option<&LuaConfiguration>, theme: &Theme) -> Vec<Action> {
+    let (window, contents) = match app.current_window_and_contents_mut() {
+        Ok(window) => window,
+        Err(_) => return Vec::new(),
+    };
+
+    let target = match app::get_focused_directory_buffer_ids(window) {
+        Some((_, current_id, _)) => current_id,
+        None => return log(LogSeverity::Error, "wdiff failed: no directory focused"),
+    };
+
+    let operations = match contents.buffers.get(&target) {
+        Some(Buffer::Directory(dir)) => {
+            let changes = consolidate_modifications(&dir.buffer.uncommitted_changes());
+            save::get_operations(&dir.path, &changes)
+        }
+        _ => return log(LogSeverity::Error, "wdiff failed: no directory focused"),
+    };
+
+    if operations.is_empty() {
+        return log(LogSeverity::Information, "No pending changes to write");
+    }
+
+    let lines = operations
+        .iter()
+        .map(|operation| build_operation_line(operation, theme))
+        .collect();
+
+    let buffer_id = app::get_next_buffer_id(contents);
+    contents.buffers.insert(
+        buffer_id,
+        Buffer::Diff(DiffBuffer {
+            buffer: TextBuffer::from_lines(lines),
+            target,
+        }),
+    );
+
+    let mut diff_window = Window::Diff(ViewPort {
+        buffer_id,
+        show_border: false,
+        ..Default::default()
+    });
+
+    if let Some(lua) = lua {
+        hook::on_window_create(lua, &mut diff_window, None);
+    }
+
+    let old_window = mem::take(window);
+    *window = Window::Horizontal {
+        first: Box::new(old_window),
+        second: Box::new(diff_window),
+        focus: SplitFocus::Second,
+    };
+
+    Vec::new()
+}
+
+pub fn get_focused_target(app: &App) -> Option<usize> {
+    let window = app.current_window().ok()?;
+    match app
+        .contents
+        .buffers
+        .get(&window.focused_viewport().buffer_id)
+    {
+        Some(Buffer::Diff(diff)) => Some(diff.target),
+        _ => None,
+    }
+}
+
+pub fn confirm(app: &mut App, junk: &mut JunkYard, mode: &Mode, target: usize) -> Vec<Action> {
+    let (window, contents) = match app.current_window_and_contents_mut() {
+        Ok(window) => window,
+        Err(_) => return Vec::new(),
+    };
+
+    let old_window = mem::take(window);
+    *window = match old_window.close_focused() {
+        Ok((kept, _)) => kept,
+        Err(leaf) => leaf,
+    };
+
+    save::buffer(window, contents, junk, mode, target)
+}
+
+fn build_operation_line(operation: &Operation, theme: &Theme) -> BufferLine {
+    let (token, content) = match operation {
+        Operation::Add(path) => (tokens::DIFF_ADDED, format!("add    {}", path.display())),
+        Operation::Delete(path) => (tokens::DIFF_REMOVED, format!("delete {}", path.display())),
+        Operation::Rename(source, target) => (
+            tokens::DIFF_MODIFIED,
+            format!("rename {} -> {}", source.display(), target.display()),
+        ),
+        Operation::Trash(path) => (tokens::DIFF_REMOVED, format!("trash  {}", path.display())),
+    };
+
+    BufferLine {
+        content: Ansi::new(&format!("{}{}\x1b[39m", theme.ansi_fg(token), content)),
+        ..Default::default()
+    }
+}
+
+fn log(severity: LogSeverity, content: &str) -> Vec<Action> {
+    vec![Action::EmitMessages(vec![Message::Log(
+        severity,
+        content.to_string(),
+    )])]
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_buffer::{
+        message::{BufferMessage, LineDirection, TextModification},
+        model::{BufferLine, Mode, TextBuffer},
+    };
+
+    use crate::{
+        action::Action,
+        event::{LogSeverity, Message},
+        model::{junkyard::JunkYard, App, Buffer, DirectoryBuffer, Window},
+        task::Task,
+        theme::{tokens, Theme},
+    };
+
+    use super::{confirm, get_focused_target, open};
+
+    fn make_app() -> (App, usize) {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, current_id, _) =
+            crate::update::app::get_focused_directory_buffer_ids(window).unwrap();
+
+        let lines = ["a", "b", "c"].map(BufferLine::from).to_vec();
+        app.contents.buffers.insert(
+            current_id,
+            Buffer::Directory(DirectoryBuffer {
+                path: PathBuf::from("/diff"),
+                buffer: TextBuffer::from_lines(lines),
+                ..Default::default()
+            }),
+        );
+
+        (app, current_id)
+    }
+
+    fn modify(app: &mut App, cursor: usize, messages: &[BufferMessage]) {
+        let (window, contents) = app
+            .current_window_and_contents_mut()
+            .expect("test requires current tab");
+        let (viewport, buffer) =
+            crate::update::app::get_focused_current_mut(window, contents).unwrap();
+        let Buffer::Directory(directory) = buffer else {
+            panic!("expected directory buffer");
+        };
+
+        viewport.cursor.vertical_index = cursor;
+        yeet_buffer::update(
+            Some(viewport),
+            &Mode::Normal,
+            &mut directory.buffer,
+            messages,
+        );
+    }
+
+    fn make_dirty_app() -> (App, usize) {
+        let (mut app, current_id) = make_app();
+        modify(
+            &mut app,
+            1,
+            &[BufferMessage::Modification(1, TextModification::DeleteLine)],
+        );
+        modify(
+            &mut app,
+            1,
+            &[
+                BufferMessage::Modification(
+                    1,
+                    TextModification::InsertNewLine(LineDirection::Down),
+                ),
+                BufferMessage::Modification(1, TextModification::Insert("new".to_string())),
+            ],
+        );
+        (app, current_id)
+    }
+
+    fn make_dirty_app_with_rename() -> (App, usize) {
+        let (mut app, current_id) = make_dirty_app();
+        modify(
+            &mut app,
+            0,
+            &[BufferMessage::Modification(
+                1,
+                TextModification::Insert("z".to_string()),
+            )],
+        );
+        (app, current_id)
+    }
+
+    fn diff_lines(app: &App) -> Vec<String> {
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Diff(it)) => it
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn is_dirty(app: &App, buffer_id: usize) -> bool {
+        match app.contents.buffers.get(&buffer_id) {
+            Some(Buffer::Directory(it)) => it.buffer.has_unsaved_changes(),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn open_lists_planned_operations() {
+        let (mut app, current_id) = make_dirty_app_with_rename();
+
+        let actions = open(&mut app, None, &Theme::default());
+
+        assert!(actions.is_empty());
+        assert_eq!(get_focused_target(&app), Some(current_id));
+
+        let mut lines = diff_lines(&app);
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                "add    /diff/new",
+                "rename /diff/a -> /diff/az",
+                "trash  /diff/b",
+            ]
+        );
+        assert!(is_dirty(&app, current_id));
+    }
+
+    #[test]
+    fn open_colors_operations_with_diff_tokens() {
+        let (mut app, _) = make_dirty_app_with_rename();
+        let theme = Theme::default();
+
+        open(&mut app, None, &theme);
+
+        let window = app.current_window().expect("test requires current tab");
+        let Some(Buffer::Diff(diff)) = app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        else {
+            panic!("expected diff buffer");
+        };
+
+        let contents: Vec<_> = diff
+            .buffer
+            .lines
+            .iter()
+            .map(|line| line.content.to_string())
+            .collect();
+
+        let colored = |token: &str, prefix: &str| {
+            contents
+                .iter()
+                .any(|line| line.starts_with(&format!("{}{}", theme.ansi_fg(token), prefix)))
+        };
+
+        assert!(colored(tokens::DIFF_ADDED, "add"));
+        assert!(colored(tokens::DIFF_MODIFIED, "rename"));
+        assert!(colored(tokens::DIFF_REMOVED, "trash"));
+    }
+
+    #[test]
+    fn open_without_changes_logs_information() {
+        let (mut app, _) = make_app();
+
+        let actions = open(&mut app, None, &Theme::default());
+
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::EmitMessages(messages) if messages
+                .iter()
+                .any(|m| matches!(m, Message::Log(LogSeverity::Information, _)))
+        )));
+        assert_eq!(get_focused_target(&app), None);
+    }
+
+    #[test]
+    fn confirm_writes_changes_and_closes_window() {
+        let (mut app, current_id) = make_dirty_app();
+        open(&mut app, None, &Theme::default());
+
+        let mut junk = JunkYard::default();
+        let actions = confirm(&mut app, &mut junk, &Mode::Navigation, current_id);
+
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, Action::Task(Task::AddPath(path)) if path == &PathBuf::from("/diff/new"))));
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, Action::Task(Task::TrashPath(_)))));
+        assert!(!is_dirty(&app, current_id));
+
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Directory(_, _, _)));
+    }
+}
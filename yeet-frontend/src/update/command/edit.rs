@@ -0,0 +1,196 @@
+use std::{mem, path::Path};
+
+use yeet_buffer::model::{
+    viewport::{LineNumber, ViewPort},
+    BufferLine, TextBuffer,
+};
+use yeet_lua::LuaConfiguration;
+
+use crate::{
+    action::Action,
+    model::{App, Buffer, EditBuffer, SplitFocus, Window},
+    task::Task,
+    update::{app, hook},
+};
+
+pub fn open(app: &mut App, lua: Option<&LuaConfiguration>, path: &Path) -> Vec<Action> {
+    let existing = app
+        .contents
+        .buffers
+        .iter()
+        .find_map(|(id, buffer)| match buffer {
+            Buffer::Edit(it) if it.path == path => Some(*id),
+            _ => None,
+        });
+
+    match existing {
+        Some(buffer_id) => {
+            create_split(app, lua, buffer_id, path);
+            Vec::new()
+        }
+        None => vec![Action::Task(Task::LoadEditContent(path.to_path_buf()))],
+    }
+}
+
+pub fn set_content(app: &mut App, lua: Option<&LuaConfiguration>, path: &Path, content: &str) {
+    let mut lines: Vec<_> = content.lines().map(BufferLine::from).collect();
+    if lines.is_empty() {
+        lines.push(BufferLine::default());
+    }
+
+    let buffer_id = app::get_next_buffer_id(&mut app.contents);
+    app.contents.buffers.insert(
+        buffer_id,
+        Buffer::Edit(EditBuffer {
+            buffer: TextBuffer::from_lines(lines),
+            close_after_save: false,
+            crlf: content
+                .find('\n')
+                .is_some_and(|index| content[..index].ends_with('\r')),
+            path: path.to_path_buf(),
+            saving: None,
+            trailing_newline: content.ends_with('\n'),
+        }),
+    );
+
+    create_split(app, lua, buffer_id, path);
+}
+
+fn create_split(app: &mut App, lua: Option<&LuaConfiguration>, buffer_id: usize, path: &Path) {
+    let mut edit_window = Window::Edit(ViewPort {
+        buffer_id,
+        line_number: LineNumber::Relative,
+        line_number_width: 3,
+        ..Default::default()
+    });
+
+    if let Some(lua) = lua {
+        hook::on_window_create(lua, &mut edit_window, Some(path));
+    }
+
+    let window = match app.current_window_mut() {
+        Ok(window) => window,
+        Err(_) => return,
+    };
+
+    let focused_leaf = window.focused_window_mut();
+    let old_window = mem::take(focused_leaf);
+    *focused_leaf = Window::Horizontal {
+        first: Box::new(old_window),
+        second: Box::new(edit_window),
+        focus: SplitFocus::Second,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use crate::{
+        action::Action,
+        model::{App, Buffer, SplitFocus, Window},
+        task::Task,
+    };
+
+    use super::{open, set_content};
+
+    fn get_edit_lines(app: &App) -> Vec<String> {
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Edit(it)) => it
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect(),
+            _ => panic!("expected focused edit buffer"),
+        }
+    }
+
+    #[test]
+    fn open_loads_content_for_new_path() {
+        let mut app = App::default();
+
+        let actions = open(&mut app, None, Path::new("/tmp/config.toml"));
+
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::LoadEditContent(path))] if path == Path::new("/tmp/config.toml")
+        ));
+    }
+
+    #[test]
+    fn set_content_opens_focused_edit_split() {
+        let mut app = App::default();
+
+        set_content(&mut app, None, Path::new("/tmp/a.txt"), "first\nsecond\n");
+
+        let window = app.current_window().expect("test requires current tab");
+        match window {
+            Window::Horizontal {
+                first,
+                second,
+                focus,
+            } => {
+                assert!(matches!(first.as_ref(), Window::Directory(_, _, _)));
+                assert!(matches!(second.as_ref(), Window::Edit(_)));
+                assert_eq!(*focus, SplitFocus::Second);
+            }
+            _ => panic!("expected Horizontal"),
+        }
+        assert_eq!(get_edit_lines(&app), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn set_content_keeps_an_editable_line_for_empty_files() {
+        let mut app = App::default();
+
+        set_content(&mut app, None, Path::new("/tmp/empty"), "");
+
+        assert_eq!(get_edit_lines(&app), vec![""]);
+    }
+
+    #[test]
+    fn set_content_keeps_crlf_line_endings() {
+        let mut app = App::default();
+
+        set_content(
+            &mut app,
+            None,
+            Path::new("/tmp/a.txt"),
+            "first\r\nsecond\r\n",
+        );
+
+        assert_eq!(get_edit_lines(&app), vec!["first", "second"]);
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Edit(it)) => assert_eq!(it.to_file_content(), "first\r\nsecond\r\n"),
+            _ => panic!("expected focused edit buffer"),
+        }
+    }
+
+    #[test]
+    fn open_reuses_existing_edit_buffer() {
+        let mut app = App::default();
+        set_content(&mut app, None, Path::new("/tmp/a.txt"), "content");
+        let buffer_id = app
+            .current_window()
+            .expect("test requires current tab")
+            .focused_viewport()
+            .buffer_id;
+
+        let actions = open(&mut app, None, Path::new("/tmp/a.txt"));
+
+        assert!(actions.is_empty());
+        let window = app.current_window().expect("test requires current tab");
+        assert_eq!(window.focused_viewport().buffer_id, buffer_id);
+    }
+}
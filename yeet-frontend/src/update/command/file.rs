@@ -1,50 +1,86 @@
 use std::path::{Path, PathBuf};
 
+use yeet_buffer::model::Mode;
 use yeet_keymap::message::KeymapMessage;
 
 use crate::{
     action::{self, Action},
     error::AppError,
     event::{LogSeverity, Message},
-    model::{mark::Marks, App},
-    task::Task,
-    update::app,
+    model::{
+        conflict::{Transfer, TransferSource},
+        mark::Marks,
+        App, State,
+    },
+    settings::ConflictPolicy,
+    update::{app, conflict, journal},
 };
 
-pub fn copy_path(marks: &Marks, source_path: &Path, target: &str) -> Vec<Action> {
+pub fn copy_path(marks: &Marks, source_path: &Path, target: &str) -> Result<Transfer, String> {
     tracing::info!("copying path: {:?}", source_path);
-    match expand_and_validate_path(marks, target, source_path) {
-        Ok(target_path) => {
-            vec![Action::Task(Task::CopyPath(
-                source_path.to_path_buf(),
-                target_path,
-            ))]
-        }
-        Err(err) => {
-            vec![Action::EmitMessages(vec![Message::Log(
-                LogSeverity::Error,
-                err,
-            )])]
-        }
+    Ok(Transfer {
+        source: TransferSource::Copy(source_path.to_path_buf()),
+        target: expand_and_validate_path(marks, target, source_path)?,
+    })
+}
+
+pub fn link_path(marks: &Marks, source_path: &Path, target: &str) -> Result<Transfer, String> {
+    tracing::info!("hard linking path: {:?}", source_path);
+    Ok(Transfer {
+        source: TransferSource::Link(source_path.to_path_buf()),
+        target: expand_and_validate_path(marks, target, source_path)?,
+    })
+}
+
+pub fn symlink_path(marks: &Marks, source_path: &Path, target: &str) -> Result<Transfer, String> {
+    tracing::info!("symlinking path: {:?}", source_path);
+    // NOTE: links may be created inside of their source, but never replace it
+    let target_file = expand_target_file(marks, target, source_path)?;
+    if resolve_parent(&target_file) == resolve_parent(source_path) {
+        return Err(format!("target {:?} is the source", target_file.display()));
     }
+
+    Ok(Transfer {
+        source: TransferSource::Symlink(source_path.to_path_buf()),
+        target: target_file,
+    })
 }
 
-pub fn rename_path(marks: &Marks, source_path: &Path, target: &str) -> Vec<Action> {
+pub fn rename_path(marks: &Marks, source_path: &Path, target: &str) -> Result<Transfer, String> {
     tracing::info!("renaming path: {:?}", source_path);
-    match expand_and_validate_path(marks, target, source_path) {
-        Ok(target_path) => {
-            vec![Action::Task(Task::RenamePath(
-                source_path.to_path_buf(),
-                target_path,
-            ))]
-        }
-        Err(err) => {
-            vec![Action::EmitMessages(vec![Message::Log(
-                LogSeverity::Error,
-                err,
-            )])]
+    Ok(Transfer {
+        source: TransferSource::Move(source_path.to_path_buf()),
+        target: expand_and_validate_path(marks, target, source_path)?,
+    })
+}
+
+pub fn transfer(
+    state: &mut State,
+    policy: &ConflictPolicy,
+    from: &Mode,
+    transfers: Vec<Result<Transfer, String>>,
+) -> Vec<Action> {
+    let mut valid = Vec::new();
+    let mut errors = Vec::new();
+    for transfer in transfers {
+        match transfer {
+            Ok(it) => valid.push(it),
+            Err(err) => errors.push(Message::Log(LogSeverity::Error, err)),
         }
     }
+
+    let mut actions = Vec::new();
+    if !errors.is_empty() {
+        actions.push(Action::EmitMessages(errors));
+    }
+    let transfers = conflict::transfer(&mut state.conflicts, &mut state.junk, policy, from, valid);
+    if !state.conflicts.pending.is_empty() {
+        journal::hold(&mut state.journal);
+    }
+    journal::record(&mut state.journal, &transfers);
+    actions.extend(transfers);
+
+    actions
 }
 
 fn expand_and_validate_path(
@@ -52,6 +88,18 @@ fn expand_and_validate_path(
     target: &str,
     source_path: &Path,
 ) -> Result<PathBuf, String> {
+    let target_file = expand_target_file(marks, target, source_path)?;
+    if resolve_parent(&target_file).starts_with(resolve_parent(source_path)) {
+        return Err(format!(
+            "target {:?} is the source or inside of it",
+            target_file.display()
+        ));
+    }
+
+    Ok(target_file)
+}
+
+fn expand_target_file(marks: &Marks, target: &str, source_path: &Path) -> Result<PathBuf, String> {
     let file_name = match source_path.file_name() {
         Some(it) => it,
         None => {
@@ -74,13 +122,6 @@ fn expand_and_validate_path(
         ));
     }
 
-    if target_file.exists() {
-        return Err(format!(
-            "target file path {:?} exists already",
-            target_file.display()
-        ));
-    }
-
     let parent_dir = match target_file.parent() {
         Some(it) => it,
         None => {
@@ -101,6 +142,15 @@ fn expand_and_validate_path(
     Ok(target_file)
 }
 
+fn resolve_parent(path: &Path) -> PathBuf {
+    let resolved = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => parent.canonicalize().map(|parent| parent.join(name)),
+        _ => path.canonicalize(),
+    };
+
+    resolved.unwrap_or_else(|_| path.components().collect())
+}
+
 pub fn refresh(app: &mut App) -> Result<Vec<Action>, AppError> {
     let (window, contents) = app.current_window_and_contents_mut()?;
     let (_, buffer) = app::get_focused_current_mut(window, contents)?;
@@ -209,14 +259,21 @@ mod test {
         time::{SystemTime, UNIX_EPOCH},
     };
 
+    use yeet_buffer::model::Mode;
+
     use crate::{
         action::Action,
         event::{LogSeverity, Message},
-        model::mark::Marks,
-        task::Task,
+        model::{
+            conflict::{Transfer, TransferSource},
+            mark::Marks,
+            State,
+        },
+        settings::ConflictPolicy,
+        task::{Existing, Task},
     };
 
-    use super::{copy_path, expand_path, rename_path};
+    use super::{copy_path, expand_path, link_path, rename_path, symlink_path, transfer};
 
     fn unique_temp_dir() -> PathBuf {
         let mut dir = std::env::temp_dir();
@@ -306,41 +363,103 @@ mod test {
     }
 
     #[test]
-    fn copy_path_returns_copy_task_on_success() {
+    fn copy_path_returns_copy_transfer_on_success() {
         let marks = Marks::default();
-        let target_dir = unique_temp_dir();
-        let source = target_dir.join("source.txt");
+        let source_dir = unique_temp_dir();
+        let target_dir = source_dir.join("target");
+        fs::create_dir_all(&target_dir).expect("create target dir");
+        let source = source_dir.join("source.txt");
         let target = target_dir.to_string_lossy();
 
-        let actions = copy_path(&marks, &source, target.as_ref());
+        let transfer = copy_path(&marks, &source, target.as_ref()).expect("transfer");
 
-        assert_eq!(actions.len(), 1);
-        assert!(matches!(
-            &actions[0],
-            Action::Task(Task::CopyPath(src, dst))
-                if src == &source && dst == &target_dir.join("source.txt")
-        ));
+        assert_eq!(transfer.source, TransferSource::Copy(source.clone()));
+        assert_eq!(transfer.target, target_dir.join("source.txt"));
 
-        let _ = fs::remove_dir_all(&target_dir);
+        let _ = fs::remove_dir_all(&source_dir);
     }
 
     #[test]
-    fn rename_path_returns_rename_task_on_success() {
+    fn rename_path_returns_move_transfer_on_success() {
         let marks = Marks::default();
-        let target_dir = unique_temp_dir();
-        let source = target_dir.join("source.txt");
+        let source_dir = unique_temp_dir();
+        let target_dir = source_dir.join("target");
+        fs::create_dir_all(&target_dir).expect("create target dir");
+        let source = source_dir.join("source.txt");
         let target = target_dir.to_string_lossy();
 
-        let actions = rename_path(&marks, &source, target.as_ref());
+        let transfer = rename_path(&marks, &source, target.as_ref()).expect("transfer");
 
-        assert_eq!(actions.len(), 1);
-        assert!(matches!(
-            &actions[0],
-            Action::Task(Task::RenamePath(src, dst))
-                if src == &source && dst == &target_dir.join("source.txt")
-        ));
+        assert_eq!(transfer.source, TransferSource::Move(source.clone()));
+        assert_eq!(transfer.target, target_dir.join("source.txt"));
 
-        let _ = fs::remove_dir_all(&target_dir);
+        let _ = fs::remove_dir_all(&source_dir);
+    }
+
+    #[test]
+    fn link_paths_return_link_transfers_on_success() {
+        let marks = Marks::default();
+        let source_dir = unique_temp_dir();
+        let target_dir = source_dir.join("target");
+        fs::create_dir_all(&target_dir).expect("create target dir");
+        let source = source_dir.join("source.txt");
+        let target = target_dir.to_string_lossy();
+
+        let hard = link_path(&marks, &source, target.as_ref()).expect("transfer");
+        let soft = symlink_path(&marks, &source, target.as_ref()).expect("transfer");
+
+        assert_eq!(hard.source, TransferSource::Link(source.clone()));
+        assert_eq!(soft.source, TransferSource::Symlink(source.clone()));
+        assert_eq!(hard.target, target_dir.join("source.txt"));
+        assert_eq!(soft.target, target_dir.join("source.txt"));
+
+        let _ = fs::remove_dir_all(&source_dir);
+    }
+
+    #[test]
+    fn copy_path_into_own_directory_returns_error() {
+        let marks = Marks::default();
+        let dir = unique_temp_dir();
+        let source = dir.join("source.txt");
+        fs::write(&source, "content").expect("write source");
+
+        let error = copy_path(&marks, &source, ".").expect_err("same path");
+        assert!(error.contains("is the source"));
+
+        let mut state = State::default();
+        let actions = transfer(
+            &mut state,
+            &ConflictPolicy::Overwrite,
+            &Mode::Navigation,
+            vec![copy_path(&marks, &source, ".")],
+        );
+
+        assert!(actions
+            .iter()
+            .all(|action| !matches!(action, Action::Task(_))));
+        assert_eq!(fs::read_to_string(&source).expect("read source"), "content");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn transfers_into_source_directory_return_error() {
+        let marks = Marks::default();
+        let dir = unique_temp_dir();
+        let source = dir.join("source");
+        fs::create_dir_all(source.join("child")).expect("create source");
+
+        for target in [".", "child", "./child/../child"] {
+            assert!(copy_path(&marks, &source, target).is_err());
+            assert!(rename_path(&marks, &source, target).is_err());
+            assert!(link_path(&marks, &source, target).is_err());
+        }
+
+        assert!(symlink_path(&marks, &source, "..").is_err());
+        let transfer = symlink_path(&marks, &source, "child").expect("link inside source");
+        assert_eq!(transfer.target, source.join("child/source"));
+
+        let _ = fs::remove_dir_all(&dir);
     }
 
     #[test]
@@ -350,15 +469,10 @@ mod test {
         missing_dir.push("yeet_missing_target_dir");
         let source = PathBuf::from("/home/user/file.txt");
 
-        let actions = copy_path(&marks, &source, missing_dir.to_string_lossy().as_ref());
+        let error = copy_path(&marks, &source, missing_dir.to_string_lossy().as_ref())
+            .expect_err("missing target dir");
 
-        assert_eq!(actions.len(), 1);
-        assert!(
-            matches!(&actions[0], Action::EmitMessages(messages) if matches!(
-                messages.as_slice(),
-                [Message::Log(LogSeverity::Error,message)] if message.contains("target directory")
-            ))
-        );
+        assert!(error.contains("target directory"));
     }
 
     #[test]
@@ -368,14 +482,39 @@ mod test {
         missing_dir.push("yeet_missing_target_dir_rename");
         let source = PathBuf::from("/home/user/file.txt");
 
-        let actions = rename_path(&marks, &source, missing_dir.to_string_lossy().as_ref());
+        let error = rename_path(&marks, &source, missing_dir.to_string_lossy().as_ref())
+            .expect_err("missing target dir");
 
-        assert_eq!(actions.len(), 1);
-        assert!(
-            matches!(&actions[0], Action::EmitMessages(messages) if matches!(
-                messages.as_slice(),
-                [Message::Log(LogSeverity::Error,message)] if message.contains("target directory")
-            ))
+        assert!(error.contains("target directory"));
+    }
+
+    #[test]
+    fn transfer_logs_errors_and_plans_valid_transfers() {
+        let mut state = State::default();
+        let target_dir = unique_temp_dir();
+        let target = target_dir.join("source.txt");
+
+        let actions = transfer(
+            &mut state,
+            &ConflictPolicy::Prompt,
+            &Mode::Navigation,
+            vec![
+                Err("target directory missing".to_string()),
+                Ok(Transfer {
+                    source: TransferSource::Copy(PathBuf::from("/source.txt")),
+                    target: target.clone(),
+                }),
+            ],
         );
+
+        assert!(matches!(
+            actions.as_slice(),
+            [
+                Action::EmitMessages(messages),
+                Action::Task(Task::CopyPath(_, dst, Existing::Fail)),
+            ] if matches!(messages.as_slice(), [Message::Log(LogSeverity::Error, _)]) && dst == &target
+        ));
+
+        let _ = fs::remove_dir_all(&target_dir);
     }
 }
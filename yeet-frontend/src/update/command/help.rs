@@ -13,6 +13,7 @@ use crate::{
 };
 
 const INDEX_CONTENT: &str = include_str!("../../../../docs/help/index.md");
+const API_CONTENT: &str = include_str!("../../../../docs/help/api.md");
 const COMMANDS_CONTENT: &str = include_str!("../../../../docs/help/commands.md");
 const CONFIGURATION_CONTENT: &str = include_str!("../../../../docs/help/configuration.md");
 const HOOKS_CONTENT: &str = include_str!("../../../../docs/help/hooks.md");
@@ -31,6 +32,10 @@ const HELP_PAGES: &[HelpPage] = &[
         name: "index",
         content: INDEX_CONTENT,
     },
+    HelpPage {
+        name: "api",
+        content: API_CONTENT,
+    },
     HelpPage {
         name: "commands",
         content: COMMANDS_CONTENT,
@@ -221,8 +226,38 @@ fn discover_plugin_help_pages_from_paths(
     pages
 }
 
+fn get_user_command_page(lua: &LuaConfiguration) -> Option<PluginHelpPage> {
+    let commands = yeet_lua::read_commands(lua);
+    if commands.is_empty() {
+        return None;
+    }
+
+    let mut content = String::from(
+        "# User Commands\n\nCommands registered with `y.command.register` in `init.lua` or plugins.\n",
+    );
+    for command in commands {
+        content.push_str(&format!("\n### `{}`\n\n", command.name));
+        content.push_str(
+            command
+                .description
+                .as_deref()
+                .unwrap_or("No description available."),
+        );
+        content.push('\n');
+        if command.bang {
+            content.push_str(&format!("\nAlso available as `:{}!`.\n", command.name));
+        }
+    }
+
+    Some(PluginHelpPage {
+        name: "usercommands".to_string(),
+        content,
+    })
+}
+
 pub fn open(app: &mut App, lua: Option<&LuaConfiguration>, topic: Option<&str>) -> Vec<Action> {
-    let plugin_pages = lua.map(discover_plugin_help_pages).unwrap_or_default();
+    let mut plugin_pages = lua.map(discover_plugin_help_pages).unwrap_or_default();
+    plugin_pages.extend(lua.and_then(get_user_command_page));
 
     let topic_match = match topic {
         Some(t) => match resolve_topic(t, &plugin_pages) {
@@ -252,6 +287,7 @@ pub fn open(app: &mut App, lua: Option<&LuaConfiguration>, topic: Option<&str>)
                     &mut line,
                     yeet_lua::BufferType::Help,
                     None,
+                    None,
                 );
             }
             line
@@ -327,6 +363,7 @@ pub fn apply_highlighted(
                         &mut line,
                         yeet_lua::BufferType::Help,
                         None,
+                        None,
                     );
                 }
                 line
@@ -652,4 +689,40 @@ mod test {
         let pages = discover_plugin_help_pages_from_paths(&[dir.path().to_path_buf()]);
         assert!(pages.is_empty());
     }
+
+    #[test]
+    fn documented_commands_are_reserved_for_user_commands() {
+        for line in COMMANDS_CONTENT.lines() {
+            let Some(heading) = line.strip_prefix("### `") else {
+                continue;
+            };
+
+            let name = heading
+                .split(['`', ' ', '!'])
+                .next()
+                .expect("command heading has a name");
+            assert!(
+                yeet_lua::BUILTIN_COMMANDS.contains(&name),
+                "{} is missing from the built-in commands",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn user_command_page_lists_registered_commands() {
+        let lua = yeet_lua::init_from_script(
+            r#"
+            y.command.register("deploy-artifacts", function() end, { bang = true, desc = "Upload build artifacts." })
+            "#,
+        )
+        .unwrap();
+
+        let pages: Vec<_> = get_user_command_page(&lua).into_iter().collect();
+        let m = resolve_plugin_topic("deploy-artifacts", &pages).expect("command topic");
+        assert!(m.line_offset() > 0);
+        assert!(m.content().contains("Upload build artifacts."));
+        assert!(m.content().contains("`:deploy-artifacts!`"));
+        assert!(get_user_command_page(&yeet_lua::Lua::new()).is_none());
+    }
 }
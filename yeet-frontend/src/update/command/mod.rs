@@ -7,14 +7,17 @@ use yeet_lua::LuaConfiguration;
 use crate::{
     action::{self, Action},
     event::{LogSeverity, Message},
-    model::{App, Buffer, Contents, State, Window},
+    model::{conflict::Transfer, mark::Marks, App, Buffer, Contents, State, Window},
     settings::Settings,
     task::Task,
-    update::{app, tab},
+    update::{api, app, journal, save, tab, visual},
 };
 
-mod file;
+mod diff;
+pub mod edit;
+pub mod file;
 pub mod help;
+mod permission;
 mod print;
 pub mod qfix;
 mod settings;
@@ -51,6 +54,8 @@ pub fn execute(
             mode,
             qfix::commands::select_first(&mut state.qfix),
         ),
+        ("chmod", args) => permission::chmod(app, args, mode_before, mode),
+        ("chown", args) => permission::chown(app, args, mode_before, mode),
         ("cl", "") => print::qfix(&state.qfix),
         ("clearcl", "") => add_change_mode(
             mode_before,
@@ -75,15 +80,7 @@ pub fn execute(
         ),
         ("cN", "") => add_change_mode(mode_before, mode, qfix::commands::previous(&mut state.qfix)),
         ("cp", target) => {
-            let path = get_preview_path(app);
-            let actions = match path {
-                Some(source_path) => file::copy_path(&state.marks, source_path, target),
-                None => {
-                    tracing::warn!("cp command failed: no path in preview buffer");
-                    Vec::new()
-                }
-            };
-
+            let actions = transfer_selection(app, state, settings, &mode, target, file::copy_path);
             add_change_mode(mode_before, mode, actions)
         }
         ("d!", "") => {
@@ -129,6 +126,29 @@ pub fn execute(
 
             add_change_mode(mode_before, mode, actions)
         }
+        ("e", args) => {
+            let expand_result = match (args.trim(), get_current_path(app)) {
+                ("", _) => get_preview_path(app)
+                    .map(Path::to_path_buf)
+                    .ok_or_else(|| "Edit failed. No entry selected.".to_string()),
+                (args, Some(path)) => file::expand_path(&state.marks, args, path),
+                (args, None) => file::expand_path_without_source(&state.marks, args),
+            };
+            let actions = match expand_result {
+                Ok(target_path) if target_path.is_file() => {
+                    edit::open(app, lua, target_path.as_path())
+                }
+                Ok(target_path) => vec![Action::EmitMessages(vec![Message::Log(
+                    LogSeverity::Error,
+                    format!("Edit failed. Path {:?} is not a file.", target_path),
+                )])],
+                Err(err) => vec![Action::EmitMessages(vec![Message::Log(
+                    LogSeverity::Error,
+                    err,
+                )])],
+            };
+            add_change_mode(mode_before, Mode::Navigation, actions)
+        }
         ("e!", "") => {
             let actions = match file::refresh(app) {
                 Ok(actions) => actions,
@@ -154,12 +174,23 @@ pub fn execute(
 
             add_change_mode(mode_before, mode, actions)
         }
+        ("fsredo", "") => add_change_mode(
+            mode_before,
+            mode,
+            journal::redo(&mut state.journal, &mut state.junk),
+        ),
+        ("fsundo", "") => add_change_mode(
+            mode_before,
+            mode,
+            journal::undo(&mut state.journal, &mut state.junk),
+        ),
         ("help", "") => add_change_mode(mode_before, Mode::Navigation, help::open(app, lua, None)),
         ("help", topic) => add_change_mode(
             mode_before,
             Mode::Navigation,
             help::open(app, lua, Some(topic.trim())),
         ),
+        ("info", "") => print::info(app),
         ("invertcl", "") => {
             let actions =
                 match qfix::commands::invert_in_current(app, &mut state.qfix, &settings.theme) {
@@ -178,6 +209,15 @@ pub fn execute(
             add_change_mode(mode_before, mode, actions)
         }
         ("junk", "") => print::junkyard(&state.junk),
+        (command @ ("ln" | "ln!"), target) => {
+            let link = if command == "ln" {
+                file::symlink_path
+            } else {
+                file::link_path
+            };
+            let actions = transfer_selection(app, state, settings, &mode, target, link);
+            add_change_mode(mode_before, mode, actions)
+        }
         ("marks", "") => print::marks(&state.marks),
         ("pluginlist", _) => print::plugin_list(&state.plugin_states),
         ("pluginsync", "") => {
@@ -209,15 +249,8 @@ pub fn execute(
             )
         }
         ("mv", target) => {
-            let preview_path = get_preview_path(app);
-            let actions = match preview_path {
-                Some(source_path) => file::rename_path(&state.marks, source_path, target),
-                None => vec![Action::EmitMessages(vec![Message::Log(
-                    LogSeverity::Error,
-                    "Mv failed. Preview path could not be resolved.".to_string(),
-                )])],
-            };
-
+            let actions =
+                transfer_selection(app, state, settings, &mode, target, file::rename_path);
             add_change_mode(mode_before, mode, actions)
         }
         ("noh", "") => add_change_mode(
@@ -239,9 +272,15 @@ pub fn execute(
                     mode,
                 );
             }
-            close_focused_window_or_quit(app, QuitMode::FailOnRunningTasks, mode_before, false)
+            close_focused_window_or_quit(
+                app,
+                QuitMode::FailOnRunningTasks,
+                mode_before,
+                mode,
+                false,
+            )
         }
-        ("q!", "") => close_focused_window_or_quit(app, QuitMode::Force, mode_before, true),
+        ("q!", "") => close_focused_window_or_quit(app, QuitMode::Force, mode_before, mode, true),
         ("qa", "") => {
             if buffer_has_unsaved_changes(&app.contents, None) {
                 return print_error(
@@ -420,15 +459,36 @@ pub fn execute(
             };
             add_change_mode(mode_before, Mode::Navigation, actions)
         }
-        ("w", "") => add_change_mode(
-            mode_before,
-            mode,
-            vec![Action::EmitMessages(vec![Message::Keymap(
-                KeymapMessage::Buffer(BufferMessage::SaveBuffer),
-            )])],
-        ),
+        ("w", "") => match diff::get_focused_target(app) {
+            Some(target) => {
+                let actions = diff::confirm(app, &mut state.junk, &Mode::Navigation, target);
+                journal::record(&mut state.journal, &actions);
+
+                add_change_mode(mode_before, Mode::Navigation, actions)
+            }
+            None => add_change_mode(
+                mode_before,
+                mode,
+                vec![Action::EmitMessages(vec![Message::Keymap(
+                    KeymapMessage::Buffer(BufferMessage::SaveBuffer),
+                )])],
+            ),
+        },
+        ("wdiff", "") => add_change_mode(mode_before, mode, diff::open(app, lua, &settings.theme)),
         ("wq", "") => {
-            close_focused_window_or_quit(app, QuitMode::FailOnRunningTasks, mode_before, false)
+            if focused_buffer_is_edit(app) {
+                let actions =
+                    save::current_and_close(app, &mut state.junk, &mode_before).unwrap_or_default();
+                add_change_mode(mode_before, mode, actions)
+            } else {
+                close_focused_window_or_quit(
+                    app,
+                    QuitMode::FailOnRunningTasks,
+                    mode_before,
+                    mode,
+                    false,
+                )
+            }
         }
         ("z", params) => add_change_mode(
             mode_before,
@@ -437,12 +497,27 @@ pub fn execute(
         ),
         (cmd, args) => {
             let mut actions = Vec::new();
-            if !args.is_empty() {
-                let err = format!("command '{} {}' is not valid", cmd, args);
-                actions.push(Action::EmitMessages(vec![Message::Log(
-                    LogSeverity::Error,
-                    err,
-                )]));
+            match lua.filter(|lua| yeet_lua::get_command(lua, cmd).is_some()) {
+                Some(lua) => {
+                    let (result, api_actions) =
+                        api::invoke(lua, app, state, || yeet_lua::invoke_command(lua, cmd, args));
+                    actions.extend(api_actions);
+                    if let Err(err) = result {
+                        let err = format!("command '{}' failed: {}", cmd, err);
+                        actions.push(Action::EmitMessages(vec![Message::Log(
+                            LogSeverity::Error,
+                            err,
+                        )]));
+                    }
+                }
+                None if !args.is_empty() => {
+                    let err = format!("command '{} {}' is not valid", cmd, args);
+                    actions.push(Action::EmitMessages(vec![Message::Log(
+                        LogSeverity::Error,
+                        err,
+                    )]));
+                }
+                None => {}
             }
             add_change_mode(mode_before, mode, actions)
         }
@@ -453,12 +528,45 @@ pub fn execute(
     result
 }
 
+fn focused_buffer_is_edit(app: &App) -> bool {
+    app.current_window().is_ok_and(|window| {
+        matches!(
+            app.contents
+                .buffers
+                .get(&window.focused_viewport().buffer_id),
+            Some(Buffer::Edit(_))
+        )
+    })
+}
+
 fn get_current_path(app: &App) -> Option<&Path> {
     let window = app.current_window().ok()?;
     let (_, current_id, _) = app::get_focused_directory_buffer_ids(window)?;
     app::get_buffer_path(app, current_id).ok()?
 }
 
+fn transfer_selection(
+    app: &App,
+    state: &mut State,
+    settings: &Settings,
+    mode: &Mode,
+    target: &str,
+    to_transfer: fn(&Marks, &Path, &str) -> Result<Transfer, String>,
+) -> Vec<Action> {
+    let transfers = match visual::get_selected_paths(app) {
+        Some(paths) => paths
+            .iter()
+            .map(|path| to_transfer(&state.marks, path, target))
+            .collect(),
+        None => match get_preview_path(app) {
+            Some(path) => vec![to_transfer(&state.marks, path, target)],
+            None => vec![Err("Preview path could not be resolved.".to_string())],
+        },
+    };
+
+    file::transfer(state, &settings.conflict_policy, mode, transfers)
+}
+
 fn get_preview_path(app: &App) -> Option<&Path> {
     let window = app.current_window().ok()?;
     let (_, _, preview_id) = app::get_focused_directory_buffer_ids(window)?;
@@ -495,10 +603,10 @@ fn buffer_has_unsaved_changes(contents: &Contents, buffer_id: Option<usize>) ->
             }
         }
 
-        if let Buffer::Directory(dir) = buf {
-            dir.buffer.has_unsaved_changes()
-        } else {
-            false
+        match buf {
+            Buffer::Directory(dir) => dir.buffer.has_unsaved_changes(),
+            Buffer::Edit(edit) => edit.buffer.has_unsaved_changes(),
+            _ => false,
         }
     })
 }
@@ -508,8 +616,10 @@ fn reset_unsaved_changes_for_buffer_ids(
     contents: &mut Contents,
 ) {
     for buffer_id in buffer_ids {
-        if let Some(Buffer::Directory(dir)) = contents.buffers.get_mut(&buffer_id) {
-            dir.buffer.revert_unsaved_changes();
+        match contents.buffers.get_mut(&buffer_id) {
+            Some(Buffer::Directory(dir)) => dir.buffer.revert_unsaved_changes(),
+            Some(Buffer::Edit(edit)) => edit.buffer.revert_unsaved_changes(),
+            _ => {}
         }
     }
 }
@@ -540,8 +650,16 @@ fn close_focused_window_or_quit(
     app: &mut App,
     quit_mode: QuitMode,
     mode_before: Mode,
+    mode: Mode,
     discard_changes: bool,
 ) -> Vec<Action> {
+    // NOTE: changing to navigation saves all directories, aborting a diff must keep the changes
+    let mode_after = if diff::get_focused_target(app).is_some() {
+        mode
+    } else {
+        Mode::Navigation
+    };
+
     let (window, contents) = match app.current_window_and_contents_mut() {
         Ok(window) => window,
         Err(_) => return Vec::new(),
@@ -554,7 +672,7 @@ fn close_focused_window_or_quit(
             }
 
             *window = kept;
-            add_change_mode(mode_before, Mode::Navigation, Vec::new())
+            add_change_mode(mode_before, mode_after, Vec::new())
         }
         Err(leaf) => {
             *window = leaf;
@@ -579,7 +697,7 @@ fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
         match mode {
             Mode::Command(_) => Mode::default(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::Visual => Mode::Navigation,
         }
     } else {
         Mode::default()
@@ -588,6 +706,8 @@ fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
 
 #[cfg(test)]
 mod test {
+    use std::path::Path;
+
     use yeet_buffer::message::{BufferMessage, LineDirection, TextModification};
     use yeet_buffer::model::{viewport::ViewPort, BufferLine, CommandMode, Mode};
     use yeet_keymap::message::{KeymapMessage, QuitMode};
@@ -598,9 +718,9 @@ mod test {
         model::{App, Buffer, Contents, DirectoryBuffer, SplitFocus, State, TasksBuffer, Window},
     };
 
-    use crate::settings::Settings;
+    use crate::{settings::Settings, task::Task};
 
-    use super::execute;
+    use super::{edit, execute};
 
     fn make_state_with_command_mode() -> State {
         let mut state = State::default();
@@ -769,6 +889,18 @@ mod test {
         yeet_buffer::update(Some(&mut viewport), &Mode::Normal, buffer, &messages);
     }
 
+    fn make_app_with_dirty_edit_split() -> App {
+        let mut app = App::default();
+        edit::set_content(&mut app, None, Path::new("/tmp/edit.txt"), "first\n");
+
+        let window = app.current_window().expect("test requires current tab");
+        let buffer_id = window.focused_viewport().buffer_id;
+        if let Some(Buffer::Edit(it)) = app.contents.buffers.get_mut(&buffer_id) {
+            apply_modifications(&mut it.buffer, buffer_id);
+        }
+        app
+    }
+
     fn mark_directory_buffer_dirty(buffer: &mut DirectoryBuffer, buffer_id: usize) {
         apply_modifications(&mut buffer.buffer, buffer_id);
     }
@@ -809,6 +941,24 @@ mod test {
         assert_eq!(result, Mode::Navigation);
     }
 
+    #[test]
+    fn transfer_commands_without_preview_path_log_error() {
+        for command in ["cp /tmp", "mv /tmp", "ln /tmp", "ln! /tmp"] {
+            let mut app = App::default();
+            let mut state = make_state_with_command_mode();
+            let settings = Settings::default();
+
+            let actions = execute(&mut app, &mut state, &settings, None, command);
+
+            assert!(
+                contains_command_error(&actions, "Preview path could not be resolved"),
+                "{}",
+                command
+            );
+            assert!(!actions.iter().any(|a| matches!(a, Action::Task(_))));
+        }
+    }
+
     #[test]
     fn q_on_horizontal_closes_focused_and_collapses_to_directory() {
         let mut app = make_app_with_horizontal_split();
@@ -825,6 +975,88 @@ mod test {
         ));
     }
 
+    fn make_app_with_dirty_directory() -> (App, usize) {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, current_id, _) =
+            crate::update::app::get_focused_directory_buffer_ids(window).unwrap();
+
+        let mut dir_buffer = DirectoryBuffer {
+            path: std::path::PathBuf::from("/wdiff"),
+            buffer: yeet_buffer::model::TextBuffer::from_lines(vec![BufferLine::from("a")]),
+            ..Default::default()
+        };
+        add_line_to_directory_buffer(&mut dir_buffer, current_id, "new");
+        app.contents
+            .buffers
+            .insert(current_id, Buffer::Directory(dir_buffer));
+
+        (app, current_id)
+    }
+
+    fn execute_from_normal(app: &mut App, state: &mut State, cmd: &str) -> Vec<Action> {
+        state.modes.current = Mode::Command(CommandMode::Command);
+        state.modes.previous = Some(Mode::Normal);
+
+        let mut actions = execute(app, state, &Settings::default(), None, cmd);
+        let changes: Vec<_> = actions
+            .iter()
+            .flat_map(|action| match action {
+                Action::EmitMessages(msgs) => msgs.as_slice(),
+                _ => &[],
+            })
+            .filter_map(|msg| match msg {
+                Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(from, to))) => {
+                    Some((from.clone(), to.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (from, to) in changes {
+            actions.extend(
+                crate::update::mode::change(
+                    app,
+                    state,
+                    &from,
+                    &to,
+                    &crate::theme::Theme::default(),
+                    None,
+                )
+                .unwrap(),
+            );
+        }
+        actions
+    }
+
+    #[test]
+    fn wdiff_and_q_from_normal_keep_changes_unsaved() {
+        let (mut app, current_id) = make_app_with_dirty_directory();
+        let mut state = State::default();
+
+        let mut actions = execute_from_normal(&mut app, &mut state, "wdiff");
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Horizontal { .. }));
+        assert_eq!(state.modes.current, Mode::Normal);
+
+        actions.extend(execute_from_normal(&mut app, &mut state, "q"));
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Directory(_, _, _)));
+        assert_eq!(state.modes.current, Mode::Normal);
+
+        assert!(
+            !actions
+                .iter()
+                .any(|action| matches!(action, Action::Task(_))),
+            "reviewing and aborting a diff must not write anything; actions: {:?}",
+            actions,
+        );
+        assert!(matches!(
+            app.contents.buffers.get(&current_id),
+            Some(Buffer::Directory(it)) if it.buffer.has_unsaved_changes()
+        ));
+    }
+
     #[test]
     fn q_on_horizontal_emits_change_mode_to_navigation() {
         let mut app = make_app_with_horizontal_split();
@@ -897,6 +1129,131 @@ mod test {
         assert!(matches!(window, Window::Directory(_, _, _)));
     }
 
+    #[test]
+    fn q_on_dirty_edit_window_prints_error() {
+        let mut app = make_app_with_dirty_edit_split();
+        let mut state = make_state_with_command_mode();
+        let settings = Settings::default();
+
+        let actions = execute(&mut app, &mut state, &settings, None, "q");
+
+        assert!(contains_error_message(&actions));
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Horizontal { .. }));
+    }
+
+    #[test]
+    fn wq_on_edit_window_writes_content_and_closes_window() {
+        let mut app = make_app_with_dirty_edit_split();
+        let mut state = make_state_with_command_mode();
+        let settings = Settings::default();
+
+        let actions = execute(&mut app, &mut state, &settings, None, "wq");
+
+        let task = actions
+            .iter()
+            .find_map(|action| match action {
+                Action::Task(task @ Task::SaveEditContent(path, content))
+                    if path == Path::new("/tmp/edit.txt") && content == "first\ntest\n" =>
+                {
+                    Some(task.to_string())
+                }
+                _ => None,
+            })
+            .expect("wq should save the edit buffer");
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Horizontal { .. }));
+
+        crate::update::update_with_message(
+            &mut app,
+            &mut state,
+            &settings,
+            None,
+            Message::TaskEnded(task, true),
+        );
+
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Directory(_, _, _)));
+    }
+
+    #[test]
+    fn wq_on_edit_window_keeps_window_when_write_fails() {
+        let mut app = make_app_with_dirty_edit_split();
+        let mut state = make_state_with_command_mode();
+        let settings = Settings::default();
+        let buffer_id = app
+            .current_window()
+            .expect("test requires current tab")
+            .focused_viewport()
+            .buffer_id;
+
+        let actions = execute(&mut app, &mut state, &settings, None, "wq");
+        let task = actions
+            .iter()
+            .find_map(|action| match action {
+                Action::Task(task @ Task::SaveEditContent(..)) => Some(task.to_string()),
+                _ => None,
+            })
+            .expect("wq should save the edit buffer");
+
+        let actions = crate::update::update_with_message(
+            &mut app,
+            &mut state,
+            &settings,
+            None,
+            Message::TaskEnded(task, false),
+        );
+
+        assert!(!contains_quit_action(
+            &actions,
+            &QuitMode::FailOnRunningTasks
+        ));
+        let window = app.current_window().expect("test requires current tab");
+        assert!(matches!(window, Window::Horizontal { .. }));
+        assert_eq!(window.focused_viewport().buffer_id, buffer_id);
+        assert!(matches!(
+            app.contents.buffers.get(&buffer_id),
+            Some(Buffer::Edit(it)) if it.buffer.has_unsaved_changes() && !it.close_after_save
+        ));
+    }
+
+    #[test]
+    fn wq_on_last_edit_window_quits_after_write() {
+        let mut app = make_app_with_dirty_edit_split();
+        let window = app.current_window_mut().expect("test requires current tab");
+        if let Window::Horizontal { second, .. } = std::mem::take(window) {
+            *window = *second;
+        }
+        let mut state = make_state_with_command_mode();
+        let settings = Settings::default();
+
+        let actions = execute(&mut app, &mut state, &settings, None, "wq");
+        assert!(!contains_quit_action(
+            &actions,
+            &QuitMode::FailOnRunningTasks
+        ));
+        let task = actions
+            .iter()
+            .find_map(|action| match action {
+                Action::Task(task @ Task::SaveEditContent(..)) => Some(task.to_string()),
+                _ => None,
+            })
+            .expect("wq should save the edit buffer");
+
+        let actions = crate::update::update_with_message(
+            &mut app,
+            &mut state,
+            &settings,
+            None,
+            Message::TaskEnded(task, true),
+        );
+
+        assert!(contains_quit_action(
+            &actions,
+            &QuitMode::FailOnRunningTasks
+        ));
+    }
+
     #[test]
     fn q_on_split_focused_on_dirty_buffer_prints_error() {
         let mut app = make_app_with_unsaved_changes_and_split();
@@ -1738,4 +2095,67 @@ mod test {
             "no split should have been created",
         );
     }
+
+    fn make_lua_with_command() -> yeet_lua::Lua {
+        yeet_lua::init_from_script(
+            r#"
+            y.command.register("deploy", function(ctx) deployed = ctx.args .. tostring(ctx.bang) end, { bang = true })
+            y.command.register("fail", function(ctx) error("boom") end)
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn user_command_is_invoked_with_args_and_bang() {
+        let mut app = App::default();
+        let mut state = make_state_with_command_mode();
+        let lua = make_lua_with_command();
+
+        let actions = execute(
+            &mut app,
+            &mut state,
+            &Settings::default(),
+            Some(&lua),
+            "deploy! prod",
+        );
+
+        let deployed: String = lua.globals().get("deployed").unwrap();
+        assert_eq!(deployed, "prodtrue");
+        assert!(!actions.iter().any(|action| matches!(
+            action,
+            Action::EmitMessages(msgs) if msgs.iter().any(|m| matches!(m, Message::Log(LogSeverity::Error, _)))
+        )));
+    }
+
+    #[test]
+    fn user_command_error_and_unknown_bang_are_logged() {
+        let mut app = App::default();
+        let mut state = make_state_with_command_mode();
+        let lua = make_lua_with_command();
+
+        let actions = execute(
+            &mut app,
+            &mut state,
+            &Settings::default(),
+            Some(&lua),
+            "fail",
+        );
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::EmitMessages(msgs) if msgs.iter().any(|m| matches!(m, Message::Log(LogSeverity::Error, s) if s.contains("command 'fail' failed")))
+        )));
+
+        let actions = execute(
+            &mut app,
+            &mut state,
+            &Settings::default(),
+            Some(&lua),
+            "fail! now",
+        );
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::EmitMessages(msgs) if msgs.iter().any(|m| matches!(m, Message::Log(LogSeverity::Error, s) if s.contains("is not valid")))
+        )));
+    }
 }
@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use yeet_buffer::model::Mode;
+
+use crate::{
+    action::Action,
+    model::App,
+    task::{OwnerChange, PermissionChange, Task},
+    update::visual,
+};
+
+pub fn chmod(app: &App, args: &str, mode_before: Mode, mode: Mode) -> Vec<Action> {
+    let (recursive, args) = split_recursive_flag(args);
+    if args.is_empty() {
+        return super::print_error("chmod requires a mode", mode_before, mode);
+    }
+
+    let change = match PermissionChange::parse(args) {
+        Ok(it) => it,
+        Err(err) => {
+            return super::print_error(&format!("chmod failed: {}", err), mode_before, mode)
+        }
+    };
+
+    match get_paths(app) {
+        Some(paths) => super::add_change_mode(
+            mode_before,
+            mode,
+            vec![Action::Task(Task::SetPermissions(paths, change, recursive))],
+        ),
+        None => super::print_error("chmod failed: no entry selected", mode_before, mode),
+    }
+}
+
+pub fn chown(app: &App, args: &str, mode_before: Mode, mode: Mode) -> Vec<Action> {
+    let (recursive, args) = split_recursive_flag(args);
+    if args.is_empty() {
+        return super::print_error("chown requires an owner", mode_before, mode);
+    }
+
+    let change = match OwnerChange::parse(args) {
+        Ok(it) => it,
+        Err(err) => {
+            return super::print_error(&format!("chown failed: {}", err), mode_before, mode)
+        }
+    };
+
+    match get_paths(app) {
+        Some(paths) => super::add_change_mode(
+            mode_before,
+            mode,
+            vec![Action::Task(Task::SetOwner(paths, change, recursive))],
+        ),
+        None => super::print_error("chown failed: no entry selected", mode_before, mode),
+    }
+}
+
+fn split_recursive_flag(args: &str) -> (bool, &str) {
+    let args = args.trim();
+    match args.strip_prefix("-R") {
+        Some(rest) if rest.is_empty() || rest.starts_with(' ') => (true, rest.trim()),
+        _ => (false, args),
+    }
+}
+
+fn get_paths(app: &App) -> Option<Vec<PathBuf>> {
+    visual::get_selected_paths(app)
+        .or_else(|| super::get_preview_path(app).map(|path| vec![path.to_path_buf()]))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_buffer::model::{CommandMode, Mode};
+
+    use crate::{
+        action::Action,
+        event::{LogSeverity, Message},
+        model::{App, Buffer},
+        task::{OwnerChange, PermissionChange, Task},
+        update::app,
+    };
+
+    use super::{chmod, chown, split_recursive_flag};
+
+    fn mode_before() -> Mode {
+        Mode::Command(CommandMode::Command)
+    }
+
+    fn make_app_with_preview(path: &str) -> App {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, _, preview_id) = app::get_focused_directory_buffer_ids(window).unwrap();
+        app.contents
+            .buffers
+            .insert(preview_id, Buffer::PathReference(PathBuf::from(path)));
+        app
+    }
+
+    fn has_error(actions: &[Action]) -> bool {
+        actions.iter().any(|action| {
+            matches!(action, Action::EmitMessages(messages) if messages
+                .iter()
+                .any(|m| matches!(m, Message::Log(LogSeverity::Error, _))))
+        })
+    }
+
+    #[test]
+    fn split_recursive_flag_detects_flag() {
+        assert_eq!(split_recursive_flag("-R 755"), (true, "755"));
+        assert_eq!(split_recursive_flag("u+x"), (false, "u+x"));
+        assert_eq!(split_recursive_flag("-R"), (true, ""));
+        assert_eq!(split_recursive_flag("-Rx"), (false, "-Rx"));
+    }
+
+    #[test]
+    fn chmod_creates_task_for_preview_path() {
+        let app = make_app_with_preview("/dir/file");
+
+        let actions = chmod(&app, "-R u+x", mode_before(), Mode::Navigation);
+
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::Task(Task::SetPermissions(paths, change, true))
+                if paths == &vec![PathBuf::from("/dir/file")]
+                    && Ok(change) == PermissionChange::parse("u+x").as_ref()
+        )));
+    }
+
+    #[test]
+    fn chmod_reports_invalid_mode() {
+        let app = make_app_with_preview("/dir/file");
+
+        let actions = chmod(&app, "u+q", mode_before(), Mode::Navigation);
+
+        assert!(has_error(&actions));
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, Action::Task(_))));
+    }
+
+    #[test]
+    fn chown_creates_task_for_preview_path() {
+        let app = make_app_with_preview("/dir/file");
+
+        let actions = chown(&app, "alice:staff", mode_before(), Mode::Navigation);
+
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            Action::Task(Task::SetOwner(_, change, false))
+                if change == &OwnerChange::parse("alice:staff").unwrap()
+        )));
+    }
+
+    #[test]
+    fn chown_requires_owner() {
+        let app = make_app_with_preview("/dir/file");
+
+        assert!(has_error(&chown(
+            &app,
+            "-R",
+            mode_before(),
+            Mode::Navigation
+        )));
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         register::Register,
         App, Buffer, SplitFocus, Tasks, Window,
     },
-    update::{junkyard, tab},
+    update::{app, junkyard, tab},
 };
 
 pub fn marks(marks: &Marks) -> Vec<Action> {
@@ -38,7 +38,7 @@ pub fn tasks(tasks: &Tasks) -> Vec<Action> {
     let mut tasks: Vec<_> = tasks
         .running
         .values()
-        .map(|task| format!("{:<4} {}", task.id, task.external_id))
+        .map(|task| task.to_string())
         .collect();
 
     tasks.sort();
@@ -157,6 +157,30 @@ pub fn register(register: &Register) -> Vec<Action> {
     vec![action::emit_keymap(KeymapMessage::Print(content))]
 }
 
+pub fn info(app: &App) -> Vec<Action> {
+    let buffer = app.current_window().ok().and_then(|window| {
+        let (_, _, preview_id) = app::get_focused_directory_buffer_ids(window)?;
+        app.contents.buffers.get(&preview_id)
+    });
+
+    let mut contents = vec![PrintContent::Default(":info".to_string())];
+    match buffer.and_then(|buffer| Some((buffer.resolve_path()?, buffer.mime()))) {
+        Some((path, mime)) => {
+            contents.push(PrintContent::Default(format!(
+                "Path {}",
+                path.to_string_lossy()
+            )));
+            contents.push(PrintContent::Default(format!(
+                "Type {}",
+                mime.unwrap_or("unknown")
+            )));
+        }
+        None => contents.push(PrintContent::Error("No entry selected".to_string())),
+    }
+
+    vec![action::emit_keymap(KeymapMessage::Print(contents))]
+}
+
 pub fn tabs(app: &App) -> Vec<Action> {
     let mut lines = vec![":tabs".to_string()];
     let ordered = tab::ordered_tab_ids(app);
@@ -207,6 +231,8 @@ fn tab_title_from_window_full_path(
         }
         Window::Tasks(_) => "Tasks".to_string(),
         Window::QuickFix(_) => "QuickFix".to_string(),
+        Window::Diff(_) => "Diff".to_string(),
+        Window::Edit(_) => "Edit".to_string(),
         Window::Help(_) => "Help".to_string(),
     }
 }
@@ -252,3 +278,65 @@ pub fn plugin_list(states: &[yeet_plugin::PluginState]) -> Vec<Action> {
 fn print_content(prefix: &char, content: &str) -> String {
     format!("\"{:<3} {}", prefix, content)
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+    use crate::{
+        action::Action,
+        event::Message,
+        model::{App, Buffer, ContentBuffer},
+        update::app,
+    };
+
+    use super::info;
+
+    fn get_printed(actions: &[Action]) -> Vec<PrintContent> {
+        actions
+            .iter()
+            .find_map(|action| match action {
+                Action::EmitMessages(messages) => messages.iter().find_map(|m| match m {
+                    Message::Keymap(KeymapMessage::Print(content)) => Some(content.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn info_prints_path_and_detected_type() {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, _, preview_id) = app::get_focused_directory_buffer_ids(window).unwrap();
+        app.contents.buffers.insert(
+            preview_id,
+            Buffer::Content(ContentBuffer {
+                path: PathBuf::from("/dir/run"),
+                mime: Some("text/x-python".to_string()),
+                ..Default::default()
+            }),
+        );
+
+        let printed = get_printed(&info(&app));
+
+        assert_eq!(
+            printed,
+            vec![
+                PrintContent::Default(":info".to_string()),
+                PrintContent::Default("Path /dir/run".to_string()),
+                PrintContent::Default("Type text/x-python".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn info_without_preview_prints_error() {
+        let printed = get_printed(&info(&App::default()));
+
+        assert!(matches!(printed.last(), Some(PrintContent::Error(_))));
+    }
+}
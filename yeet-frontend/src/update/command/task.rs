@@ -77,7 +77,7 @@ fn focus_tasks(window: &mut Window) -> bool {
             }
         }
         Window::Tasks(_) => true,
-        Window::QuickFix(_) | Window::Help(_) => false,
+        Window::QuickFix(_) | Window::Diff(_) | Window::Edit(_) | Window::Help(_) => false,
         Window::Directory(_, _, _) => false,
     }
 }
@@ -131,7 +131,7 @@ fn find_tasks_viewport_mut(window: &mut Window) -> Option<&mut ViewPort> {
             find_tasks_viewport_mut(first).or_else(|| find_tasks_viewport_mut(second))
         }
         Window::Tasks(vp) => Some(vp),
-        Window::QuickFix(_) | Window::Help(_) => None,
+        Window::QuickFix(_) | Window::Diff(_) | Window::Edit(_) | Window::Help(_) => None,
         Window::Directory(_, _, _) => None,
     }
 }
@@ -146,14 +146,20 @@ fn build_task_lines(tasks: &Tasks, lua: Option<&LuaConfiguration>) -> Vec<Buffer
 }
 
 fn build_task_line(task: &CurrentTask, lua: Option<&LuaConfiguration>) -> BufferLine {
-    let formatted = format!("{:<4} {}", task.id, task.external_id);
+    let formatted = task.to_string();
     let mut line = if task.token.is_cancelled() {
         BufferLine::from(&format!("\x1b[9;90m{}\x1b[0m", formatted))
     } else {
         BufferLine::from(&formatted)
     };
     if let Some(lua) = lua {
-        yeet_lua::invoke_on_bufferline_mutate(lua, &mut line, yeet_lua::BufferType::Tasks, None);
+        yeet_lua::invoke_on_bufferline_mutate(
+            lua,
+            &mut line,
+            yeet_lua::BufferType::Tasks,
+            None,
+            None,
+        );
     }
     line
 }
@@ -162,7 +168,10 @@ fn build_task_line(task: &CurrentTask, lua: Option<&LuaConfiguration>) -> Buffer
 mod test {
     use tokio_util::sync::CancellationToken;
 
-    use crate::model::{App, Buffer, CurrentTask, SplitFocus, Tasks, Window};
+    use crate::{
+        model::{App, Buffer, CurrentTask, SplitFocus, Tasks, Window},
+        task::TaskKind,
+    };
 
     use super::open;
 
@@ -173,6 +182,8 @@ mod test {
             CurrentTask {
                 external_id: "rg foo".to_string(),
                 id: 1,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -181,6 +192,8 @@ mod test {
             CurrentTask {
                 external_id: "fd bar".to_string(),
                 id: 12,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
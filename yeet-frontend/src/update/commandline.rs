@@ -3,6 +3,7 @@ use yeet_buffer::{
     model::{ansi::Ansi, BufferLine, CommandMode, Mode, SearchDirection},
 };
 use yeet_keymap::message::{KeymapMessage, PrintContent};
+use yeet_lua::LuaConfiguration;
 
 use crate::{
     action::{self, Action},
@@ -22,7 +23,7 @@ pub fn update(
 ) -> Vec<Action> {
     let command_mode = match mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
     let buffer = &mut commandline.buffer;
@@ -67,7 +68,7 @@ pub fn modify(
 ) -> Vec<Action> {
     let command_mode = match &modes.current {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
     let text_buffer = &mut app.commandline.buffer;
@@ -153,6 +154,65 @@ pub fn modify(
     }
 }
 
+pub fn complete(app: &mut App, modes: &ModeState, lua: Option<&LuaConfiguration>) -> Vec<Action> {
+    let (Mode::Command(CommandMode::Command), Some(lua)) = (&modes.current, lua) else {
+        return Vec::new();
+    };
+
+    let Some(line) = app.commandline.buffer.lines.last_mut() else {
+        return Vec::new();
+    };
+
+    let text = line.content.to_stripped_string();
+    let (head, lead, candidates) = match text.split_once(' ') {
+        Some((name, lead)) => (
+            format!("{} ", name),
+            lead,
+            yeet_lua::complete_command(lua, name, lead),
+        ),
+        None => (
+            String::new(),
+            text.as_str(),
+            yeet_lua::read_commands(lua)
+                .into_iter()
+                .map(|command| command.name)
+                .filter(|name| name.starts_with(text.as_str()))
+                .collect(),
+        ),
+    };
+
+    let Some(completion) = get_common_prefix(&candidates) else {
+        return Vec::new();
+    };
+
+    if completion.len() > lead.len() {
+        line.content = Ansi::new(&format!("{}{}", head, completion));
+        yeet_buffer::update(
+            Some(&mut app.commandline.viewport),
+            &modes.current,
+            &mut app.commandline.buffer,
+            std::slice::from_ref(&BufferMessage::MoveCursor(1, CursorDirection::LineEnd)),
+        );
+    }
+
+    Vec::new()
+}
+
+fn get_common_prefix(candidates: &[String]) -> Option<&str> {
+    let (first, rest) = candidates.split_first()?;
+    let length = rest.iter().fold(first.len(), |length, candidate| {
+        first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((index, a), b)| *index < length && a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+    });
+
+    Some(&first[..length])
+}
+
 pub fn update_on_execute(
     app: &mut App,
     register: &mut Register,
@@ -160,7 +220,7 @@ pub fn update_on_execute(
 ) -> Vec<Action> {
     let command_mode = match &modes.current {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
     let messages = match command_mode {
@@ -301,14 +361,59 @@ pub fn print(
     actions
 }
 
-fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
+pub fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
     if let Some(mode) = mode_before {
         match mode {
             Mode::Command(_) => Mode::default(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::Visual => Mode::Navigation,
         }
     } else {
         Mode::default()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use yeet_buffer::model::{ansi::Ansi, BufferLine, CommandMode, Mode};
+
+    use crate::model::{App, ModeState};
+
+    use super::complete;
+
+    fn complete_text(text: &str) -> String {
+        let lua = yeet_lua::init_from_script(
+            r#"
+            y.command.register("deploy-artifacts", function() end, { complete = { "production", "preview" } })
+            y.command.register("deploy-docs", function() end)
+            "#,
+        )
+        .unwrap();
+
+        let mut app = App::default();
+        app.commandline.buffer.lines = vec![BufferLine {
+            prefix: Some(":".to_string()),
+            content: Ansi::new(text),
+            ..Default::default()
+        }];
+        let modes = ModeState {
+            current: Mode::Command(CommandMode::Command),
+            ..Default::default()
+        };
+
+        complete(&mut app, &modes, Some(&lua));
+        app.commandline.buffer.lines[0].content.to_stripped_string()
+    }
+
+    #[test]
+    fn complete_extends_command_names_and_arguments() {
+        assert_eq!(complete_text("dep"), "deploy-");
+        assert_eq!(complete_text("deploy-a"), "deploy-artifacts");
+        assert_eq!(complete_text("deploy-artifacts p"), "deploy-artifacts pr");
+        assert_eq!(
+            complete_text("deploy-artifacts pro"),
+            "deploy-artifacts production"
+        );
+        assert_eq!(complete_text("unknown x"), "unknown x");
+    }
+}
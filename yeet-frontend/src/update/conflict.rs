@@ -0,0 +1,581 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::{
+    message::{BufferMessage, TextModification},
+    model::{CommandMode, Mode},
+};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    action::Action,
+    event::{LogSeverity, Message},
+    model::{
+        conflict::{Conflicts, Transfer, TransferSource},
+        junkyard::JunkYard,
+        ModeState,
+    },
+    settings::ConflictPolicy,
+    task::{Existing, Task},
+    update::{commandline, junkyard},
+};
+
+pub fn transfer(
+    conflicts: &mut Conflicts,
+    junk: &mut JunkYard,
+    policy: &ConflictPolicy,
+    from: &Mode,
+    transfers: Vec<Transfer>,
+) -> Vec<Action> {
+    let is_prompting = !conflicts.pending.is_empty();
+    if !is_prompting {
+        conflicts.reserved.clear();
+    }
+
+    let mut actions = Vec::new();
+    let mut messages = Vec::new();
+    let mut overwrites = Vec::new();
+    for transfer in transfers {
+        // NOTE: tasks of one batch run concurrently, thus targets shared in a batch are renamed
+        if conflicts.reserved.contains(&transfer.target) {
+            actions.push(to_renamed_action(transfer, &mut conflicts.reserved));
+            continue;
+        }
+
+        if !exists(&transfer.target) {
+            conflicts.reserved.insert(transfer.target.clone());
+            actions.push(to_action(transfer, Existing::Fail));
+            continue;
+        }
+
+        match policy {
+            ConflictPolicy::Overwrite => {
+                conflicts.reserved.insert(transfer.target.clone());
+                overwrites.push(transfer);
+            }
+            ConflictPolicy::Prompt => conflicts.pending.push(transfer),
+            ConflictPolicy::Rename => {
+                actions.push(to_renamed_action(transfer, &mut conflicts.reserved))
+            }
+            ConflictPolicy::Skip => messages.push(skipped(&transfer.target)),
+        }
+    }
+    actions.extend(to_overwrite_actions(junk, overwrites));
+
+    if !is_prompting {
+        if let Some(conflict) = conflicts.pending.first() {
+            messages.push(Message::Keymap(KeymapMessage::Buffer(
+                BufferMessage::ChangeMode(from.clone(), Mode::Command(CommandMode::PrintMultiline)),
+            )));
+            messages.push(prompt(conflict));
+        }
+    }
+
+    if !messages.is_empty() {
+        actions.push(Action::EmitMessages(messages));
+    }
+
+    actions
+}
+
+pub fn answer(
+    conflicts: &mut Conflicts,
+    junk: &mut JunkYard,
+    modes: &ModeState,
+    modification: &TextModification,
+) -> Vec<Action> {
+    let key = match modification {
+        TextModification::Insert(key) => key.as_str(),
+        _ => return Vec::new(),
+    };
+
+    let mut actions = Vec::new();
+    let mut messages = Vec::new();
+    match key {
+        "a" => messages.extend(conflicts.pending.drain(..).map(|it| skipped(&it.target))),
+        "o" | "r" | "s" if !conflicts.pending.is_empty() => {
+            let transfer = conflicts.pending.remove(0);
+            match key {
+                "o" if conflicts.reserved.contains(&transfer.target) => {
+                    actions.push(to_renamed_action(transfer, &mut conflicts.reserved))
+                }
+                "o" => {
+                    conflicts.reserved.insert(transfer.target.clone());
+                    actions.extend(to_overwrite_actions(junk, vec![transfer]));
+                }
+                "r" => actions.push(to_renamed_action(transfer, &mut conflicts.reserved)),
+                _ => messages.push(skipped(&transfer.target)),
+            }
+        }
+        _ => return Vec::new(),
+    }
+
+    match conflicts.pending.first() {
+        Some(conflict) => messages.push(prompt(conflict)),
+        None => messages.push(Message::Keymap(KeymapMessage::Buffer(
+            BufferMessage::ChangeMode(
+                modes.current.clone(),
+                commandline::get_mode_after_command(&modes.previous),
+            ),
+        ))),
+    }
+
+    actions.push(Action::EmitMessages(messages));
+    actions
+}
+
+pub fn abort(conflicts: &mut Conflicts) -> Vec<Action> {
+    if conflicts.pending.is_empty() {
+        return Vec::new();
+    }
+
+    let messages = conflicts
+        .pending
+        .drain(..)
+        .map(|it| skipped(&it.target))
+        .collect();
+    vec![Action::EmitMessages(messages)]
+}
+
+pub fn get_free_path(target: &Path, reserved: &HashSet<PathBuf>) -> PathBuf {
+    let parent = target.parent().unwrap_or(Path::new(""));
+    let (stem, extension) = match (target.file_stem(), target.extension()) {
+        (Some(stem), Some(extension)) => (
+            stem.to_string_lossy().to_string(),
+            format!(".{}", extension.to_string_lossy()),
+        ),
+        _ => (
+            target
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            String::new(),
+        ),
+    };
+
+    let mut index = 1;
+    loop {
+        let candidate = parent.join(format!("{} ({}){}", stem, index, extension));
+        if !exists(&candidate) && !reserved.contains(&candidate) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+fn to_action(transfer: Transfer, existing: Existing) -> Action {
+    let task = match transfer.source {
+        TransferSource::Copy(source) => Task::CopyPath(source, transfer.target, existing),
+        TransferSource::Link(source) => Task::LinkPath(source, transfer.target, existing),
+        TransferSource::Move(source) => Task::RenamePath(source, transfer.target, existing),
+        TransferSource::Restore(entry) => Task::RestorePath(entry, transfer.target, existing),
+        TransferSource::Symlink(link) => Task::SymlinkPath(link, transfer.target, existing),
+    };
+
+    Action::Task(task)
+}
+
+fn to_overwrite_actions(junk: &mut JunkYard, transfers: Vec<Transfer>) -> Vec<Action> {
+    if transfers.is_empty() {
+        return Vec::new();
+    }
+
+    let targets = transfers.iter().map(|it| it.target.clone()).collect();
+    let (transaction, obsolete) = junkyard::trash_to_junkyard(junk, targets);
+
+    let mut actions: Vec<_> = transfers
+        .into_iter()
+        .zip(transaction.entries)
+        .map(|(transfer, entry)| to_action(transfer, Existing::Overwrite(entry)))
+        .collect();
+
+    if let Some(obsolete) = obsolete {
+        for entry in obsolete.entries {
+            actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+        }
+    }
+
+    actions
+}
+
+fn to_renamed_action(transfer: Transfer, reserved: &mut HashSet<PathBuf>) -> Action {
+    let target = get_free_path(&transfer.target, reserved);
+    reserved.insert(target.clone());
+    to_action(
+        Transfer {
+            source: transfer.source,
+            target,
+        },
+        Existing::Fail,
+    )
+}
+
+fn prompt(conflict: &Transfer) -> Message {
+    Message::Keymap(KeymapMessage::Print(vec![PrintContent::Warning(format!(
+        "{} exists: [r]ename, [o]verwrite, [s]kip, skip [a]ll",
+        conflict.target.display()
+    ))]))
+}
+
+fn skipped(target: &Path) -> Message {
+    Message::Log(
+        LogSeverity::Information,
+        format!("Skipped {}: target exists already", target.display()),
+    )
+}
+
+fn exists(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashSet,
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use yeet_buffer::{
+        message::{BufferMessage, TextModification},
+        model::{CommandMode, Mode},
+    };
+    use yeet_keymap::message::KeymapMessage;
+
+    use crate::{
+        action::Action,
+        event::Message,
+        model::{
+            conflict::{Conflicts, Transfer, TransferSource},
+            junkyard::JunkYard,
+            ModeState,
+        },
+        settings::ConflictPolicy,
+        task::{Existing, Task},
+    };
+
+    use super::{abort, answer, get_free_path, transfer};
+
+    fn setup() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let existing = dir.path().join("file.txt");
+        fs::write(&existing, "existing").unwrap();
+        (dir, existing)
+    }
+
+    fn copy(target: &Path) -> Transfer {
+        Transfer {
+            source: TransferSource::Copy(PathBuf::from("/source/file.txt")),
+            target: target.to_path_buf(),
+        }
+    }
+
+    fn tasks(actions: &[Action]) -> Vec<&Task> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Task(task) => Some(task),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn messages(actions: &[Action]) -> Vec<&Message> {
+        actions
+            .iter()
+            .flat_map(|action| match action {
+                Action::EmitMessages(messages) => messages.iter().collect(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transfer_without_conflict_fails_on_existing() {
+        let (dir, _) = setup();
+        let target = dir.path().join("new.txt");
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Prompt,
+            &Mode::Navigation,
+            vec![copy(&target)],
+        );
+
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::CopyPath(
+                PathBuf::from("/source/file.txt"),
+                target,
+                Existing::Fail
+            )]
+        );
+        assert!(conflicts.pending.is_empty());
+    }
+
+    #[test]
+    fn transfer_with_rename_policy_suffixes_target() {
+        let (dir, existing) = setup();
+        fs::write(dir.path().join("file (1).txt"), "").unwrap();
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Rename,
+            &Mode::Navigation,
+            vec![copy(&existing)],
+        );
+
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::CopyPath(
+                PathBuf::from("/source/file.txt"),
+                dir.path().join("file (2).txt"),
+                Existing::Fail
+            )]
+        );
+    }
+
+    #[test]
+    fn transfer_with_overwrite_policy_replaces_target() {
+        let (_dir, existing) = setup();
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Overwrite,
+            &Mode::Navigation,
+            vec![copy(&existing)],
+        );
+
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::CopyPath(
+                PathBuf::from("/source/file.txt"),
+                existing.clone(),
+                Existing::Overwrite(junk.trashed[0].entries[0].clone())
+            )]
+        );
+        assert_eq!(junk.trashed[0].entries[0].target, existing);
+    }
+
+    #[test]
+    fn transfer_with_skip_policy_logs_and_drops_transfer() {
+        let (_dir, existing) = setup();
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Skip,
+            &Mode::Navigation,
+            vec![copy(&existing)],
+        );
+
+        assert!(tasks(&actions).is_empty());
+        assert!(matches!(
+            messages(&actions).as_slice(),
+            [Message::Log(_, _)]
+        ));
+    }
+
+    #[test]
+    fn transfer_with_prompt_policy_queues_conflict_and_prompts() {
+        let (_dir, existing) = setup();
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Prompt,
+            &Mode::Navigation,
+            vec![copy(&existing)],
+        );
+
+        assert!(tasks(&actions).is_empty());
+        assert_eq!(conflicts.pending, vec![copy(&existing)]);
+        assert!(matches!(
+            messages(&actions).as_slice(),
+            [
+                Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+                    Mode::Navigation,
+                    Mode::Command(CommandMode::PrintMultiline)
+                ))),
+                Message::Keymap(KeymapMessage::Print(_)),
+            ]
+        ));
+    }
+
+    #[test]
+    fn answer_resolves_conflicts_one_by_one() {
+        let (dir, existing) = setup();
+        let other = dir.path().join("other.txt");
+        let mut conflicts = Conflicts {
+            pending: vec![copy(&existing), copy(&other)],
+            ..Default::default()
+        };
+        let mut junk = JunkYard::default();
+        let modes = ModeState {
+            current: Mode::Command(CommandMode::PrintMultiline),
+            previous: Some(Mode::Visual),
+        };
+
+        let actions = answer(
+            &mut conflicts,
+            &mut junk,
+            &modes,
+            &TextModification::Insert("o".to_string()),
+        );
+
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::CopyPath(
+                PathBuf::from("/source/file.txt"),
+                existing.clone(),
+                Existing::Overwrite(junk.trashed[0].entries[0].clone())
+            )]
+        );
+        assert_eq!(junk.trashed[0].entries[0].target, existing);
+        assert!(matches!(
+            messages(&actions).as_slice(),
+            [Message::Keymap(KeymapMessage::Print(_))]
+        ));
+
+        let actions = answer(
+            &mut conflicts,
+            &mut junk,
+            &modes,
+            &TextModification::Insert("s".to_string()),
+        );
+
+        assert!(tasks(&actions).is_empty());
+        assert!(conflicts.pending.is_empty());
+        assert!(matches!(
+            messages(&actions).as_slice(),
+            [
+                Message::Log(_, _),
+                Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+                    _,
+                    Mode::Navigation
+                ))),
+            ]
+        ));
+    }
+
+    #[test]
+    fn answer_ignores_unknown_keys() {
+        let (_dir, existing) = setup();
+        let mut conflicts = Conflicts {
+            pending: vec![copy(&existing)],
+            ..Default::default()
+        };
+        let mut junk = JunkYard::default();
+
+        let actions = answer(
+            &mut conflicts,
+            &mut junk,
+            &ModeState::default(),
+            &TextModification::Insert("x".to_string()),
+        );
+
+        assert!(actions.is_empty());
+        assert_eq!(conflicts.pending.len(), 1);
+    }
+
+    #[test]
+    fn abort_skips_all_pending_conflicts() {
+        let (dir, existing) = setup();
+        let mut conflicts = Conflicts {
+            pending: vec![copy(&existing), copy(&dir.path().join("other.txt"))],
+            ..Default::default()
+        };
+
+        let actions = abort(&mut conflicts);
+
+        assert!(conflicts.pending.is_empty());
+        assert_eq!(messages(&actions).len(), 2);
+    }
+
+    #[test]
+    fn get_free_path_handles_names_without_extension() {
+        let (dir, _) = setup();
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+
+        assert_eq!(
+            get_free_path(&folder, &HashSet::new()),
+            dir.path().join("folder (1)")
+        );
+    }
+
+    #[test]
+    fn get_free_path_skips_reserved_names() {
+        let (dir, existing) = setup();
+        let reserved = HashSet::from([dir.path().join("file (1).txt")]);
+
+        assert_eq!(
+            get_free_path(&existing, &reserved),
+            dir.path().join("file (2).txt")
+        );
+    }
+
+    #[test]
+    fn transfer_renames_colliding_sources_of_one_batch() {
+        let (dir, existing) = setup();
+        let other = dir.path().join("new.txt");
+        let mut conflicts = Conflicts::default();
+        let mut junk = JunkYard::default();
+
+        let transfer_from = |source: &str, target: &Path| Transfer {
+            source: TransferSource::Copy(PathBuf::from(source)),
+            target: target.to_path_buf(),
+        };
+
+        let actions = transfer(
+            &mut conflicts,
+            &mut junk,
+            &ConflictPolicy::Rename,
+            &Mode::Navigation,
+            vec![
+                transfer_from("/first/file.txt", &existing),
+                transfer_from("/second/file.txt", &existing),
+                transfer_from("/first/new.txt", &other),
+                transfer_from("/second/new.txt", &other),
+            ],
+        );
+
+        assert_eq!(
+            tasks(&actions),
+            vec![
+                &Task::CopyPath(
+                    PathBuf::from("/first/file.txt"),
+                    dir.path().join("file (1).txt"),
+                    Existing::Fail
+                ),
+                &Task::CopyPath(
+                    PathBuf::from("/second/file.txt"),
+                    dir.path().join("file (2).txt"),
+                    Existing::Fail
+                ),
+                &Task::CopyPath(PathBuf::from("/first/new.txt"), other, Existing::Fail),
+                &Task::CopyPath(
+                    PathBuf::from("/second/new.txt"),
+                    dir.path().join("new (1).txt"),
+                    Existing::Fail
+                ),
+            ]
+        );
+    }
+}
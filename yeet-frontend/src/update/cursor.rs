@@ -2,14 +2,14 @@ use std::slice;
 
 use yeet_buffer::{
     message::{BufferMessage, CursorDirection, Search},
-    model::{viewport::ViewPort, BufferResult, Mode, SearchDirection},
+    model::{viewport::ViewPort, BufferResult, Mode, SearchDirection, TextBuffer},
 };
 use yeet_lua::LuaConfiguration;
 
 use crate::{
     action::Action,
     error::AppError,
-    model::{history::History, App, Buffer, Contents, DirectoryBuffer, State},
+    model::{history::History, register::Register, App, Buffer, Contents, DirectoryBuffer, State},
     update::{history, register},
 };
 
@@ -83,6 +83,42 @@ pub fn set_cursor_index_to_selection(
     result.contains(&BufferResult::CursorPositionChanged)
 }
 
+pub fn get_motion_by_search_register(
+    register: &Register,
+    mtn: &CursorDirection,
+) -> Option<CursorDirection> {
+    let drctn = match mtn {
+        CursorDirection::Search(it) => it,
+        _ => return Some(mtn.clone()),
+    };
+
+    let current_drctn = register::get_direction_from_search_register(register)?;
+    let direction = match (drctn, current_drctn) {
+        (Search::Next, SearchDirection::Down) => Search::Next,
+        (Search::Next, SearchDirection::Up) => Search::Previous,
+        (Search::Previous, SearchDirection::Down) => Search::Previous,
+        (Search::Previous, SearchDirection::Up) => Search::Next,
+    };
+
+    Some(CursorDirection::Search(direction))
+}
+
+pub fn move_directory_cursor(
+    mode: &Mode,
+    viewport: &mut ViewPort,
+    buffer: &mut TextBuffer,
+    rpt: &usize,
+    mtn: &CursorDirection,
+) {
+    let is_entry_mode = matches!(mode, Mode::Navigation | Mode::Visual);
+    if is_entry_mode && yeet_buffer::update_cursor_by_entry_find(viewport, buffer, rpt, mtn) {
+        return;
+    }
+
+    let msg = BufferMessage::MoveCursor(*rpt, mtn.clone());
+    yeet_buffer::update(Some(viewport), mode, buffer, slice::from_ref(&msg));
+}
+
 pub fn relocate(
     app: &mut App,
     state: &mut State,
@@ -110,36 +146,18 @@ pub fn relocate(
 
     match focused {
         Buffer::Directory(buffer) => {
-            let msg = BufferMessage::MoveCursor(*rpt, mtn.clone());
-            if let CursorDirection::Search(drctn) = mtn {
-                let current_drctn =
-                    match register::get_direction_from_search_register(&state.register) {
-                        Some(it) => it,
-                        None => return Ok(Vec::new()),
-                    };
-
-                let direction = match (drctn, current_drctn) {
-                    (Search::Next, SearchDirection::Down) => Search::Next,
-                    (Search::Next, SearchDirection::Up) => Search::Previous,
-                    (Search::Previous, SearchDirection::Down) => Search::Previous,
-                    (Search::Previous, SearchDirection::Up) => Search::Next,
-                };
-
-                let msg = BufferMessage::MoveCursor(*rpt, CursorDirection::Search(direction));
-                yeet_buffer::update(
-                    Some(viewport),
-                    &state.modes.current,
-                    &mut buffer.buffer,
-                    slice::from_ref(&msg),
-                );
-            } else {
-                yeet_buffer::update(
-                    Some(viewport),
-                    &state.modes.current,
-                    &mut buffer.buffer,
-                    slice::from_ref(&msg),
-                );
-            }
+            let mtn = match get_motion_by_search_register(&state.register, mtn) {
+                Some(it) => it,
+                None => return Ok(Vec::new()),
+            };
+
+            move_directory_cursor(
+                &state.modes.current,
+                viewport,
+                &mut buffer.buffer,
+                rpt,
+                &mtn,
+            );
 
             let actions = selection::refresh_preview_from_current_selection(
                 app,
@@ -173,6 +191,31 @@ pub fn relocate(
             );
             Ok(Vec::new())
         }
+        Buffer::Diff(diff_buf) => {
+            let msg = BufferMessage::MoveCursor(*rpt, mtn.clone());
+            yeet_buffer::update(
+                Some(viewport),
+                &state.modes.current,
+                &mut diff_buf.buffer,
+                slice::from_ref(&msg),
+            );
+            Ok(Vec::new())
+        }
+        Buffer::Edit(edit_buf) => {
+            let mtn = match get_motion_by_search_register(&state.register, mtn) {
+                Some(it) => it,
+                None => return Ok(Vec::new()),
+            };
+
+            let msg = BufferMessage::MoveCursor(*rpt, mtn);
+            yeet_buffer::update(
+                Some(viewport),
+                &state.modes.current,
+                &mut edit_buf.buffer,
+                slice::from_ref(&msg),
+            );
+            Ok(Vec::new())
+        }
         Buffer::Help(help_buf) => {
             let msg = BufferMessage::MoveCursor(*rpt, mtn.clone());
             yeet_buffer::update(
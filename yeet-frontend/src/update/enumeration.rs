@@ -1,4 +1,7 @@
-use std::{path::PathBuf, slice};
+use std::{
+    path::{Path, PathBuf},
+    slice,
+};
 
 use yeet_buffer::{
     message::{BufferMessage, ViewPortDirection},
@@ -9,11 +12,12 @@ use yeet_lua::LuaConfiguration;
 use crate::{
     action::Action,
     error::AppError,
+    event::EnumerationEntry,
     model::{App, Buffer, DirectoryBuffer, DirectoryBufferState, State},
-    theme::Theme,
+    theme::{tokens, Theme},
     update::{
-        app, cursor, hook, selection,
-        sign::{set_sign_if_marked, set_sign_if_qfix},
+        app, cursor, git, hook, selection,
+        sign::{set_sign_if_git, set_sign_if_marked, set_sign_if_qfix},
     },
 };
 
@@ -22,7 +26,7 @@ pub fn change(
     state: &mut State,
     app: &mut App,
     path: &PathBuf,
-    content: &[String],
+    content: &[EnumerationEntry],
     selection: &Option<String>,
     theme: &Theme,
     lua: Option<&LuaConfiguration>,
@@ -99,7 +103,7 @@ pub fn finish(
     state: &mut State,
     app: &mut App,
     path: &PathBuf,
-    content: &[String],
+    content: &[EnumerationEntry],
     selection: &Option<String>,
     theme: &Theme,
     lua: Option<&LuaConfiguration>,
@@ -207,6 +211,7 @@ pub fn finish(
         )?);
     }
     app.current_tab_id = original_tab;
+    actions.extend(git::load(&mut state.git, &state.tasks, path));
 
     if let Some(lua) = lua {
         hook::invoke_on_window_change_for_focused(app, lua);
@@ -233,17 +238,19 @@ fn set_directory_content(
     let is_first_changed_event = buffer.buffer.lines.is_empty();
     let content: Vec<BufferLine> = contents
         .iter()
-        .map(|cntnt| {
-            let mut line = from_enumeration(cntnt);
-            let bare_name = cntnt.strip_suffix('/').unwrap_or(cntnt);
+        .map(|entry| {
+            let mut line = from_enumeration(&entry.name, entry.link.as_deref(), theme);
+            let bare_name = entry.name.strip_suffix('/').unwrap_or(&entry.name);
             set_sign_if_marked(&state.marks, &mut line, &path.join(bare_name), theme);
             set_sign_if_qfix(&state.qfix, &mut line, &path.join(bare_name), theme);
+            set_sign_if_git(&buffer.git, &mut line, &path.join(bare_name), theme);
             if let Some(lua) = lua {
                 yeet_lua::invoke_on_bufferline_mutate(
                     lua,
                     &mut line,
                     yeet_lua::BufferType::Directory,
                     Some(&path.join(bare_name)),
+                    None,
                 );
             }
 
@@ -283,33 +290,41 @@ fn set_directory_content(
 
 struct DirectoryContentUpdate<'a> {
     path: &'a PathBuf,
-    contents: &'a [String],
+    contents: &'a [EnumerationEntry],
     selection: &'a Option<String>,
     theme: &'a Theme,
     lua: Option<&'a LuaConfiguration>,
 }
 
-pub fn from_enumeration(content: &str) -> BufferLine {
+pub fn from_enumeration(content: &str, link: Option<&Path>, theme: &Theme) -> BufferLine {
     BufferLine {
         content: Ansi::new(content),
+        suffix: link.map(|link| {
+            format!(
+                "{} -> {}\x1b[39m",
+                theme.ansi_fg(tokens::SYMLINK_TARGET_FG),
+                link.display()
+            )
+        }),
         ..Default::default()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::{env, fs, time::SystemTime};
+    use std::{env, fs, path::Path, time::SystemTime};
 
     use yeet_buffer::model::{ansi::Ansi, viewport::ViewPort, Cursor, TextBuffer};
 
     use crate::{
         action::Action,
+        event::EnumerationEntry,
         model::{App, Buffer, DirectoryBuffer, Window},
         theme::Theme,
         update::app,
     };
 
-    use super::{change, finish};
+    use super::{change, finish, from_enumeration};
 
     fn unique_temp_dir() -> std::path::PathBuf {
         let nanos = SystemTime::now()
@@ -359,7 +374,10 @@ mod test {
             &mut state,
             &mut app,
             &current_path,
-            &["Cargo.toml".to_string()],
+            &[EnumerationEntry {
+                name: "Cargo.toml".to_string(),
+                link: None,
+            }],
             &None,
             &theme,
             None,
@@ -445,7 +463,10 @@ mod test {
             &mut state,
             &mut app,
             &base,
-            &[file_name.to_string()],
+            &[EnumerationEntry {
+                name: file_name.to_string(),
+                link: None,
+            }],
             &None,
             &theme,
             None,
@@ -468,4 +489,18 @@ mod test {
 
         let _ = fs::remove_dir_all(&base);
     }
+
+    #[test]
+    fn from_enumeration_adds_link_target_as_suffix() {
+        let theme = Theme::default();
+
+        let line = from_enumeration("link", Some(Path::new("../target")), &theme);
+
+        assert_eq!(line.content.to_stripped_string(), "link");
+        assert_eq!(
+            line.get_displayed_content().to_stripped_string(),
+            "link -> ../target"
+        );
+        assert!(from_enumeration("file", None, &theme).suffix.is_none());
+    }
 }
@@ -66,9 +66,12 @@ fn try_move(window: &mut Window, direction: &FocusDirection) -> bool {
                 _ => false,
             }
         }
-        Window::Directory(_, _, _) | Window::QuickFix(_) | Window::Tasks(_) | Window::Help(_) => {
-            false
-        }
+        Window::Directory(_, _, _)
+        | Window::QuickFix(_)
+        | Window::Tasks(_)
+        | Window::Diff(_)
+        | Window::Edit(_)
+        | Window::Help(_) => false,
     }
 }
 
@@ -102,7 +105,12 @@ fn enter_from(window: &mut Window, direction: &FocusDirection) {
                 enter_from(second, direction);
             }
         },
-        Window::Directory(_, _, _) | Window::QuickFix(_) | Window::Tasks(_) | Window::Help(_) => {}
+        Window::Directory(_, _, _)
+        | Window::QuickFix(_)
+        | Window::Tasks(_)
+        | Window::Diff(_)
+        | Window::Edit(_)
+        | Window::Help(_) => {}
     }
 }
 
@@ -0,0 +1,313 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    action::Action,
+    model::{
+        git::{GitRepositories, GitStatus},
+        App, Buffer, Tasks,
+    },
+    task::Task,
+    theme::Theme,
+};
+
+use super::sign;
+
+pub fn update(
+    app: &mut App,
+    repositories: &mut GitRepositories,
+    tasks: &Tasks,
+    path: &Path,
+    status: Option<GitStatus>,
+    theme: &Theme,
+) -> Vec<Action> {
+    repositories.roots.insert(
+        path.to_path_buf(),
+        status.as_ref().map(|status| status.repository.clone()),
+    );
+
+    let status = status.unwrap_or_default();
+    for buffer in app.contents.buffers.values_mut() {
+        let buffer = match buffer {
+            Buffer::Directory(it) if it.path == path => it,
+            _ => continue,
+        };
+
+        for line in buffer.buffer.lines.iter_mut() {
+            let name = line.content.to_stripped_string();
+            let entry = buffer.path.join(name.trim_end_matches('/'));
+            sign::set_sign_if_git(&status, line, &entry, theme);
+        }
+
+        buffer.git = status.clone();
+    }
+
+    if repositories.outdated.remove(path) {
+        load(repositories, tasks, path)
+    } else {
+        Vec::new()
+    }
+}
+
+pub fn load(repositories: &mut GitRepositories, tasks: &Tasks, path: &Path) -> Vec<Action> {
+    let repository = match repositories.roots.get(path) {
+        Some(None) => return Vec::new(),
+        Some(Some(repository)) => Some(repository.clone()),
+        None => None,
+    };
+
+    let task = Task::LoadGitStatus(path.to_path_buf(), repository);
+    if tasks.running.contains_key(&task.to_string()) {
+        repositories.outdated.insert(path.to_path_buf());
+        return Vec::new();
+    }
+
+    vec![Action::Task(task)]
+}
+
+pub fn refresh(
+    app: &App,
+    repositories: &mut GitRepositories,
+    tasks: &Tasks,
+    paths: &[PathBuf],
+) -> Vec<Action> {
+    for path in paths {
+        if path.file_name().is_some_and(|name| name == ".git") {
+            if let Some(parent) = path.parent() {
+                repositories
+                    .roots
+                    .retain(|directory, _| !directory.starts_with(parent));
+            }
+        }
+    }
+
+    let mut directories: Vec<_> = paths
+        .iter()
+        .filter_map(|path| path.parent())
+        .filter(|parent| {
+            app.contents.buffers.values().any(|buffer| match buffer {
+                Buffer::Directory(it) => it.path == *parent,
+                _ => false,
+            })
+        })
+        .collect();
+
+    directories.sort_unstable();
+    directories.dedup();
+
+    directories
+        .into_iter()
+        .flat_map(|directory| load(repositories, tasks, directory))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use tokio_util::sync::CancellationToken;
+    use yeet_buffer::model::{ansi::Ansi, BufferLine, TextBuffer};
+
+    use crate::{
+        action::Action,
+        model::{
+            git::{
+                GitEntryStatus, GitRepositories, GitStatus, GIT_MODIFIED_SIGN_ID,
+                GIT_UNTRACKED_SIGN_ID,
+            },
+            App, Buffer, CurrentTask, DirectoryBuffer, Tasks,
+        },
+        task::{Task, TaskKind},
+        theme::Theme,
+    };
+
+    use super::{load, refresh, update};
+
+    fn make_app(path: &str, names: &[&str]) -> App {
+        let mut app = App::default();
+        let lines = names
+            .iter()
+            .map(|name| BufferLine {
+                content: Ansi::new(name),
+                ..Default::default()
+            })
+            .collect();
+
+        app.contents.buffers.insert(
+            42,
+            Buffer::Directory(DirectoryBuffer {
+                path: PathBuf::from(path),
+                buffer: TextBuffer::from_lines(lines),
+                ..Default::default()
+            }),
+        );
+        app
+    }
+
+    fn get_sign_ids(app: &App) -> Vec<Vec<&'static str>> {
+        match app.contents.buffers.get(&42) {
+            Some(Buffer::Directory(it)) => it
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.signs.iter().map(|sign| sign.id).collect())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn update_replaces_git_signs_and_sets_branch() {
+        let mut app = make_app("/repo", &["src/", "README.md"]);
+        let status = GitStatus {
+            branch: Some("main".to_string()),
+            entries: HashMap::from([
+                (PathBuf::from("/repo/src"), GitEntryStatus::Untracked),
+                (PathBuf::from("/repo/README.md"), GitEntryStatus::Modified),
+            ]),
+            repository: PathBuf::from("/repo"),
+        };
+        let mut repositories = GitRepositories::default();
+        let tasks = Tasks::default();
+        update(
+            &mut app,
+            &mut repositories,
+            &tasks,
+            &PathBuf::from("/repo"),
+            Some(status),
+            &Theme::default(),
+        );
+
+        let status = GitStatus {
+            branch: Some("main".to_string()),
+            entries: HashMap::from([(PathBuf::from("/repo/src"), GitEntryStatus::Modified)]),
+            repository: PathBuf::from("/repo"),
+        };
+        update(
+            &mut app,
+            &mut repositories,
+            &tasks,
+            &PathBuf::from("/repo"),
+            Some(status),
+            &Theme::default(),
+        );
+
+        assert_eq!(
+            get_sign_ids(&app),
+            vec![vec![GIT_MODIFIED_SIGN_ID], Vec::<&str>::new()]
+        );
+        assert!(!get_sign_ids(&app)[0].contains(&GIT_UNTRACKED_SIGN_ID));
+        match app.contents.buffers.get(&42) {
+            Some(Buffer::Directory(it)) => assert_eq!(it.git.branch.as_deref(), Some("main")),
+            _ => panic!("expected directory buffer"),
+        }
+    }
+
+    #[test]
+    fn refresh_loads_status_for_open_parent_directories() {
+        let app = make_app("/repo", &[]);
+
+        let actions = refresh(
+            &app,
+            &mut GitRepositories::default(),
+            &Tasks::default(),
+            &[
+                PathBuf::from("/repo/a"),
+                PathBuf::from("/repo/b"),
+                PathBuf::from("/other/c"),
+            ],
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            &actions[0],
+            Action::Task(Task::LoadGitStatus(path, None)) if path == &PathBuf::from("/repo")
+        ));
+    }
+
+    #[test]
+    fn load_uses_cached_repository_and_skips_directories_outside_of_repositories() {
+        let mut app = make_app("/repo", &[]);
+        let mut repositories = GitRepositories::default();
+        let tasks = Tasks::default();
+
+        update(
+            &mut app,
+            &mut repositories,
+            &tasks,
+            &PathBuf::from("/other"),
+            None,
+            &Theme::default(),
+        );
+        update(
+            &mut app,
+            &mut repositories,
+            &tasks,
+            &PathBuf::from("/repo"),
+            Some(GitStatus {
+                repository: PathBuf::from("/repo"),
+                ..Default::default()
+            }),
+            &Theme::default(),
+        );
+
+        assert!(load(&mut repositories, &tasks, &PathBuf::from("/other")).is_empty());
+        assert!(matches!(
+            load(&mut repositories, &tasks, &PathBuf::from("/repo")).as_slice(),
+            [Action::Task(Task::LoadGitStatus(_, Some(repository)))]
+                if repository == &PathBuf::from("/repo")
+        ));
+
+        let actions = refresh(
+            &app,
+            &mut repositories,
+            &tasks,
+            &[PathBuf::from("/other/.git")],
+        );
+        assert!(actions.is_empty());
+        assert_eq!(
+            load(&mut repositories, &tasks, &PathBuf::from("/other")).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn refresh_waits_for_running_load_and_loads_again_after_it_finished() {
+        let mut app = make_app("/repo", &[]);
+        let mut repositories = GitRepositories::default();
+        let mut tasks = Tasks::default();
+        let task = Task::LoadGitStatus(PathBuf::from("/repo"), None);
+        tasks.running.insert(
+            task.to_string(),
+            CurrentTask {
+                external_id: task.to_string(),
+                id: 1,
+                kind: TaskKind::Other,
+                progress: None,
+                token: CancellationToken::new(),
+            },
+        );
+
+        for name in ["a", "b", "c"] {
+            let actions = refresh(
+                &app,
+                &mut repositories,
+                &tasks,
+                &[PathBuf::from("/repo").join(name)],
+            );
+            assert!(actions.is_empty());
+        }
+
+        tasks.running.clear();
+        let actions = update(
+            &mut app,
+            &mut repositories,
+            &tasks,
+            &PathBuf::from("/repo"),
+            Some(GitStatus::default()),
+            &Theme::default(),
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert!(repositories.outdated.is_empty());
+    }
+}
@@ -56,6 +56,13 @@ pub fn invoke_on_window_change_for_focused(app: &mut App, lua: &LuaConfiguration
         .get(&preview_id)
         .map(|b| b.buffer_type_for_lua());
 
+    let preview_image = app
+        .contents
+        .buffers
+        .get(&preview_id)
+        .and_then(|b| b.image_metadata())
+        .cloned();
+
     let window = match app.current_window_mut() {
         Ok(w) => w,
         Err(_) => return,
@@ -71,6 +78,7 @@ pub fn invoke_on_window_change_for_focused(app: &mut App, lua: &LuaConfiguration
             ],
             &mut [parent, current, preview],
             [parent_buffer_type, current_buffer_type, preview_buffer_type],
+            preview_image.as_ref(),
         );
     }
 }
@@ -85,6 +93,12 @@ pub fn on_window_create(lua: &LuaConfiguration, window: &mut Window, path: Optio
                 &mut [parent, current, preview],
             );
         }
+        Window::Diff(vp) => {
+            yeet_lua::invoke_on_window_create(lua, "diff", None, &mut [vp]);
+        }
+        Window::Edit(vp) => {
+            yeet_lua::invoke_on_window_create(lua, "edit", path, &mut [vp]);
+        }
         Window::Help(vp) => {
             yeet_lua::invoke_on_window_create(lua, "help", None, &mut [vp]);
         }
@@ -212,6 +226,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert_eq!(
@@ -263,6 +278,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("content")],
+            None,
         );
 
         assert_eq!(
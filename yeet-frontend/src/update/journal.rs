@@ -0,0 +1,1090 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    action::Action,
+    event::{LogSeverity, Message},
+    model::{
+        journal::{
+            Journal, JournalOperation, JournalTransaction, PendingKind, PendingStatus, PendingTask,
+            PendingTransaction,
+        },
+        junkyard::{FileEntry, FileEntryStatus, JunkYard},
+    },
+    task::{Existing, Task},
+    update::junkyard::{trash_paths, trash_to_junkyard},
+};
+
+pub fn record(journal: &mut Journal, actions: &[Action]) {
+    match journal.pending.iter_mut().find(|pending| pending.open) {
+        Some(pending) => pending.tasks.extend(to_pending_tasks(actions)),
+        None => begin(journal, PendingKind::Record, actions, Vec::new()),
+    }
+}
+
+pub fn hold(journal: &mut Journal) {
+    if !journal.pending.iter().any(|pending| pending.open) {
+        journal.pending.push(PendingTransaction {
+            deferred: Vec::new(),
+            kind: PendingKind::Record,
+            open: true,
+            tasks: Vec::new(),
+        });
+    }
+}
+
+pub fn release(journal: &mut Journal) -> Vec<Action> {
+    let Some(index) = journal.pending.iter().position(|pending| pending.open) else {
+        return Vec::new();
+    };
+
+    journal.pending[index].open = false;
+    if is_running(&journal.pending[index]) {
+        Vec::new()
+    } else {
+        finish(journal, index)
+    }
+}
+
+pub fn undo(journal: &mut Journal, junk: &mut JunkYard) -> Vec<Action> {
+    let transaction = match journal.undo.pop() {
+        Some(it) => it,
+        None => {
+            return log(
+                LogSeverity::Information,
+                "Already at oldest filesystem change",
+            )
+        }
+    };
+
+    // NOTE: transactions from a previous session are dropped if the filesystem changed since
+    if let Some(path) = transaction
+        .loaded
+        .then(|| find_stale_undo(&transaction))
+        .flatten()
+    {
+        return log(LogSeverity::Error, &stale("fsundo", &path));
+    }
+
+    match get_inverse_actions(&transaction, junk) {
+        Ok((actions, deferred)) if actions.is_empty() => {
+            let actions = to_restore_actions(deferred);
+            begin(
+                journal,
+                PendingKind::Undo(transaction),
+                &actions,
+                Vec::new(),
+            );
+            actions
+        }
+        Ok((actions, deferred)) => {
+            begin(journal, PendingKind::Undo(transaction), &actions, deferred);
+            actions
+        }
+        Err(err) => {
+            journal.undo.push(transaction);
+            log(LogSeverity::Error, &format!("fsundo failed: {}", err))
+        }
+    }
+}
+
+pub fn redo(journal: &mut Journal, junk: &mut JunkYard) -> Vec<Action> {
+    let transaction = match journal.redo.pop() {
+        Some(it) => it,
+        None => {
+            return log(
+                LogSeverity::Information,
+                "Already at newest filesystem change",
+            )
+        }
+    };
+
+    // NOTE: transactions from a previous session are dropped if the filesystem changed since
+    if let Some(path) = transaction
+        .loaded
+        .then(|| find_stale_redo(&transaction))
+        .flatten()
+    {
+        return log(LogSeverity::Error, &stale("fsredo", &path));
+    }
+
+    match get_actions(&transaction, junk) {
+        Ok(actions) => {
+            begin(journal, PendingKind::Redo, &actions, Vec::new());
+            actions
+        }
+        Err(err) => {
+            journal.redo.push(transaction);
+            log(LogSeverity::Error, &format!("fsredo failed: {}", err))
+        }
+    }
+}
+
+pub fn complete(journal: &mut Journal, identifier: &str, succeeded: bool) -> Vec<Action> {
+    let index = journal.pending.iter().position(|pending| {
+        pending
+            .tasks
+            .iter()
+            .any(|task| task.identifier == identifier && task.status == PendingStatus::Running)
+    });
+
+    let Some(index) = index else {
+        return Vec::new();
+    };
+
+    let pending = &mut journal.pending[index];
+    if let Some(task) = pending
+        .tasks
+        .iter_mut()
+        .find(|task| task.identifier == identifier && task.status == PendingStatus::Running)
+    {
+        task.status = if succeeded {
+            PendingStatus::Succeeded
+        } else {
+            PendingStatus::Failed
+        };
+    }
+
+    if pending.open || is_running(pending) {
+        return Vec::new();
+    }
+
+    finish(journal, index)
+}
+
+fn is_running(pending: &PendingTransaction) -> bool {
+    pending
+        .tasks
+        .iter()
+        .any(|task| task.status == PendingStatus::Running)
+}
+
+fn finish(journal: &mut Journal, index: usize) -> Vec<Action> {
+    let pending = journal.pending.remove(index);
+    let failed = pending
+        .tasks
+        .iter()
+        .filter(|task| task.status == PendingStatus::Failed)
+        .count();
+
+    match pending.kind {
+        PendingKind::Record | PendingKind::Redo => {
+            let operations: Vec<_> = pending
+                .tasks
+                .into_iter()
+                .filter(|task| task.status == PendingStatus::Succeeded)
+                .flat_map(|task| task.operations)
+                .collect();
+
+            if !operations.is_empty() {
+                if pending.kind == PendingKind::Record {
+                    journal.redo.clear();
+                }
+                journal.undo.push(JournalTransaction {
+                    loaded: false,
+                    operations,
+                });
+            }
+            Vec::new()
+        }
+        PendingKind::Undo(transaction) => {
+            if failed == 0 && !pending.deferred.is_empty() {
+                let actions = to_restore_actions(pending.deferred);
+                begin(
+                    journal,
+                    PendingKind::Undo(transaction),
+                    &actions,
+                    Vec::new(),
+                );
+                actions
+            } else if failed == 0 {
+                journal.redo.push(transaction);
+                Vec::new()
+            } else if failed == pending.tasks.len() {
+                journal.undo.push(transaction);
+                log(LogSeverity::Error, "fsundo failed")
+            } else {
+                log(
+                    LogSeverity::Error,
+                    "fsundo failed partially, the transaction can not be redone",
+                )
+            }
+        }
+    }
+}
+
+fn begin(journal: &mut Journal, kind: PendingKind, actions: &[Action], deferred: Vec<FileEntry>) {
+    let tasks = to_pending_tasks(actions);
+    if !tasks.is_empty() {
+        journal.pending.push(PendingTransaction {
+            deferred,
+            kind,
+            open: false,
+            tasks,
+        });
+    }
+}
+
+fn to_pending_tasks(actions: &[Action]) -> Vec<PendingTask> {
+    let mut tasks = Vec::new();
+    for action in actions {
+        let task = match action {
+            Action::Task(task) => task,
+            _ => continue,
+        };
+
+        let operations = match task {
+            Task::AddPath(path) => vec![JournalOperation::Create(path.clone())],
+            Task::CopyPath(source, target, existing) => with_replaced(
+                existing,
+                JournalOperation::Copy(source.clone(), target.clone()),
+            ),
+            Task::LinkPath(source, target, existing) => with_replaced(
+                existing,
+                JournalOperation::Link(source.clone(), target.clone()),
+            ),
+            Task::RenamePath(source, target, existing) => with_replaced(
+                existing,
+                JournalOperation::Rename(source.clone(), target.clone()),
+            ),
+            Task::RenamePaths(renames) => renames
+                .iter()
+                .map(|(source, target)| JournalOperation::Rename(source.clone(), target.clone()))
+                .collect(),
+            Task::RestorePath(entry, path, existing) => with_replaced(
+                existing,
+                JournalOperation::Restore(entry.clone(), path.clone()),
+            ),
+            Task::SymlinkPath(link, target, existing) => with_replaced(
+                existing,
+                JournalOperation::Symlink(link.clone(), target.clone()),
+            ),
+            Task::TrashPath(entry) => vec![JournalOperation::Trash(entry.clone())],
+            _ => continue,
+        };
+
+        tasks.push(PendingTask {
+            identifier: task.to_string(),
+            operations,
+            status: PendingStatus::Running,
+        });
+    }
+
+    tasks
+}
+
+fn with_replaced(existing: &Existing, operation: JournalOperation) -> Vec<JournalOperation> {
+    match existing {
+        Existing::Fail => vec![operation],
+        Existing::Overwrite(entry) => vec![JournalOperation::Replace(entry.clone()), operation],
+    }
+}
+
+fn get_inverse_actions(
+    transaction: &JournalTransaction,
+    junk: &mut JunkYard,
+) -> Result<(Vec<Action>, Vec<FileEntry>), String> {
+    let mut renames = Vec::new();
+    let mut replaced = Vec::new();
+    let mut restores = Vec::new();
+    let mut trashes = Vec::new();
+    for operation in transaction.operations.iter().rev() {
+        match operation {
+            JournalOperation::Copy(_, path)
+            | JournalOperation::Create(path)
+            | JournalOperation::Link(_, path)
+            | JournalOperation::Symlink(_, path) => trashes.push(normalize(path)),
+            JournalOperation::Rename(source, target) => {
+                renames.push((target.clone(), source.clone()))
+            }
+            JournalOperation::Replace(entry) => replaced.push(get_ready_entry(junk, entry)?),
+            JournalOperation::Restore(_, path) => trashes.push(normalize(path)),
+            JournalOperation::Trash(entry) => {
+                let entry = get_ready_entry(junk, entry)?;
+                let target = entry.target.clone();
+                restores.push(Action::Task(Task::RestorePath(
+                    entry,
+                    target,
+                    Existing::Fail,
+                )));
+            }
+        }
+    }
+
+    let mut actions = Vec::new();
+    if !renames.is_empty() {
+        actions.push(Action::Task(Task::RenamePaths(renames)));
+    }
+    actions.extend(restores);
+    if !trashes.is_empty() {
+        actions.extend(trash_paths(junk, trashes));
+    }
+
+    Ok((actions, replaced))
+}
+
+fn to_restore_actions(entries: Vec<FileEntry>) -> Vec<Action> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let target = entry.target.clone();
+            Action::Task(Task::RestorePath(entry, target, Existing::Fail))
+        })
+        .collect()
+}
+
+fn get_actions(
+    transaction: &JournalTransaction,
+    junk: &mut JunkYard,
+) -> Result<Vec<Action>, String> {
+    let mut actions = Vec::new();
+    let mut replaced = get_replacements(transaction, junk, &mut actions);
+    let mut existing = |target: &PathBuf| match replaced.remove(target) {
+        Some(entry) => Existing::Overwrite(entry),
+        None => Existing::Fail,
+    };
+
+    let mut renames = Vec::new();
+    let mut trashes = Vec::new();
+    for operation in transaction.operations.iter() {
+        match operation {
+            JournalOperation::Copy(source, target) => actions.push(Action::Task(Task::CopyPath(
+                source.clone(),
+                target.clone(),
+                existing(target),
+            ))),
+            JournalOperation::Create(path) => {
+                actions.push(Action::Task(Task::AddPath(path.clone())))
+            }
+            JournalOperation::Link(source, target) => actions.push(Action::Task(Task::LinkPath(
+                source.clone(),
+                target.clone(),
+                existing(target),
+            ))),
+            JournalOperation::Rename(source, target) => match existing(target) {
+                Existing::Fail => renames.push((source.clone(), target.clone())),
+                overwrite => actions.push(Action::Task(Task::RenamePath(
+                    source.clone(),
+                    target.clone(),
+                    overwrite,
+                ))),
+            },
+            JournalOperation::Replace(_) => {}
+            JournalOperation::Restore(entry, path) => {
+                let entry = get_ready_entry(junk, entry)?;
+                actions.push(Action::Task(Task::RestorePath(
+                    entry,
+                    path.clone(),
+                    existing(path),
+                )));
+            }
+            JournalOperation::Symlink(link, target) => actions.push(Action::Task(
+                Task::SymlinkPath(link.clone(), target.clone(), existing(target)),
+            )),
+            JournalOperation::Trash(entry) => trashes.push(entry.target.clone()),
+        }
+    }
+
+    if !renames.is_empty() {
+        actions.push(Action::Task(Task::RenamePaths(renames)));
+    }
+    if !trashes.is_empty() {
+        actions.extend(trash_paths(junk, trashes));
+    }
+
+    Ok(actions)
+}
+
+fn get_replacements(
+    transaction: &JournalTransaction,
+    junk: &mut JunkYard,
+    actions: &mut Vec<Action>,
+) -> HashMap<PathBuf, FileEntry> {
+    let targets: Vec<_> = transaction
+        .operations
+        .iter()
+        .filter_map(|operation| match operation {
+            JournalOperation::Replace(entry) => Some(entry.target.clone()),
+            _ => None,
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return HashMap::new();
+    }
+
+    let (transaction, obsolete) = trash_to_junkyard(junk, targets);
+    if let Some(obsolete) = obsolete {
+        for entry in obsolete.entries {
+            actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+        }
+    }
+
+    transaction
+        .entries
+        .into_iter()
+        .map(|entry| (entry.target.clone(), entry))
+        .collect()
+}
+
+fn get_ready_entry(junk: &JunkYard, entry: &FileEntry) -> Result<FileEntry, String> {
+    let found = junk
+        .trashed
+        .iter()
+        .chain(junk.yanked.iter())
+        .flat_map(|transaction| transaction.entries.iter())
+        .find(|it| it.id == entry.id);
+
+    match found {
+        Some(it) if it.status == FileEntryStatus::Ready => Ok(it.clone()),
+        Some(_) => Err(format!(
+            "junk yard entry for {:?} is not ready",
+            entry.target
+        )),
+        None => Err(format!(
+            "junk yard entry for {:?} is no longer available",
+            entry.target
+        )),
+    }
+}
+
+fn find_stale_undo(transaction: &JournalTransaction) -> Option<PathBuf> {
+    let renamed: HashSet<_> = transaction
+        .operations
+        .iter()
+        .filter_map(|operation| match operation {
+            JournalOperation::Rename(source, _) => Some(source),
+            _ => None,
+        })
+        .collect();
+
+    transaction
+        .operations
+        .iter()
+        .find_map(|operation| match operation {
+            JournalOperation::Copy(_, path)
+            | JournalOperation::Create(path)
+            | JournalOperation::Link(_, path)
+            | JournalOperation::Restore(_, path)
+            | JournalOperation::Symlink(_, path)
+                if !exists(path) =>
+            {
+                Some(path)
+            }
+            JournalOperation::Rename(_, path) if !renamed.contains(path) && !exists(path) => {
+                Some(path)
+            }
+            JournalOperation::Trash(entry) if exists(&entry.target) => Some(&entry.target),
+            _ => None,
+        })
+        .cloned()
+}
+
+fn find_stale_redo(transaction: &JournalTransaction) -> Option<PathBuf> {
+    let renamed: HashSet<_> = transaction
+        .operations
+        .iter()
+        .filter_map(|operation| match operation {
+            JournalOperation::Rename(_, target) => Some(target),
+            _ => None,
+        })
+        .collect();
+
+    transaction
+        .operations
+        .iter()
+        .find_map(|operation| match operation {
+            JournalOperation::Copy(path, _) | JournalOperation::Link(path, _) if !exists(path) => {
+                Some(path)
+            }
+            JournalOperation::Rename(path, _) if !renamed.contains(path) && !exists(path) => {
+                Some(path)
+            }
+            JournalOperation::Create(path) if exists(path) => Some(path),
+            JournalOperation::Trash(entry) if !exists(&entry.target) => Some(&entry.target),
+            _ => None,
+        })
+        .cloned()
+}
+
+fn stale(command: &str, path: &Path) -> String {
+    format!(
+        "{} failed: {} changed since the journal was saved, the transaction was dropped",
+        command,
+        path.display()
+    )
+}
+
+fn exists(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.components().collect()
+}
+
+fn log(severity: LogSeverity, content: &str) -> Vec<Action> {
+    vec![Action::EmitMessages(vec![Message::Log(
+        severity,
+        content.to_string(),
+    )])]
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use crate::{
+        action::Action,
+        event::{LogSeverity, Message},
+        model::{
+            journal::{Journal, JournalOperation, JournalTransaction},
+            junkyard::{FileEntryStatus, JunkYard},
+        },
+        task::{Existing, Task},
+        update::junkyard::trash_paths,
+    };
+
+    use super::{complete, hold, record, redo, release, undo};
+
+    fn trash(junk: &mut JunkYard, path: &str, status: FileEntryStatus) -> Vec<Action> {
+        let actions = trash_paths(junk, vec![PathBuf::from(path)]);
+        for transaction in junk.trashed.iter_mut() {
+            for entry in transaction.entries.iter_mut() {
+                entry.status = status.clone();
+            }
+        }
+        actions
+    }
+
+    fn finish(journal: &mut Journal, actions: &[Action], succeeded: bool) -> Vec<Action> {
+        tasks(actions)
+            .into_iter()
+            .flat_map(|task| complete(journal, &task.to_string(), succeeded))
+            .collect()
+    }
+
+    fn tasks(actions: &[Action]) -> Vec<&Task> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Task(task) => Some(task),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_log(actions: &[Action], severity: LogSeverity) -> bool {
+        actions.iter().any(|action| {
+            matches!(action, Action::EmitMessages(messages) if messages
+                .iter()
+                .any(|m| matches!(m, Message::Log(s, _) if std::mem::discriminant(s) == std::mem::discriminant(&severity))))
+        })
+    }
+
+    #[test]
+    fn record_groups_tasks_into_one_transaction() {
+        let mut journal = Journal::default();
+        let actions = vec![
+            Action::Task(Task::AddPath(PathBuf::from("/a"))),
+            Action::Task(Task::DeletePath(PathBuf::from("/b"))),
+            Action::Task(Task::RenamePaths(vec![
+                (PathBuf::from("/c"), PathBuf::from("/d")),
+                (PathBuf::from("/e"), PathBuf::from("/f")),
+            ])),
+        ];
+
+        record(&mut journal, &actions);
+        assert!(journal.undo.is_empty());
+
+        finish(&mut journal, &actions, true);
+
+        assert_eq!(journal.undo.len(), 1);
+        assert_eq!(
+            journal.undo[0].operations,
+            vec![
+                JournalOperation::Create(PathBuf::from("/a")),
+                JournalOperation::Rename(PathBuf::from("/c"), PathBuf::from("/d")),
+                JournalOperation::Rename(PathBuf::from("/e"), PathBuf::from("/f")),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_ignores_actions_without_filesystem_changes() {
+        let mut journal = Journal::default();
+        record(
+            &mut journal,
+            &[Action::Task(Task::DeletePath(PathBuf::from("/b")))],
+        );
+
+        assert!(journal.undo.is_empty());
+        assert!(journal.pending.is_empty());
+    }
+
+    #[test]
+    fn record_clears_redo() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![Action::Task(Task::AddPath(PathBuf::from("/a")))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        let actions = undo(&mut journal, &mut junk);
+        finish(&mut journal, &actions, true);
+        assert_eq!(journal.redo.len(), 1);
+
+        let actions = vec![Action::Task(Task::AddPath(PathBuf::from("/b")))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn undo_reverses_renames_in_reverse_order() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![Action::Task(Task::RenamePaths(vec![
+            (PathBuf::from("/a"), PathBuf::from("/tmp")),
+            (PathBuf::from("/b"), PathBuf::from("/a")),
+            (PathBuf::from("/tmp"), PathBuf::from("/b")),
+        ]))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::RenamePaths(vec![
+                (PathBuf::from("/b"), PathBuf::from("/tmp")),
+                (PathBuf::from("/a"), PathBuf::from("/b")),
+                (PathBuf::from("/tmp"), PathBuf::from("/a")),
+            ])]
+        );
+    }
+
+    #[test]
+    fn undo_trashes_created_and_copied_paths() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![
+            Action::Task(Task::AddPath(PathBuf::from("/dir/new/"))),
+            Action::Task(Task::CopyPath(
+                PathBuf::from("/src"),
+                PathBuf::from("/dir/src"),
+                Existing::Fail,
+            )),
+        ];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+
+        let targets: Vec<_> = tasks(&actions)
+            .into_iter()
+            .filter_map(|task| match task {
+                Task::TrashPath(entry) => Some(entry.target.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            vec![PathBuf::from("/dir/src"), PathBuf::from("/dir/new")]
+        );
+    }
+
+    #[test]
+    fn undo_restores_trashed_entries_to_original_path() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = trash(&mut junk, "/dir/file", FileEntryStatus::Ready);
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert!(matches!(
+            tasks(&actions).as_slice(),
+            [Task::RestorePath(entry, path, Existing::Fail)]
+                if entry.target == Path::new("/dir/file") && path == Path::new("/dir/file")
+        ));
+        assert!(journal.undo.is_empty());
+        assert!(journal.redo.is_empty());
+
+        finish(&mut journal, &actions, true);
+        assert_eq!(journal.redo.len(), 1);
+    }
+
+    #[test]
+    fn undo_restores_replaced_target_after_removing_copy() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        trash(&mut junk, "/dir/file", FileEntryStatus::Ready);
+        let replaced = junk.trashed[0].entries[0].clone();
+        let actions = vec![Action::Task(Task::CopyPath(
+            PathBuf::from("/src/file"),
+            PathBuf::from("/dir/file"),
+            Existing::Overwrite(replaced.clone()),
+        ))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        assert_eq!(
+            journal.undo[0].operations,
+            vec![
+                JournalOperation::Replace(replaced.clone()),
+                JournalOperation::Copy(PathBuf::from("/src/file"), PathBuf::from("/dir/file")),
+            ]
+        );
+
+        let actions = undo(&mut journal, &mut junk);
+        assert!(matches!(
+            tasks(&actions).as_slice(),
+            [Task::TrashPath(entry)] if entry.target == Path::new("/dir/file")
+        ));
+
+        let actions = finish(&mut journal, &actions, true);
+        assert_eq!(
+            tasks(&actions),
+            vec![&Task::RestorePath(
+                replaced,
+                PathBuf::from("/dir/file"),
+                Existing::Fail
+            )]
+        );
+        assert!(journal.redo.is_empty());
+
+        finish(&mut journal, &actions, true);
+        assert!(journal.pending.is_empty());
+        assert_eq!(journal.redo.len(), 1);
+    }
+
+    #[test]
+    fn redo_replaces_restored_target_again() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        trash(&mut junk, "/dir/file", FileEntryStatus::Ready);
+        let replaced = junk.trashed[0].entries[0].clone();
+        let actions = vec![Action::Task(Task::RenamePath(
+            PathBuf::from("/src/file"),
+            PathBuf::from("/dir/file"),
+            Existing::Overwrite(replaced),
+        ))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        let actions = undo(&mut journal, &mut junk);
+        let actions = finish(&mut journal, &actions, true);
+        finish(&mut journal, &actions, true);
+
+        let actions = redo(&mut journal, &mut junk);
+
+        assert!(matches!(
+            tasks(&actions).as_slice(),
+            [Task::RenamePath(source, target, Existing::Overwrite(entry))]
+                if source == Path::new("/src/file")
+                    && target == Path::new("/dir/file")
+                    && entry.target == Path::new("/dir/file")
+        ));
+    }
+
+    #[test]
+    fn undo_fails_when_junk_entry_was_evicted() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = trash(&mut junk, "/dir/file", FileEntryStatus::Ready);
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        junk.trashed.clear();
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert!(is_log(&actions, LogSeverity::Error));
+        assert!(tasks(&actions).is_empty());
+        assert_eq!(journal.undo.len(), 1);
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn undo_fails_when_junk_entry_is_not_ready() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = trash(&mut junk, "/dir/file", FileEntryStatus::Processing);
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert!(is_log(&actions, LogSeverity::Error));
+        assert_eq!(journal.undo.len(), 1);
+    }
+
+    #[test]
+    fn undo_drops_loaded_transaction_that_no_longer_matches_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut junk = JunkYard::default();
+        let mut journal = Journal {
+            undo: vec![JournalTransaction {
+                loaded: true,
+                operations: vec![JournalOperation::Create(dir.path().join("missing"))],
+            }],
+            ..Default::default()
+        };
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert!(is_log(&actions, LogSeverity::Error));
+        assert!(tasks(&actions).is_empty());
+        assert!(journal.undo.is_empty());
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn undo_replays_loaded_transaction_that_matches_disk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("created");
+        std::fs::write(&path, "").unwrap();
+        let mut junk = JunkYard::default();
+        let mut journal = Journal {
+            undo: vec![JournalTransaction {
+                loaded: true,
+                operations: vec![JournalOperation::Create(path.clone())],
+            }],
+            ..Default::default()
+        };
+
+        let actions = undo(&mut journal, &mut junk);
+
+        assert!(matches!(
+            tasks(&actions).as_slice(),
+            [Task::TrashPath(entry)] if entry.target == path
+        ));
+    }
+
+    #[test]
+    fn redo_drops_loaded_transaction_with_missing_source() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut junk = JunkYard::default();
+        let mut journal = Journal {
+            redo: vec![JournalTransaction {
+                loaded: true,
+                operations: vec![JournalOperation::Copy(
+                    dir.path().join("missing"),
+                    dir.path().join("copy"),
+                )],
+            }],
+            ..Default::default()
+        };
+
+        let actions = redo(&mut journal, &mut junk);
+
+        assert!(is_log(&actions, LogSeverity::Error));
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_on_empty_journal_log_information() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+
+        assert!(is_log(
+            &undo(&mut journal, &mut junk),
+            LogSeverity::Information
+        ));
+        assert!(is_log(
+            &redo(&mut journal, &mut junk),
+            LogSeverity::Information
+        ));
+    }
+
+    #[test]
+    fn redo_trashes_again_with_new_junk_entry() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = trash(&mut junk, "/dir/file", FileEntryStatus::Ready);
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        let actions = undo(&mut journal, &mut junk);
+        finish(&mut journal, &actions, true);
+
+        let actions = redo(&mut journal, &mut junk);
+        finish(&mut journal, &actions, true);
+
+        assert!(matches!(
+            tasks(&actions).as_slice(),
+            [Task::TrashPath(entry)] if entry.target == Path::new("/dir/file")
+        ));
+        assert_eq!(junk.trashed.len(), 2);
+        assert_eq!(journal.undo.len(), 1);
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn redo_repeats_copy_and_rename() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let original = vec![
+            Action::Task(Task::CopyPath(
+                PathBuf::from("/a"),
+                PathBuf::from("/b"),
+                Existing::Fail,
+            )),
+            Action::Task(Task::RenamePath(
+                PathBuf::from("/c"),
+                PathBuf::from("/d"),
+                Existing::Fail,
+            )),
+        ];
+        record(&mut journal, &original);
+        finish(&mut journal, &original, true);
+        let actions = undo(&mut journal, &mut junk);
+        finish(&mut journal, &actions, true);
+
+        let actions = redo(&mut journal, &mut junk);
+
+        assert_eq!(
+            tasks(&actions),
+            vec![
+                &Task::CopyPath(PathBuf::from("/a"), PathBuf::from("/b"), Existing::Fail),
+                &Task::RenamePaths(vec![(PathBuf::from("/c"), PathBuf::from("/d"))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn complete_records_only_succeeded_tasks() {
+        let mut journal = Journal::default();
+        let actions = vec![
+            Action::Task(Task::AddPath(PathBuf::from("/a"))),
+            Action::Task(Task::CopyPath(
+                PathBuf::from("/b"),
+                PathBuf::from("/c"),
+                Existing::Fail,
+            )),
+        ];
+        record(&mut journal, &actions);
+
+        complete(&mut journal, "AddPath(\"/a\")", true);
+        assert!(journal.undo.is_empty());
+        complete(&mut journal, "CopyPath(\"/b\", \"/c\")", false);
+
+        assert!(journal.pending.is_empty());
+        assert_eq!(
+            journal.undo[0].operations,
+            vec![JournalOperation::Create(PathBuf::from("/a"))]
+        );
+    }
+
+    #[test]
+    fn complete_skips_transaction_when_all_tasks_failed() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![Action::Task(Task::AddPath(PathBuf::from("/a")))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+        let actions = undo(&mut journal, &mut junk);
+        finish(&mut journal, &actions, true);
+
+        let actions = vec![Action::Task(Task::RenamePath(
+            PathBuf::from("/b"),
+            PathBuf::from("/c"),
+            Existing::Fail,
+        ))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, false);
+
+        assert!(journal.undo.is_empty());
+        assert_eq!(journal.redo.len(), 1);
+    }
+
+    #[test]
+    fn complete_keeps_undo_when_all_tasks_failed() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![Action::Task(Task::AddPath(PathBuf::from("/a")))];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+        let logs = finish(&mut journal, &actions, false);
+
+        assert!(is_log(&logs, LogSeverity::Error));
+        assert_eq!(journal.undo.len(), 1);
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn complete_drops_partially_failed_undo() {
+        let mut journal = Journal::default();
+        let mut junk = JunkYard::default();
+        let actions = vec![
+            Action::Task(Task::AddPath(PathBuf::from("/a"))),
+            Action::Task(Task::RenamePath(
+                PathBuf::from("/b"),
+                PathBuf::from("/c"),
+                Existing::Fail,
+            )),
+        ];
+        record(&mut journal, &actions);
+        finish(&mut journal, &actions, true);
+
+        let actions = undo(&mut journal, &mut junk);
+        let tasks = tasks(&actions);
+        complete(&mut journal, &tasks[0].to_string(), true);
+        let logs = complete(&mut journal, &tasks[1].to_string(), false);
+
+        assert!(is_log(&logs, LogSeverity::Error));
+        assert!(journal.undo.is_empty());
+        assert!(journal.redo.is_empty());
+    }
+
+    #[test]
+    fn held_transaction_groups_prompt_answers_until_release() {
+        let mut journal = Journal::default();
+        hold(&mut journal);
+
+        let first = vec![Action::Task(Task::AddPath(PathBuf::from("/a")))];
+        record(&mut journal, &first);
+        finish(&mut journal, &first, true);
+
+        let second = vec![Action::Task(Task::CopyPath(
+            PathBuf::from("/b"),
+            PathBuf::from("/c"),
+            Existing::Fail,
+        ))];
+        record(&mut journal, &second);
+        finish(&mut journal, &second, true);
+        assert!(journal.undo.is_empty());
+
+        release(&mut journal);
+
+        assert!(journal.pending.is_empty());
+        assert_eq!(
+            journal.undo,
+            vec![JournalTransaction {
+                loaded: false,
+                operations: vec![
+                    JournalOperation::Create(PathBuf::from("/a")),
+                    JournalOperation::Copy(PathBuf::from("/b"), PathBuf::from("/c")),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn release_waits_for_running_tasks() {
+        let mut journal = Journal::default();
+        hold(&mut journal);
+
+        let actions = vec![Action::Task(Task::AddPath(PathBuf::from("/a")))];
+        record(&mut journal, &actions);
+        release(&mut journal);
+        assert!(journal.undo.is_empty());
+
+        finish(&mut journal, &actions, true);
+
+        assert_eq!(journal.undo.len(), 1);
+    }
+}
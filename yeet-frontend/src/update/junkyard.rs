@@ -1,13 +1,17 @@
 use std::{
     cmp::Reverse,
+    fs,
     path::{Path, PathBuf},
     time,
 };
 
+use yeet_keymap::message::LinkKind;
+
 use crate::{
     action::Action,
     error::AppError,
     model::{
+        conflict::{Transfer, TransferSource},
         junkyard::{FileEntry, FileEntryStatus, FileEntryType, FileTransaction, JunkYard},
         App, Buffer,
     },
@@ -135,36 +139,93 @@ fn decompose_compression_path(path: &Path) -> Option<(String, String, PathBuf)>
     }
 }
 
-pub fn paste(app: &mut App, junk: &JunkYard, entry_id: &char) -> Result<Vec<Action>, AppError> {
-    let window = app.current_window()?;
-    let (_, current_id, _) = match app::get_focused_directory_buffer_ids(window) {
-        Some(ids) => ids,
+pub fn paste(app: &mut App, junk: &JunkYard, entry_id: &char) -> Result<Vec<Transfer>, AppError> {
+    let directory = match get_current_directory(app)? {
+        Some(it) => it,
         None => return Ok(Vec::new()),
     };
-    let buffer = match app.contents.buffers.get(&current_id) {
-        Some(Buffer::Directory(it)) => it,
-        _ => {
-            return Err(AppError::InvalidState(format!(
-                "paste called on non-directory buffer with buffer_id {}",
-                current_id
-            )))
+
+    if let Some(transaction) = get_junkyard_transaction(junk, entry_id) {
+        let mut transfers = Vec::new();
+        for entry in transaction.entries.iter() {
+            if let Some(file_name) = entry.target.file_name() {
+                transfers.push(Transfer {
+                    source: TransferSource::Restore(entry.clone()),
+                    target: directory.join(file_name),
+                });
+            }
         }
+        Ok(transfers)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+pub fn paste_link(
+    app: &mut App,
+    junk: &JunkYard,
+    entry_id: &char,
+    kind: &LinkKind,
+) -> Result<Vec<Result<Transfer, String>>, AppError> {
+    let directory = match get_current_directory(app)? {
+        Some(it) => it,
+        None => return Ok(Vec::new()),
     };
 
     if let Some(transaction) = get_junkyard_transaction(junk, entry_id) {
-        let mut actions = Vec::new();
+        let mut transfers = Vec::new();
         for entry in transaction.entries.iter() {
-            actions.push(Action::Task(Task::RestorePath(
-                entry.clone(),
-                buffer.path.clone(),
-            )));
+            if let Some(file_name) = entry.target.file_name() {
+                transfers.push(
+                    get_link(&entry.target, &directory, kind).map(|link| Transfer {
+                        source: TransferSource::Symlink(link),
+                        target: directory.join(file_name),
+                    }),
+                );
+            }
         }
-        Ok(actions)
+        Ok(transfers)
     } else {
         Ok(Vec::new())
     }
 }
 
+fn get_current_directory(app: &App) -> Result<Option<PathBuf>, AppError> {
+    let window = app.current_window()?;
+    let (_, current_id, _) = match app::get_focused_directory_buffer_ids(window) {
+        Some(ids) => ids,
+        None => return Ok(None),
+    };
+
+    match app.contents.buffers.get(&current_id) {
+        Some(Buffer::Directory(it)) => Ok(Some(it.path.clone())),
+        _ => Err(AppError::InvalidState(format!(
+            "paste called on non-directory buffer with buffer_id {}",
+            current_id
+        ))),
+    }
+}
+
+fn get_link(target: &Path, directory: &Path, kind: &LinkKind) -> Result<PathBuf, String> {
+    if fs::symlink_metadata(target).is_err() {
+        return Err(format!(
+            "Paste link failed: {} does not exist anymore",
+            target.display()
+        ));
+    }
+
+    match kind {
+        LinkKind::Absolute => Ok(target.to_path_buf()),
+        LinkKind::Relative => pathdiff::diff_paths(target, directory).ok_or_else(|| {
+            format!(
+                "Paste link failed: no relative path from {} to {}",
+                directory.display(),
+                target.display()
+            )
+        }),
+    }
+}
+
 pub fn yank(app: &mut App, junk: &mut JunkYard, repeat: &usize) -> Result<Vec<Action>, AppError> {
     let (window, contents) = app.current_window_and_contents_mut()?;
     let (current_vp, current_buffer) = app::get_focused_current_mut(window, contents)?;
@@ -193,20 +254,28 @@ pub fn yank(app: &mut App, junk: &mut JunkYard, repeat: &usize) -> Result<Vec<Ac
             }
         }
 
-        let mut actions = Vec::new();
-        let (transaction, obsolete) = yank_path(junk, paths);
-        for entry in transaction.entries {
-            actions.push(Action::Task(Task::YankPath(entry)));
-        }
+        Ok(yank_paths(junk, paths))
+    }
+}
 
-        if let Some(obsolete) = obsolete {
-            for entry in obsolete.entries {
-                actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
-            }
-        }
+pub fn yank_paths(junk: &mut JunkYard, paths: Vec<PathBuf>) -> Vec<Action> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+
+    let mut actions = Vec::new();
+    let (transaction, obsolete) = yank_path(junk, paths);
+    for entry in transaction.entries {
+        actions.push(Action::Task(Task::YankPath(entry)));
+    }
 
-        Ok(actions)
+    if let Some(obsolete) = obsolete {
+        for entry in obsolete.entries {
+            actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+        }
     }
+
+    actions
 }
 
 fn yank_path(
@@ -236,29 +305,34 @@ fn generate_file_transaction(paths: Vec<PathBuf>, junk: &JunkYard) -> FileTransa
     }
 }
 
-pub fn get_junkyard_transaction<'a>(
-    junkyard: &'a JunkYard,
-    junk: &char,
-) -> Option<&'a FileTransaction> {
-    let transaction = match junk {
+const JUNK_REGISTERS: [char; 11] = ['"', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+pub fn get_junkyard_transactions(junkyard: &JunkYard) -> Vec<(char, &FileTransaction)> {
+    JUNK_REGISTERS
+        .iter()
+        .filter_map(|junk| Some((*junk, get_transaction(junkyard, junk)?)))
+        .collect()
+}
+
+fn get_transaction<'a>(junkyard: &'a JunkYard, junk: &char) -> Option<&'a FileTransaction> {
+    match junk {
         '"' => match junkyard.current {
             FileEntryType::Trash => junkyard.trashed.first(),
             FileEntryType::Yank => junkyard.yanked.as_ref(),
             FileEntryType::_Custom(_) => None,
         },
         '0' => junkyard.yanked.as_ref(),
-        '1' => junkyard.trashed.first(),
-        '2' => junkyard.trashed.get(1),
-        '3' => junkyard.trashed.get(2),
-        '4' => junkyard.trashed.get(3),
-        '5' => junkyard.trashed.get(4),
-        '6' => junkyard.trashed.get(5),
-        '7' => junkyard.trashed.get(6),
-        '8' => junkyard.trashed.get(7),
-        '9' => junkyard.trashed.get(8),
+        '1'..='9' => junkyard.trashed.get(junk.to_digit(10)? as usize - 1),
         // TODO: add custom junk handling
         _ => None,
-    };
+    }
+}
+
+pub fn get_junkyard_transaction<'a>(
+    junkyard: &'a JunkYard,
+    junk: &char,
+) -> Option<&'a FileTransaction> {
+    let transaction = get_transaction(junkyard, junk);
 
     let is_ready = transaction.as_ref().is_some_and(|trnsctn| {
         trnsctn
@@ -301,6 +375,24 @@ pub fn trash_to_junkyard(
     (transaction, obsolete)
 }
 
+pub fn trash_paths(junkyard: &mut JunkYard, paths: Vec<PathBuf>) -> Vec<Action> {
+    let (transaction, obsolete) = trash_to_junkyard(junkyard, paths);
+
+    let mut actions: Vec<_> = transaction
+        .entries
+        .into_iter()
+        .map(|entry| Action::Task(Task::TrashPath(entry)))
+        .collect();
+
+    if let Some(obsolete) = obsolete {
+        for entry in obsolete.entries {
+            actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+        }
+    }
+
+    actions
+}
+
 mod test {
     #[test]
     fn junk_add_or_update() {
@@ -442,4 +534,72 @@ mod test {
         assert!(!name.contains("\\") && !name.contains(":"));
         assert_eq!(path, dec_path);
     }
+
+    #[test]
+    fn get_link_resolves_absolute_and_relative_targets() {
+        use std::fs;
+
+        use yeet_keymap::message::LinkKind;
+
+        use super::get_link;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("source/file.txt");
+        let directory = dir.path().join("other");
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, "").unwrap();
+
+        assert_eq!(
+            get_link(&target, &directory, &LinkKind::Absolute),
+            Ok(target.clone())
+        );
+        assert_eq!(
+            get_link(&target, &directory, &LinkKind::Relative),
+            Ok(std::path::PathBuf::from("../source/file.txt"))
+        );
+        assert!(get_link(&directory, &directory, &LinkKind::Absolute).is_err());
+    }
+
+    #[test]
+    fn get_junkyard_transactions_includes_processing_entries() {
+        use std::path::PathBuf;
+
+        use crate::model::junkyard::{
+            FileEntry, FileEntryStatus, FileEntryType, FileTransaction, JunkYard,
+        };
+
+        use super::{get_junkyard_transaction, get_junkyard_transactions};
+
+        let transaction = |id: &str, status| FileTransaction {
+            id: id.to_string(),
+            entries: vec![FileEntry {
+                id: id.to_string(),
+                cache: PathBuf::from("/cache"),
+                status,
+                target: PathBuf::from("/target"),
+            }],
+        };
+
+        let junk = JunkYard {
+            current: FileEntryType::Trash,
+            path: PathBuf::from("/junk"),
+            trashed: vec![
+                transaction("new", FileEntryStatus::Processing),
+                transaction("old", FileEntryStatus::Ready),
+            ],
+            yanked: Some(transaction("yank", FileEntryStatus::Ready)),
+        };
+
+        let registers: Vec<_> = get_junkyard_transactions(&junk)
+            .into_iter()
+            .map(|(register, transaction)| (register, transaction.id.as_str()))
+            .collect();
+
+        assert_eq!(
+            registers,
+            vec![('"', "new"), ('0', "yank"), ('1', "new"), ('2', "old")]
+        );
+        assert!(get_junkyard_transaction(&junk, &'1').is_none());
+        assert!(get_junkyard_transaction(&junk, &'2').is_some());
+    }
 }
@@ -1,8 +1,8 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, slice};
 
 use yeet_buffer::{
-    message::BufferMessage,
-    model::{BufferLine, Mode},
+    message::{BufferMessage, TextModification},
+    model::{BufferLine, CommandMode, Mode},
 };
 use yeet_keymap::message::{KeySequence, KeymapMessage, PrintContent};
 use yeet_lua::LuaConfiguration;
@@ -15,15 +15,19 @@ use crate::{
     terminal::TerminalWrapper,
 };
 
+pub mod api;
 pub mod app;
 mod buffers;
 mod command;
 pub mod commandline;
+mod conflict;
 mod cursor;
 mod enumeration;
 mod focus;
+mod git;
 pub mod history;
 pub mod hook;
+mod journal;
 pub mod junkyard;
 mod mark;
 mod mode;
@@ -33,7 +37,9 @@ mod open;
 mod path;
 mod preview;
 mod qfix;
+mod range;
 mod register;
+mod rename;
 mod save;
 mod search;
 mod selection;
@@ -42,6 +48,7 @@ mod sign;
 mod tab;
 mod task;
 mod viewport;
+mod visual;
 pub mod window;
 
 const SORT: fn(&BufferLine, &BufferLine) -> Ordering = |a, b| {
@@ -151,6 +158,14 @@ fn update_with_message(
                 }
             }
         }
+        Message::GitStatusLoaded(path, status) => git::update(
+            app,
+            &mut state.git,
+            &state.tasks,
+            &path,
+            status,
+            &settings.theme,
+        ),
         Message::Log(severity, msg) => {
             let content = match severity {
                 LogSeverity::Error => PrintContent::Error(msg.to_string()),
@@ -164,12 +179,14 @@ fn update_with_message(
                 &settings.theme,
             )
         }
-        Message::FdResult(paths) | Message::RgResult(paths) => qfix::add(
-            &mut state.qfix,
-            app.contents.buffers.values_mut().collect(),
-            paths,
-            &settings.theme,
-        ),
+        Message::FdResult(paths) | Message::QuickFixAdd(paths) | Message::RgResult(paths) => {
+            qfix::add(
+                &mut state.qfix,
+                app.contents.buffers.values_mut().collect(),
+                paths,
+                &settings.theme,
+            )
+        }
         Message::Keymap(msg) => update_with_keymap_message(app, state, settings, lua, &msg),
         Message::QuickFixChanged => {
             command::qfix::window::refresh_quickfix_buffer(
@@ -187,7 +204,7 @@ fn update_with_message(
                     .push(crate::model::PendingPathEvent::Removed(path));
                 Vec::new()
             } else {
-                match path::remove(
+                let mut actions = match path::remove(
                     path::PathRemoveState {
                         history: &mut state.history,
                         marks: &mut state.marks,
@@ -204,7 +221,14 @@ fn update_with_message(
                         tracing::error!("PathsRemoved failed: {}", err);
                         Vec::new()
                     }
-                }
+                };
+                actions.extend(git::refresh(
+                    app,
+                    &mut state.git,
+                    &state.tasks,
+                    slice::from_ref(&path),
+                ));
+                actions
             }
         }
         Message::PathsAdded(paths) => {
@@ -232,6 +256,7 @@ fn update_with_message(
                         Vec::new()
                     }
                 };
+                actions.extend(git::refresh(app, &mut state.git, &state.tasks, &paths));
                 actions.extend(junkyard::cleanup_if_path_in_junkyard(
                     &mut state.junk,
                     &paths,
@@ -239,23 +264,54 @@ fn update_with_message(
                 actions
             }
         }
+        Message::EditContentLoaded(path, content) => {
+            command::edit::set_content(app, lua, &path, &content);
+            Vec::new()
+        }
         Message::HelpHighlighted(buffer_id, lines) => {
             command::help::apply_highlighted(app, lua, buffer_id, lines);
             Vec::new()
         }
-        Message::PreviewLoaded(content) => preview::update(app, lua, content),
+        Message::PreviewLoaded(content, mime) => preview::update(
+            app,
+            &state.tasks,
+            lua,
+            &settings.theme,
+            &settings.preview_limit,
+            state.show_raw_markdown,
+            content,
+            mime,
+        ),
         Message::Rerender => Vec::new(),
         Message::Resize(x, y) => vec![Action::Resize(x, y)],
-        Message::TaskStarted(id, cancellation) => match app.current_window_and_contents_mut() {
-            Ok((window, contents)) => {
-                task::add(&mut state.tasks, window, contents, id, cancellation, lua)
-            }
+        Message::TaskStarted(id, kind, cancellation) => match app.current_window_and_contents_mut()
+        {
+            Ok((window, contents)) => task::add(
+                &mut state.tasks,
+                window,
+                contents,
+                id,
+                kind,
+                cancellation,
+                lua,
+            ),
             Err(_) => Vec::new(),
         },
-        Message::TaskEnded(id) => match app.current_window_and_contents_mut() {
-            Ok((window, contents)) => task::remove(&mut state.tasks, window, contents, id, lua),
+        Message::TaskProgress(id, done, total) => match app.current_window_and_contents_mut() {
+            Ok((window, contents)) => {
+                task::progress(&mut state.tasks, window, contents, id, done, total, lua)
+            }
             Err(_) => Vec::new(),
         },
+        Message::TaskEnded(id, succeeded) => {
+            let mut actions = journal::complete(&mut state.journal, &id, succeeded);
+            let closing = save::complete(&mut app.contents, &state.modes.current, &id, succeeded);
+            actions.extend(save::close(app, &state.modes.current, closing));
+            if let Ok((window, contents)) = app.current_window_and_contents_mut() {
+                actions.extend(task::remove(&mut state.tasks, window, contents, id, lua));
+            }
+            actions
+        }
         Message::ZoxideResult(path) => navigate::path(app, &mut state.history, path.as_ref(), lua),
     }
 }
@@ -274,6 +330,7 @@ pub fn update_with_keymap_message(
             search::clear(app.contents.buffers.values_mut().collect());
             Vec::new()
         }
+        KeymapMessage::CompleteCommand => commandline::complete(app, &state.modes, lua),
         KeymapMessage::DeleteMarks(mrks) => mark::delete(
             &mut state.marks,
             app.contents.buffers.values_mut().collect(),
@@ -290,6 +347,16 @@ pub fn update_with_keymap_message(
             state.remaining_keysequence.replace(key_sequence.clone());
             Vec::new()
         }
+        KeymapMessage::ExecuteLuaFunction(id) => {
+            if let Some(lua) = lua {
+                let ((), actions) = api::invoke(lua, app, state, || {
+                    yeet_lua::invoke_keymap_function(lua, *id)
+                });
+                actions
+            } else {
+                Vec::new()
+            }
+        }
         KeymapMessage::ExecuteRegister(rgstr) => register::replay(&mut state.register, rgstr),
         KeymapMessage::LeaveCommandMode => {
             commandline::leave(app, &mut state.register, &state.modes)
@@ -328,13 +395,29 @@ pub fn update_with_keymap_message(
         }
         KeymapMessage::PasteFromJunkYard(entry_id) => {
             match junkyard::paste(app, &state.junk, entry_id) {
-                Ok(actions) => actions,
+                Ok(transfers) => {
+                    let mode = state.modes.current.clone();
+                    let transfers = transfers.into_iter().map(Ok).collect();
+                    command::file::transfer(state, &settings.conflict_policy, &mode, transfers)
+                }
                 Err(err) => {
                     tracing::error!("PasteFromJunkYard failed: {}", err);
                     Vec::new()
                 }
             }
         }
+        KeymapMessage::PasteLinkFromJunkYard(entry_id, kind) => {
+            match junkyard::paste_link(app, &state.junk, entry_id, kind) {
+                Ok(transfers) => {
+                    let mode = state.modes.current.clone();
+                    command::file::transfer(state, &settings.conflict_policy, &mode, transfers)
+                }
+                Err(err) => {
+                    tracing::error!("PasteLinkFromJunkYard failed: {}", err);
+                    Vec::new()
+                }
+            }
+        }
         KeymapMessage::Print(content) => commandline::print(
             &mut app.commandline,
             &mut state.modes,
@@ -342,6 +425,9 @@ pub fn update_with_keymap_message(
             &settings.theme,
         ),
         KeymapMessage::ReplayMacro(char) => register::replay_macro(&mut state.register, char),
+        KeymapMessage::ScrollPreview(direction) => {
+            preview::scroll(app, &state.tasks, direction, &settings.preview_limit)
+        }
         KeymapMessage::SetMark(char) => {
             match mark::add(app, &mut state.marks, *char, &settings.theme) {
                 Ok(actions) => actions,
@@ -360,8 +446,32 @@ pub fn update_with_keymap_message(
         KeymapMessage::StopMacro => {
             mode::print_mode(&mut app.commandline, &mut state.modes, &settings.theme)
         }
+        KeymapMessage::ToggleMarkdownPreview => {
+            preview::toggle_markdown(app, &mut state.show_raw_markdown)
+        }
+        KeymapMessage::ToggleQuickFix if state.modes.current == Mode::Visual => {
+            match visual::toggle_quickfix(app, state, &settings.theme, lua) {
+                Ok(actions) => actions,
+                Err(err) => {
+                    tracing::error!("ToggleQuickFix failed: {}", err);
+                    Vec::new()
+                }
+            }
+        }
         KeymapMessage::ToggleQuickFix => qfix::toggle(app, &mut state.qfix, &settings.theme),
+        KeymapMessage::TrashMotionToJunkYard(repeat, mtn) => {
+            match range::trash_motion(app, state, repeat, mtn, &settings.theme, lua) {
+                Ok(actions) => actions,
+                Err(err) => {
+                    tracing::error!("TrashMotionToJunkYard failed: {}", err);
+                    Vec::new()
+                }
+            }
+        }
         KeymapMessage::Quit(mode) => vec![Action::Quit(mode.clone(), None)],
+        KeymapMessage::YankMotionToJunkYard(repeat, mtn) => {
+            range::yank_motion(app, state, repeat, mtn)
+        }
         KeymapMessage::YankPathToClipboard => {
             let (window, contents) = match app.current_window_and_contents_mut() {
                 Ok(window) => window,
@@ -379,6 +489,15 @@ pub fn update_with_keymap_message(
 
             selection::copy_to_clipboard(&mut state.register, directory, &current_vp.cursor)
         }
+        KeymapMessage::YankToJunkYard(_) if state.modes.current == Mode::Visual => {
+            match visual::yank(app, state, &settings.theme, lua) {
+                Ok(actions) => actions,
+                Err(err) => {
+                    tracing::error!("YankToJunkYard failed: {}", err);
+                    Vec::new()
+                }
+            }
+        }
         KeymapMessage::YankToJunkYard(repeat) => {
             match junkyard::yank(app, &mut state.junk, repeat) {
                 Ok(actions) => actions,
@@ -410,6 +529,19 @@ pub fn update_with_buffer_message(
             }
         }
         BufferMessage::Modification(repeat, modification) => match &mut state.modes.current {
+            Mode::Command(CommandMode::PrintMultiline) if !state.conflicts.pending.is_empty() => {
+                let mut actions = conflict::answer(
+                    &mut state.conflicts,
+                    &mut state.junk,
+                    &state.modes,
+                    modification,
+                );
+                journal::record(&mut state.journal, &actions);
+                if state.conflicts.pending.is_empty() {
+                    actions.extend(journal::release(&mut state.journal));
+                }
+                actions
+            }
             Mode::Command(_) => commandline::modify(app, &mut state.modes, repeat, modification),
             Mode::Insert | Mode::Normal => {
                 match modify::buffer(app, state, lua, repeat, modification) {
@@ -440,12 +572,24 @@ pub fn update_with_buffer_message(
                     Vec::new()
                 }
             }
+            Mode::Visual => match modification {
+                TextModification::DeleteLine => {
+                    match visual::trash(app, state, &settings.theme, lua) {
+                        Ok(actions) => actions,
+                        Err(err) => {
+                            tracing::error!("Modification failed: {}", err);
+                            Vec::new()
+                        }
+                    }
+                }
+                _ => Vec::new(),
+            },
         },
         BufferMessage::MoveCursor(rpt, mtn) => match &mut state.modes.current {
             Mode::Command(_) => {
                 commandline::update(&mut app.commandline, &state.modes.current, Some(msg))
             }
-            Mode::Insert | Mode::Navigation | Mode::Normal => {
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
                 match cursor::relocate(app, state, rpt, mtn, lua) {
                     Ok(actions) => actions,
                     Err(err) => {
@@ -459,7 +603,7 @@ pub fn update_with_buffer_message(
             Mode::Command(_) => {
                 commandline::update(&mut app.commandline, &state.modes.current, Some(msg))
             }
-            Mode::Insert | Mode::Navigation | Mode::Normal => {
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
                 match viewport::relocate(app, &mut state.history, &state.modes.current, mtn, lua) {
                     Ok(actions) => actions,
                     Err(err) => {
@@ -471,7 +615,10 @@ pub fn update_with_buffer_message(
         },
         BufferMessage::SaveBuffer => {
             match save::current(app, &mut state.junk, &state.modes.current) {
-                Ok(actions) => actions,
+                Ok(actions) => {
+                    journal::record(&mut state.journal, &actions);
+                    actions
+                }
                 Err(err) => {
                     tracing::error!("SaveBuffer failed: {}", err);
                     Vec::new()
@@ -1,6 +1,6 @@
 use yeet_buffer::{
     message::BufferMessage,
-    model::{BufferLine, CommandMode, Mode, SearchDirection},
+    model::{BufferLine, CommandMode, Mode, SearchDirection, TextBuffer},
 };
 use yeet_keymap::message::PrintContent;
 
@@ -16,7 +16,9 @@ use crate::{
     theme::Theme,
 };
 
-use super::{app, commandline, junkyard, path, register::get_macro_register, save};
+use super::{
+    app, commandline, conflict, git, journal, junkyard, path, register::get_macro_register, save,
+};
 
 pub fn change(
     app: &mut App,
@@ -30,7 +32,8 @@ pub fn change(
         (Mode::Command(_), Mode::Command(_))
         | (Mode::Insert, Mode::Insert)
         | (Mode::Navigation, Mode::Navigation)
-        | (Mode::Normal, Mode::Normal) => return Ok(Vec::new()),
+        | (Mode::Normal, Mode::Normal)
+        | (Mode::Visual, Mode::Visual) => return Ok(Vec::new()),
         _ => {}
     }
 
@@ -39,7 +42,12 @@ pub fn change(
         if matches!(
             app.contents.buffers.get(&vp.buffer_id),
             Some(Buffer::Tasks(_)) | Some(Buffer::QuickFix(_)) | Some(Buffer::Help(_))
-        ) {
+        ) || to == &Mode::Visual
+            && matches!(
+                app.contents.buffers.get(&vp.buffer_id),
+                Some(Buffer::Edit(_))
+            )
+        {
             return Ok(Vec::new());
         }
     }
@@ -48,12 +56,16 @@ pub fn change(
     state.modes.previous = Some(from.clone());
 
     let mut actions = vec![Action::ModeChanged];
+    if !to.is_command() {
+        actions.extend(conflict::abort(&mut state.conflicts));
+        actions.extend(journal::release(&mut state.journal));
+    }
     actions.extend(match from {
         Mode::Command(_) => {
             app.commandline.viewport.hide_cursor = true;
             update_commandline_on_mode_change(&mut app.commandline, &mut state.modes)
         }
-        Mode::Insert | Mode::Navigation | Mode::Normal => {
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
             let (window, contents) = app.current_window_and_contents_mut()?;
             let (vp, _buffer) = app::get_focused_current_mut(window, contents)?;
             vp.hide_cursor = true;
@@ -78,13 +90,13 @@ pub fn change(
         Mode::Insert => {
             let (window, contents) = app.current_window_and_contents_mut()?;
             let (vp, buffer) = app::get_focused_current_mut(window, contents)?;
-            if let Buffer::Directory(dir) = buffer {
+            if let Some(text_buffer) = get_editable_buffer_mut(buffer) {
                 vp.hide_cursor = false;
 
                 yeet_buffer::update(
                     Some(vp),
                     &state.modes.current,
-                    &mut dir.buffer,
+                    text_buffer,
                     std::slice::from_ref(&msg),
                 );
             }
@@ -94,7 +106,7 @@ pub fn change(
         Mode::Navigation => {
             let (window, contents) = app.current_window_and_contents_mut()?;
             let (vp, buffer) = app::get_focused_current_mut(window, contents)?;
-            if let Buffer::Directory(dir) = buffer {
+            if let Some(text_buffer) = get_editable_buffer_mut(buffer) {
                 // TODO: handle file operations: show pending with gray, refresh on operation success
                 // TODO: sort and refresh current on PathEnumerationFinished while not in Navigation mode
                 vp.hide_cursor = false;
@@ -102,24 +114,27 @@ pub fn change(
                 yeet_buffer::update(
                     Some(vp),
                     &state.modes.current,
-                    &mut dir.buffer,
+                    text_buffer,
                     std::slice::from_ref(&msg),
                 );
             }
 
             let (window, contents) = app.current_window_and_contents_mut()?;
-            save::all(window, contents, &mut state.junk, &state.modes.current)
+            let actions = save::all(window, contents, &mut state.junk, &state.modes.current);
+            journal::record(&mut state.journal, &actions);
+
+            actions
         }
-        Mode::Normal => {
+        Mode::Normal | Mode::Visual => {
             let (window, contents) = app.current_window_and_contents_mut()?;
             let (vp, buffer) = app::get_focused_current_mut(window, contents)?;
-            if let Buffer::Directory(dir) = buffer {
+            if let Some(text_buffer) = get_editable_buffer_mut(buffer) {
                 vp.hide_cursor = false;
 
                 yeet_buffer::update(
                     Some(vp),
                     &state.modes.current,
-                    &mut dir.buffer,
+                    text_buffer,
                     std::slice::from_ref(&msg),
                 );
             }
@@ -135,6 +150,14 @@ pub fn change(
     Ok(actions)
 }
 
+fn get_editable_buffer_mut(buffer: &mut Buffer) -> Option<&mut TextBuffer> {
+    match buffer {
+        Buffer::Directory(it) => Some(&mut it.buffer),
+        Buffer::Edit(it) => Some(&mut it.buffer),
+        _ => None,
+    }
+}
+
 fn flush_pending_paths(
     state: &mut State,
     app: &mut App,
@@ -142,6 +165,7 @@ fn flush_pending_paths(
     lua: Option<&LuaConfiguration>,
 ) -> Vec<Action> {
     let mut actions = Vec::new();
+    let mut changed = Vec::new();
     for event in state.pending_path_events.drain(..) {
         match event {
             PendingPathEvent::Added(paths) => {
@@ -167,6 +191,7 @@ fn flush_pending_paths(
                     &mut state.junk,
                     &paths,
                 ));
+                changed.extend(paths);
             }
             PendingPathEvent::Removed(path) => {
                 actions.extend(
@@ -187,9 +212,12 @@ fn flush_pending_paths(
                         Vec::new()
                     }),
                 );
+                changed.push(path);
             }
         }
     }
+
+    actions.extend(git::refresh(app, &mut state.git, &state.tasks, &changed));
     actions
 }
 
@@ -202,7 +230,7 @@ fn update_commandline_on_mode_change(
 
     let command_mode = match &modes.current {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => {
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
             let from_command = matches!(modes.previous.as_ref(), Some(mode) if mode.is_command());
 
             if from_command {
@@ -58,6 +58,13 @@ pub fn buffer(
 
             actions
         }
+        Buffer::Edit(it) => {
+            let mode = &state.modes.current;
+            let msg = BufferMessage::Modification(*repeat, modification.clone());
+            yeet_buffer::update(Some(vp), mode, &mut it.buffer, std::slice::from_ref(&msg));
+
+            Vec::new()
+        }
         Buffer::QuickFix(_) => {
             if !matches!(modification, TextModification::DeleteLine) {
                 return Ok(Vec::new());
@@ -66,7 +73,7 @@ pub fn buffer(
             let cursor_index = vp.cursor.vertical_index;
             window::remove_entry(app, lua, &mut state.qfix, cursor_index)
         }
-        Buffer::Help(_) => Vec::new(),
+        Buffer::Diff(_) | Buffer::Help(_) => Vec::new(),
         Buffer::Image(_) | Buffer::Content(_) | Buffer::PathReference(_) | Buffer::Empty => {
             Vec::new()
         }
@@ -91,6 +98,7 @@ mod test {
     use crate::{
         error::AppError,
         model::{App, Buffer, CurrentTask, State, Tasks},
+        task::TaskKind,
     };
 
     use super::buffer;
@@ -110,6 +118,8 @@ mod test {
             CurrentTask {
                 external_id: "rg foo".to_string(),
                 id: 1,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -118,6 +128,8 @@ mod test {
             CurrentTask {
                 external_id: "fd bar".to_string(),
                 id: 5,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -126,6 +138,8 @@ mod test {
             CurrentTask {
                 external_id: "rg baz".to_string(),
                 id: 10,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
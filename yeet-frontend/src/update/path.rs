@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    fs,
     path::{Path, PathBuf},
     slice,
 };
@@ -40,7 +41,7 @@ pub fn add(
     let mut actions = Vec::new();
     for path in paths {
         actions.extend(update_directory_buffers_on_add(
-            history, mode, app, path, lua,
+            history, mode, app, path, theme, lua,
         ));
     }
 
@@ -166,6 +167,7 @@ fn update_directory_buffers_on_add(
     mode: &Mode,
     app: &mut App,
     path: &Path,
+    theme: &Theme,
     lua: Option<&LuaConfiguration>,
 ) -> Vec<Action> {
     let (parent, name) = match (path.parent(), path.file_name()) {
@@ -226,13 +228,16 @@ fn update_directory_buffers_on_add(
                 name_with_slash.push('/');
             }
 
-            let mut bufferline = enumeration::from_enumeration(&name_with_slash);
+            let link = fs::read_link(path).ok();
+            let mut bufferline =
+                enumeration::from_enumeration(&name_with_slash, link.as_deref(), theme);
             if let Some(lua) = lua {
                 yeet_lua::invoke_on_bufferline_mutate(
                     lua,
                     &mut bufferline,
                     yeet_lua::BufferType::Directory,
                     Some(path),
+                    None,
                 );
             }
             if let Some(index) = added_existing_directory {
@@ -430,7 +435,11 @@ fn cleanup_removed_buffers_in_window(
                 );
             }
         }
-        Window::Tasks(_) | Window::QuickFix(_) | Window::Help(_) => {}
+        Window::Tasks(_)
+        | Window::QuickFix(_)
+        | Window::Diff(_)
+        | Window::Edit(_)
+        | Window::Help(_) => {}
     }
 
     actions
@@ -576,7 +585,11 @@ fn update_viewports_for_buffers_in_window(
                     .unwrap_or_default(),
             );
         }
-        Window::Tasks(viewport) | Window::QuickFix(viewport) | Window::Help(viewport) => {
+        Window::Tasks(viewport)
+        | Window::QuickFix(viewport)
+        | Window::Diff(viewport)
+        | Window::Edit(viewport)
+        | Window::Help(viewport) => {
             update_viewport_for_buffer(viewport, contents, mode, buffer_ids, selection_by_viewport);
         }
     };
@@ -620,7 +633,11 @@ fn collect_viewport_selections_for_buffers_in_window(
             collect_viewport_selection(current, contents, buffer_ids, selections);
             collect_viewport_selection(preview, contents, buffer_ids, selections);
         }
-        Window::Tasks(viewport) | Window::QuickFix(viewport) | Window::Help(viewport) => {
+        Window::Tasks(viewport)
+        | Window::QuickFix(viewport)
+        | Window::Diff(viewport)
+        | Window::Edit(viewport)
+        | Window::Help(viewport) => {
             collect_viewport_selection(viewport, contents, buffer_ids, selections);
         }
     }
@@ -691,6 +708,12 @@ fn update_viewport_for_buffer(
         Buffer::QuickFix(qfix) => {
             update_directory_viewport_selection(viewport, mode, &qfix.buffer, selection);
         }
+        Buffer::Diff(diff_buf) => {
+            update_directory_viewport_selection(viewport, mode, &diff_buf.buffer, selection);
+        }
+        Buffer::Edit(edit) => {
+            update_directory_viewport_selection(viewport, mode, &edit.buffer, selection);
+        }
         Buffer::Help(help) => {
             update_directory_viewport_selection(viewport, mode, &help.buffer, selection);
         }
@@ -1507,6 +1530,7 @@ mod test {
                     content: Ansi::new("content"),
                     ..Default::default()
                 }]),
+                ..Default::default()
             }),
         );
         app.contents.buffers.insert(
@@ -1517,6 +1541,7 @@ mod test {
                     content: Ansi::new("content"),
                     ..Default::default()
                 }]),
+                ..Default::default()
             }),
         );
 
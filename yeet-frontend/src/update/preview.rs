@@ -1,55 +1,114 @@
-use yeet_buffer::model::{ansi::Ansi, BufferLine, TextBuffer};
+use std::{
+    mem,
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::{
+    message::{BufferMessage, ViewPortDirection},
+    model::{ansi::Ansi, BufferLine, Mode, TextBuffer},
+};
 use yeet_lua::LuaConfiguration;
 
 use crate::{
     action::Action,
     event::Preview,
-    model::{App, Buffer, ContentBuffer, Contents, PreviewImageBuffer, Window},
+    model::{App, Buffer, ContentBuffer, Contents, PreviewImageBuffer, Tasks, Window},
+    settings::PreviewLimit,
+    task::{Continuation, Task},
+    theme::{tokens, Theme},
 };
 
-use super::app;
+use super::{app, hook};
 
-pub fn update(app: &mut App, lua: Option<&LuaConfiguration>, content: Preview) -> Vec<Action> {
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    app: &mut App,
+    tasks: &Tasks,
+    lua: Option<&LuaConfiguration>,
+    theme: &Theme,
+    limit: &PreviewLimit,
+    show_raw_markdown: bool,
+    content: Preview,
+    mime: Option<String>,
+) -> Vec<Action> {
     match content {
         Preview::Content(path, content) => {
             tracing::trace!("updating preview buffer: {:?}", path);
 
-            let content: Vec<_> = content
-                .iter()
-                .map(|s| {
-                    let mut line = BufferLine {
-                        content: Ansi::new(s),
-                        ..Default::default()
-                    };
-                    if let Some(lua) = lua {
-                        yeet_lua::invoke_on_bufferline_mutate(
-                            lua,
-                            &mut line,
-                            yeet_lua::BufferType::Content,
-                            Some(&path),
-                        );
-                    }
-                    line
-                })
-                .collect();
+            set_content(app, lua, path, mime, &content, None, None);
+        }
+        Preview::Continued(path, content, continuation) => {
+            tracing::trace!("appending to preview buffer: {:?}", path);
+
+            append_content(app, lua, &path, &content, None, continuation);
+            return load_remaining(app, tasks, limit);
+        }
+        Preview::ContinuedMarkdown(path, raw, rendered, continuation) => {
+            tracing::trace!("appending to preview buffer: {:?}", path);
+
+            let (content, alternate) = if show_raw_markdown {
+                (raw, rendered)
+            } else {
+                (rendered, raw)
+            };
+            append_content(app, lua, &path, &content, Some(&alternate), continuation);
+            return load_remaining(app, tasks, limit);
+        }
+        Preview::Hex(path, bytes, bytes_per_line) => {
+            tracing::trace!("updating preview buffer: {:?}", path);
+
+            let content = build_hex_lines(&bytes, bytes_per_line, theme);
+            set_content(app, lua, path, mime, &content, None, None);
+        }
+        Preview::Image(path, protocol, metadata) => {
+            tracing::trace!("updating preview buffer: {:?}", path);
 
             let (preview_id, _) = app::resolve_buffer(&mut app.contents, &path, &None);
             app.contents.buffers.insert(
                 preview_id,
-                Buffer::Content(ContentBuffer {
+                Buffer::Image(PreviewImageBuffer {
                     path,
-                    buffer: TextBuffer::from_lines(content),
+                    protocol,
+                    mime,
+                    image_metadata: metadata.map(Box::new),
                 }),
             );
+
+            if let Some(lua) = lua {
+                hook::invoke_on_window_change_for_focused(app, lua);
+            }
         }
-        Preview::Image(path, protocol) => {
+        Preview::ImageSymbols(path, content, metadata) => {
             tracing::trace!("updating preview buffer: {:?}", path);
 
             let (preview_id, _) = app::resolve_buffer(&mut app.contents, &path, &None);
-            app.contents.buffers.insert(
-                preview_id,
-                Buffer::Image(PreviewImageBuffer { path, protocol }),
+            set_content(app, lua, path, mime, &content, None, None);
+            if let Some(Buffer::Content(it)) = app.contents.buffers.get_mut(&preview_id) {
+                it.image_metadata = metadata.map(Box::new);
+            }
+
+            if let Some(lua) = lua {
+                hook::invoke_on_window_change_for_focused(app, lua);
+            }
+        }
+        Preview::Markdown(path, raw, rendered, continuation) => {
+            tracing::trace!("updating preview buffer: {:?}", path);
+
+            let (content, alternate) = if show_raw_markdown {
+                (raw, rendered)
+            } else {
+                (rendered, raw)
+            };
+            set_content(
+                app,
+                lua,
+                path,
+                mime,
+                &content,
+                Some(&alternate),
+                continuation,
             );
+            return load_remaining(app, tasks, limit);
         }
         Preview::None(path) => {
             tracing::trace!("updating preview buffer: {:?}", path);
@@ -59,11 +118,244 @@ pub fn update(app: &mut App, lua: Option<&LuaConfiguration>, content: Preview) -
                 .buffers
                 .insert(preview_id, Buffer::PathReference(path));
         }
+        Preview::Partial(path, content, continuation) => {
+            tracing::trace!("updating preview buffer: {:?}", path);
+
+            set_content(app, lua, path, mime, &content, None, Some(continuation));
+            return load_remaining(app, tasks, limit);
+        }
+    }
+
+    Vec::new()
+}
+
+pub fn scroll(
+    app: &mut App,
+    tasks: &Tasks,
+    direction: &ViewPortDirection,
+    limit: &PreviewLimit,
+) -> Vec<Action> {
+    let (window, contents) = match app.current_window_and_contents_mut() {
+        Ok(it) => it,
+        Err(_) => return Vec::new(),
+    };
+
+    let Some((_, _, preview)) = app::get_focused_directory_viewports_mut(window) else {
+        return Vec::new();
+    };
+
+    if let Some(Buffer::Content(content)) = contents.buffers.get_mut(&preview.buffer_id) {
+        if !content.buffer.lines.is_empty() {
+            yeet_buffer::update(
+                Some(preview),
+                &Mode::Navigation,
+                &mut content.buffer,
+                &[BufferMessage::MoveViewPort(direction.clone())],
+            );
+        }
+    }
+
+    load_remaining(app, tasks, limit)
+}
+
+pub fn toggle_markdown(app: &mut App, show_raw_markdown: &mut bool) -> Vec<Action> {
+    let (window, contents) = match app.current_window_and_contents_mut() {
+        Ok(it) => it,
+        Err(_) => return Vec::new(),
+    };
+
+    let Some((_, _, preview)) = app::get_focused_directory_viewports_mut(window) else {
+        return Vec::new();
+    };
+
+    if let Some(Buffer::Content(content)) = contents.buffers.get_mut(&preview.buffer_id) {
+        if let Some(alternate) = content.alternate_lines.as_mut() {
+            *show_raw_markdown = !*show_raw_markdown;
+            mem::swap(&mut content.buffer.lines, alternate);
+            preview.cursor = Default::default();
+            preview.vertical_index = 0;
+        }
     }
 
     Vec::new()
 }
 
+pub fn load_remaining(app: &mut App, tasks: &Tasks, limit: &PreviewLimit) -> Vec<Action> {
+    let (window, contents) = match app.current_window_and_contents_mut() {
+        Ok(it) => it,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut actions = Vec::new();
+    for viewport in window.preview_viewports() {
+        let Some(Buffer::Content(content)) = contents.buffers.get_mut(&viewport.buffer_id) else {
+            continue;
+        };
+
+        let required = viewport.vertical_index + 2 * usize::from(viewport.height);
+        if required < content.buffer.lines.len() {
+            continue;
+        }
+
+        if let Some(continuation) = &content.continuation {
+            let task =
+                Task::LoadPreviewContinuation(content.path.clone(), continuation.clone(), *limit);
+            if !tasks.running.contains_key(&task.to_string()) {
+                actions.push(Action::Task(task));
+            }
+        }
+    }
+
+    actions
+}
+
+fn set_content(
+    app: &mut App,
+    lua: Option<&LuaConfiguration>,
+    path: PathBuf,
+    mime: Option<String>,
+    content: &[String],
+    alternate: Option<&[String]>,
+    continuation: Option<Continuation>,
+) {
+    let content = to_buffer_lines(lua, &path, mime.as_deref(), content);
+    let alternate_lines =
+        alternate.map(|alternate| to_buffer_lines(lua, &path, mime.as_deref(), alternate));
+
+    let (preview_id, _) = app::resolve_buffer(&mut app.contents, &path, &None);
+    app.contents.buffers.insert(
+        preview_id,
+        Buffer::Content(ContentBuffer {
+            path,
+            buffer: TextBuffer::from_lines(content),
+            continuation,
+            mime,
+            alternate_lines,
+            image_metadata: None,
+        }),
+    );
+}
+
+fn append_content(
+    app: &mut App,
+    lua: Option<&LuaConfiguration>,
+    path: &Path,
+    content: &[String],
+    alternate: Option<&[String]>,
+    continuation: Option<Continuation>,
+) {
+    let buffer = app
+        .contents
+        .buffers
+        .values_mut()
+        .find_map(|buffer| match buffer {
+            Buffer::Content(it) if it.path == path => Some(it),
+            _ => None,
+        });
+
+    if let Some(buffer) = buffer {
+        let is_next = match (&buffer.continuation, &continuation) {
+            (Some(current), Some(next)) => next.offset > current.offset,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if !is_next {
+            return;
+        }
+
+        let lines = to_buffer_lines(lua, path, buffer.mime.as_deref(), content);
+        buffer.buffer.lines.extend(lines);
+        if let (Some(alternate), Some(lines)) = (alternate, buffer.alternate_lines.as_mut()) {
+            lines.extend(to_buffer_lines(
+                lua,
+                path,
+                buffer.mime.as_deref(),
+                alternate,
+            ));
+        }
+        buffer.continuation = continuation;
+    }
+}
+
+fn to_buffer_lines(
+    lua: Option<&LuaConfiguration>,
+    path: &Path,
+    mime: Option<&str>,
+    content: &[String],
+) -> Vec<BufferLine> {
+    content
+        .iter()
+        .map(|s| {
+            let mut line = BufferLine {
+                content: Ansi::new(s),
+                ..Default::default()
+            };
+            if let Some(lua) = lua {
+                yeet_lua::invoke_on_bufferline_mutate(
+                    lua,
+                    &mut line,
+                    yeet_lua::BufferType::Content,
+                    Some(path),
+                    mime,
+                );
+            }
+            line
+        })
+        .collect()
+}
+
+fn build_hex_lines(bytes: &[u8], bytes_per_line: usize, theme: &Theme) -> Vec<String> {
+    let highlight = theme.ansi_fg(tokens::HEX_NON_PRINTABLE_FG);
+    let colorize = |byte: u8, content: String| {
+        if is_printable(byte) {
+            content
+        } else {
+            format!("{}{}\x1b[39m", highlight, content)
+        }
+    };
+
+    bytes
+        .chunks(bytes_per_line.max(1))
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut hex = Vec::new();
+            for position in 0..bytes_per_line {
+                if position > 0 && position % 8 == 0 {
+                    hex.push(String::new());
+                }
+
+                hex.push(match chunk.get(position) {
+                    Some(byte) => colorize(*byte, format!("{:02x}", byte)),
+                    None => "  ".to_string(),
+                });
+            }
+
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| {
+                    let character = if is_printable(*byte) {
+                        char::from(*byte)
+                    } else {
+                        '.'
+                    };
+                    colorize(*byte, character.to_string())
+                })
+                .collect();
+
+            format!(
+                "{:08x}  {}  {}",
+                index * bytes_per_line,
+                hex.join(" "),
+                ascii
+            )
+        })
+        .collect()
+}
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' '
+}
+
 pub fn set_buffer_id(contents: &mut Contents, window: &mut Window, buffer_id: usize) {
     let is_directory = if let Some(Buffer::Directory(it)) = contents.buffers.get(&buffer_id) {
         it.path.is_dir()
@@ -76,3 +368,371 @@ pub fn set_buffer_id(contents: &mut Contents, window: &mut Window, buffer_id: us
         preview.hide_cursor_line = !is_directory;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use tokio_util::sync::CancellationToken;
+    use yeet_buffer::{
+        message::ViewPortDirection,
+        model::{BufferLine, TextBuffer},
+    };
+
+    use crate::{
+        action::Action,
+        event::Preview,
+        model::{App, Buffer, ContentBuffer, CurrentTask, Tasks},
+        settings::PreviewLimit,
+        task::{Continuation, Task},
+        theme::{tokens, Theme},
+        update::app,
+    };
+
+    use super::{build_hex_lines, load_remaining, scroll, toggle_markdown, update};
+
+    fn make_app_with_preview(path: &Path, height: u16) -> App {
+        let mut app = App::default();
+        let (preview_id, _) = app::resolve_buffer(&mut app.contents, path, &None);
+        let window = app.current_window_mut().expect("test requires current tab");
+        let (_, _, preview) = app::get_focused_directory_viewports_mut(window).unwrap();
+        preview.buffer_id = preview_id;
+        preview.height = height;
+        app
+    }
+
+    fn lines(count: usize) -> Vec<String> {
+        (0..count).map(|i| i.to_string()).collect()
+    }
+
+    fn preview_lines(app: &App, path: &Path) -> usize {
+        app.contents
+            .buffers
+            .values()
+            .find_map(|buffer| match buffer {
+                Buffer::Content(it) if it.path == path => Some(it.buffer.lines.len()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn has_continuation_task(actions: &[Action], offset: u64) -> bool {
+        actions.iter().any(|action| {
+            matches!(
+                action,
+                Action::Task(Task::LoadPreviewContinuation(_, continuation, _))
+                    if continuation.offset == offset
+            )
+        })
+    }
+
+    #[test]
+    fn partial_preview_loads_remaining_when_viewport_is_not_filled() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+
+        let actions = update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Partial(path.clone(), lines(5), Continuation::new(42)),
+            None,
+        );
+
+        assert_eq!(preview_lines(&app, &path), 5);
+        assert!(has_continuation_task(&actions, 42));
+
+        let mut tasks = Tasks::default();
+        for action in actions {
+            if let Action::Task(task) = action {
+                tasks.running.insert(
+                    task.to_string(),
+                    CurrentTask {
+                        external_id: task.to_string(),
+                        id: 1,
+                        kind: task.kind(),
+                        progress: None,
+                        token: CancellationToken::new(),
+                    },
+                );
+            }
+        }
+        assert!(load_remaining(&mut app, &tasks, &PreviewLimit::default()).is_empty());
+    }
+
+    #[test]
+    fn canceled_continuation_is_requested_again() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Partial(path.clone(), lines(5), Continuation::new(42)),
+            None,
+        );
+
+        let actions = load_remaining(&mut app, &Tasks::default(), &PreviewLimit::default());
+
+        assert!(has_continuation_task(&actions, 42));
+    }
+
+    #[test]
+    fn stale_continued_preview_is_ignored() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Partial(path.clone(), lines(5), Continuation::new(42)),
+            None,
+        );
+
+        for _ in 0..2 {
+            update(
+                &mut app,
+                &Tasks::default(),
+                None,
+                &Theme::default(),
+                &PreviewLimit::default(),
+                false,
+                Preview::Continued(path.clone(), lines(5), Some(Continuation::new(84))),
+                None,
+            );
+        }
+
+        assert_eq!(preview_lines(&app, &path), 10);
+    }
+
+    #[test]
+    fn partial_preview_waits_while_enough_lines_are_loaded() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+
+        let actions = update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Partial(path.clone(), lines(50), Continuation::new(42)),
+            None,
+        );
+
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn continued_preview_appends_lines() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Partial(path.clone(), lines(5), Continuation::new(42)),
+            None,
+        );
+
+        let actions = update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            false,
+            Preview::Continued(path.clone(), lines(5), None),
+            None,
+        );
+
+        assert_eq!(preview_lines(&app, &path), 10);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn scroll_moves_preview_and_loads_remaining() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+        let (preview_id, _) = app::resolve_buffer(&mut app.contents, &path, &None);
+        app.contents.buffers.insert(
+            preview_id,
+            Buffer::Content(ContentBuffer {
+                path: path.clone(),
+                buffer: TextBuffer::from_lines(vec![BufferLine::default(); 12]),
+                continuation: Some(Continuation::new(7)),
+                mime: None,
+                alternate_lines: None,
+                image_metadata: None,
+            }),
+        );
+
+        let actions = scroll(
+            &mut app,
+            &Tasks::default(),
+            &ViewPortDirection::LineDown,
+            &PreviewLimit::default(),
+        );
+
+        let window = app.current_window_mut().unwrap();
+        let (_, _, preview) = app::get_focused_directory_viewports_mut(window).unwrap();
+        assert_eq!(preview.vertical_index, 1);
+        assert!(has_continuation_task(&actions, 7));
+    }
+
+    #[test]
+    fn update_with_markdown_shows_rendered_lines_and_toggles_to_raw() {
+        let path = PathBuf::from("/dir/README.md");
+        let mut app = make_app_with_preview(&path, 10);
+        let mut show_raw_markdown = false;
+
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            show_raw_markdown,
+            Preview::Markdown(path.clone(), lines(3), lines(2), None),
+            None,
+        );
+        assert_eq!(preview_lines(&app, &path), 2);
+
+        let window = app.current_window_mut().unwrap();
+        let (_, _, preview) = app::get_focused_directory_viewports_mut(window).unwrap();
+        preview.vertical_index = 1;
+
+        toggle_markdown(&mut app, &mut show_raw_markdown);
+
+        assert!(show_raw_markdown);
+        assert_eq!(preview_lines(&app, &path), 3);
+        let window = app.current_window_mut().unwrap();
+        let (_, _, preview) = app::get_focused_directory_viewports_mut(window).unwrap();
+        assert_eq!(preview.vertical_index, 0);
+    }
+
+    #[test]
+    fn partial_markdown_appends_raw_and_rendered_lines() {
+        let path = PathBuf::from("/dir/README.md");
+        let mut app = make_app_with_preview(&path, 10);
+        let mut show_raw_markdown = false;
+
+        let actions = update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            show_raw_markdown,
+            Preview::Markdown(
+                path.clone(),
+                lines(3),
+                lines(2),
+                Some(Continuation::new(42)),
+            ),
+            None,
+        );
+        assert!(has_continuation_task(&actions, 42));
+
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            show_raw_markdown,
+            Preview::ContinuedMarkdown(path.clone(), lines(3), lines(1), None),
+            None,
+        );
+        assert_eq!(preview_lines(&app, &path), 3);
+
+        toggle_markdown(&mut app, &mut show_raw_markdown);
+        assert_eq!(preview_lines(&app, &path), 6);
+    }
+
+    #[test]
+    fn toggle_markdown_without_markdown_preview_keeps_setting() {
+        let path = PathBuf::from("/dir/log.txt");
+        let mut app = make_app_with_preview(&path, 10);
+        let mut show_raw_markdown = false;
+
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            show_raw_markdown,
+            Preview::Content(path.clone(), lines(3)),
+            None,
+        );
+        toggle_markdown(&mut app, &mut show_raw_markdown);
+
+        assert!(!show_raw_markdown);
+        assert_eq!(preview_lines(&app, &path), 3);
+    }
+
+    #[test]
+    fn update_with_markdown_keeps_raw_view_when_toggled() {
+        let path = PathBuf::from("/dir/README.md");
+        let mut app = make_app_with_preview(&path, 10);
+
+        update(
+            &mut app,
+            &Tasks::default(),
+            None,
+            &Theme::default(),
+            &PreviewLimit::default(),
+            true,
+            Preview::Markdown(path.clone(), lines(3), lines(2), None),
+            None,
+        );
+
+        assert_eq!(preview_lines(&app, &path), 3);
+    }
+
+    #[test]
+    fn build_hex_lines_renders_offset_hex_and_ascii() {
+        let theme = Theme::default();
+        let bytes: Vec<u8> = (b'a'..=b'r').collect();
+
+        let lines = build_hex_lines(&bytes, 16, &theme);
+
+        assert_eq!(
+            lines,
+            vec![
+                "00000000  61 62 63 64 65 66 67 68  69 6a 6b 6c 6d 6e 6f 70  abcdefghijklmnop",
+                "00000010  71 72                                             qr",
+            ]
+        );
+    }
+
+    #[test]
+    fn build_hex_lines_highlights_non_printable_bytes() {
+        let theme = Theme::default();
+        let highlight = theme.ansi_fg(tokens::HEX_NON_PRINTABLE_FG);
+
+        let lines = build_hex_lines(&[0x00, b'A'], 8, &theme);
+
+        assert_eq!(
+            lines,
+            vec![format!(
+                "00000000  {0}00\x1b[39m 41                    {0}.\x1b[39mA",
+                highlight
+            )]
+        );
+    }
+}
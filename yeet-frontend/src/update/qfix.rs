@@ -29,32 +29,49 @@ pub fn toggle(app: &mut App, qfix: &mut QuickFix, theme: &Theme) -> Vec<Action>
         Buffer::PathReference(_) => return Vec::new(),
         Buffer::Tasks(_) => return Vec::new(),
         Buffer::QuickFix(_) => return Vec::new(),
-        Buffer::Help(_) => return Vec::new(),
+        Buffer::Diff(_) | Buffer::Edit(_) | Buffer::Help(_) => return Vec::new(),
         Buffer::Empty => return Vec::new(),
     };
 
     let selected = model::get_selected_path(buffer, &vp_cursor);
-    if let Some(selected) = selected {
-        if qfix.entries.contains(&selected) {
-            qfix.entries.retain(|p| p != &selected);
+    toggle_paths(app, qfix, selected.into_iter().collect(), theme)
+}
 
-            sign::unset_sign_for_paths(
-                app.contents.buffers.values_mut().collect(),
-                vec![selected.clone()],
-                QFIX_SIGN_ID,
-            );
+pub fn toggle_paths(
+    app: &mut App,
+    qfix: &mut QuickFix,
+    paths: Vec<PathBuf>,
+    theme: &Theme,
+) -> Vec<Action> {
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for path in paths {
+        if qfix.entries.contains(&path) {
+            qfix.entries.retain(|p| p != &path);
+            removed.push(path);
         } else {
-            qfix.entries.push(selected.clone());
-
-            sign::set_sign_for_paths(
-                app.contents.buffers.values_mut().collect(),
-                vec![selected],
-                QFIX_SIGN_ID,
-                theme,
-            );
+            qfix.entries.push(path.clone());
+            added.push(path);
         }
     }
 
+    if !removed.is_empty() {
+        sign::unset_sign_for_paths(
+            app.contents.buffers.values_mut().collect(),
+            removed,
+            QFIX_SIGN_ID,
+        );
+    }
+
+    if !added.is_empty() {
+        sign::set_sign_for_paths(
+            app.contents.buffers.values_mut().collect(),
+            added,
+            QFIX_SIGN_ID,
+            theme,
+        );
+    }
+
     vec![Action::EmitMessages(vec![Message::QuickFixChanged])]
 }
 
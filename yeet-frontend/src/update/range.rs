@@ -0,0 +1,292 @@
+use std::path::PathBuf;
+
+use yeet_buffer::{
+    message::{CursorDirection, TextModification},
+    model::{Mode, TextBuffer},
+};
+use yeet_lua::LuaConfiguration;
+
+use crate::{
+    action::Action,
+    error::AppError,
+    model::{App, Buffer, State},
+    theme::Theme,
+};
+
+use super::{cursor, junkyard, mode, modify, register, search};
+
+pub fn get_paths(app: &App, start: usize, end: usize) -> Option<Vec<PathBuf>> {
+    let window = app.current_window().ok()?;
+    let viewport = window.focused_viewport();
+
+    let directory = match app.contents.buffers.get(&viewport.buffer_id) {
+        Some(Buffer::Directory(it)) => it,
+        _ => return None,
+    };
+
+    let paths = directory
+        .buffer
+        .lines
+        .iter()
+        .skip(start)
+        .take(end - start + 1)
+        .filter(|line| !line.content.is_empty())
+        .map(|line| directory.path.join(line.content.to_stripped_string()))
+        .collect();
+
+    Some(paths)
+}
+
+pub fn from_motion(
+    app: &App,
+    state: &State,
+    count: &usize,
+    mtn: &CursorDirection,
+) -> Option<(usize, usize)> {
+    let mtn = cursor::get_motion_by_search_register(&state.register, mtn)?;
+
+    let viewport = app.current_window().ok()?.focused_viewport();
+    let directory = match app.contents.buffers.get(&viewport.buffer_id) {
+        Some(Buffer::Directory(it)) => it,
+        _ => return None,
+    };
+
+    if directory.buffer.lines.is_empty() {
+        return None;
+    }
+
+    let mut buffer = TextBuffer::from_lines(directory.buffer.lines.clone());
+    buffer.last_find = directory.buffer.last_find.clone();
+    if matches!(mtn, CursorDirection::Search(_)) {
+        let term = register::get_register(&state.register, &'/');
+        search::text_buffer(&mut buffer, term.as_deref());
+    }
+
+    let mut target = viewport.clone();
+    cursor::move_directory_cursor(&state.modes.current, &mut target, &mut buffer, count, &mtn);
+
+    let start = viewport.cursor.vertical_index;
+    let end = target.cursor.vertical_index;
+
+    Some((start.min(end), start.max(end)))
+}
+
+pub fn trash(
+    app: &mut App,
+    state: &mut State,
+    from: &Mode,
+    (start, end): (usize, usize),
+    theme: &Theme,
+    lua: Option<&LuaConfiguration>,
+) -> Result<Vec<Action>, AppError> {
+    let viewport = app.current_window_mut()?.focused_viewport_mut();
+    viewport.cursor.vertical_index = start;
+
+    let mut actions = mode::change(app, state, from, &Mode::Normal, theme, lua)?;
+    actions.extend(modify::buffer(
+        app,
+        state,
+        lua,
+        &(end - start + 1),
+        &TextModification::DeleteLine,
+    )?);
+
+    Ok(actions)
+}
+
+pub fn trash_motion(
+    app: &mut App,
+    state: &mut State,
+    count: &usize,
+    mtn: &CursorDirection,
+    theme: &Theme,
+    lua: Option<&LuaConfiguration>,
+) -> Result<Vec<Action>, AppError> {
+    match from_motion(app, state, count, mtn) {
+        Some(range) => trash(app, state, &Mode::Navigation, range, theme, lua),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn yank_motion(
+    app: &App,
+    state: &mut State,
+    count: &usize,
+    mtn: &CursorDirection,
+) -> Vec<Action> {
+    let paths = from_motion(app, state, count, mtn)
+        .and_then(|(start, end)| get_paths(app, start, end))
+        .unwrap_or_default();
+
+    junkyard::yank_paths(&mut state.junk, paths)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_buffer::{
+        message::{CursorDirection, Search},
+        model::{BufferLine, Mode, SearchDirection, TextBuffer},
+    };
+
+    use crate::{
+        action::Action,
+        model::{App, Buffer, DirectoryBuffer, State},
+        task::Task,
+        theme::Theme,
+    };
+
+    use super::{from_motion, trash_motion, yank_motion};
+
+    fn make_app(cursor: usize) -> App {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, current_id, _) =
+            crate::update::app::get_focused_directory_buffer_ids(window).unwrap();
+
+        let lines = ["alpha", "beta", "bravo", "charlie", "delta"]
+            .map(BufferLine::from)
+            .to_vec();
+        app.contents.buffers.insert(
+            current_id,
+            Buffer::Directory(DirectoryBuffer {
+                path: PathBuf::from("/range"),
+                buffer: TextBuffer::from_lines(lines),
+                ..Default::default()
+            }),
+        );
+
+        app.current_window_mut()
+            .expect("test requires current tab")
+            .focused_viewport_mut()
+            .cursor
+            .vertical_index = cursor;
+
+        app
+    }
+
+    fn current_lines(app: &App) -> Vec<String> {
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Directory(it)) => it
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn yanked(actions: &[Action]) -> Vec<PathBuf> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Task(Task::YankPath(entry)) => Some(entry.target.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_motion_covers_range_in_both_directions() {
+        let state = State::default();
+
+        let app = make_app(1);
+        assert_eq!(
+            from_motion(&app, &state, &2, &CursorDirection::Down),
+            Some((1, 3))
+        );
+
+        let app = make_app(3);
+        assert_eq!(
+            from_motion(&app, &state, &1, &CursorDirection::Top),
+            Some((0, 3))
+        );
+
+        let window = app.current_window().expect("test requires current tab");
+        assert_eq!(window.focused_viewport().cursor.vertical_index, 3);
+    }
+
+    #[test]
+    fn from_motion_resolves_finds_by_entry() {
+        let state = State::default();
+
+        let app = make_app(0);
+        assert_eq!(
+            from_motion(&app, &state, &1, &CursorDirection::FindForward('c')),
+            Some((0, 3))
+        );
+
+        let app = make_app(0);
+        assert_eq!(
+            from_motion(&app, &state, &1, &CursorDirection::TillForward('c')),
+            Some((0, 2))
+        );
+    }
+
+    #[test]
+    fn from_motion_resolves_search_by_register_direction() {
+        let mut state = State::default();
+        state.register.searched = Some((SearchDirection::Up, "ra".to_string()));
+
+        let app = make_app(4);
+        assert_eq!(
+            from_motion(&app, &state, &1, &CursorDirection::Search(Search::Next)),
+            Some((2, 4))
+        );
+
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Directory(it)) => assert!(it
+                .buffer
+                .lines
+                .iter()
+                .all(|line| line.search_char_position.is_none())),
+            _ => panic!("expected directory buffer"),
+        }
+    }
+
+    #[test]
+    fn yank_motion_yanks_entries_in_range() {
+        let app = make_app(0);
+        let mut state = State::default();
+
+        let actions = yank_motion(&app, &mut state, &1, &CursorDirection::FindForward('b'));
+
+        assert_eq!(
+            yanked(&actions),
+            vec![PathBuf::from("/range/alpha"), PathBuf::from("/range/beta")]
+        );
+        assert_eq!(state.modes.current, Mode::Navigation);
+        assert_eq!(current_lines(&app).len(), 5);
+    }
+
+    #[test]
+    fn trash_motion_removes_range_and_enters_normal() {
+        let mut app = make_app(2);
+        let mut state = State::default();
+
+        trash_motion(
+            &mut app,
+            &mut state,
+            &1,
+            &CursorDirection::Bottom,
+            &Theme::default(),
+            None,
+        )
+        .expect("trash must succeed");
+
+        assert_eq!(state.modes.current, Mode::Normal);
+        assert_eq!(current_lines(&app), vec!["alpha", "beta"]);
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum RenameConflict {
+    DuplicateTarget(PathBuf, Vec<PathBuf>),
+    SourceMissing(PathBuf),
+    TargetExists(PathBuf, PathBuf),
+}
+
+impl Display for RenameConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameConflict::DuplicateTarget(target, sources) => write!(
+                f,
+                "{} is the target of multiple renames: {}",
+                target.display(),
+                sources
+                    .iter()
+                    .map(|source| source.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            RenameConflict::SourceMissing(source) => {
+                write!(f, "{} does not exist anymore", source.display())
+            }
+            RenameConflict::TargetExists(source, target) => write!(
+                f,
+                "renaming {} failed: {} exists already",
+                source.display(),
+                target.display()
+            ),
+        }
+    }
+}
+
+pub fn plan(
+    renames: Vec<(PathBuf, PathBuf)>,
+    exists: impl Fn(&Path) -> bool,
+) -> Result<Vec<(PathBuf, PathBuf)>, Vec<RenameConflict>> {
+    let renames: Vec<_> = renames
+        .into_iter()
+        .filter(|(source, target)| source != target)
+        .collect();
+
+    let conflicts = validate(&renames, &exists);
+    if !conflicts.is_empty() {
+        return Err(conflicts);
+    }
+
+    Ok(order(renames, &exists))
+}
+
+fn validate(
+    renames: &[(PathBuf, PathBuf)],
+    exists: &impl Fn(&Path) -> bool,
+) -> Vec<RenameConflict> {
+    let sources: HashSet<_> = renames.iter().map(|(source, _)| source).collect();
+
+    let mut sources_by_target: Vec<(&PathBuf, Vec<PathBuf>)> = Vec::new();
+    for (source, target) in renames {
+        match sources_by_target.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, sources)) => sources.push(source.clone()),
+            None => sources_by_target.push((target, vec![source.clone()])),
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for (target, sources) in sources_by_target {
+        if sources.len() > 1 {
+            conflicts.push(RenameConflict::DuplicateTarget(target.clone(), sources));
+        }
+    }
+
+    for (source, target) in renames {
+        if !exists(source) {
+            conflicts.push(RenameConflict::SourceMissing(source.clone()));
+        } else if exists(target) && !sources.contains(target) {
+            conflicts.push(RenameConflict::TargetExists(source.clone(), target.clone()));
+        }
+    }
+
+    conflicts
+}
+
+fn order(
+    renames: Vec<(PathBuf, PathBuf)>,
+    exists: &impl Fn(&Path) -> bool,
+) -> Vec<(PathBuf, PathBuf)> {
+    let reserved: HashSet<_> = renames
+        .iter()
+        .flat_map(|(source, target)| [source.clone(), target.clone()])
+        .collect();
+
+    let mut pending: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let mut sequence = Vec::new();
+    for (source, target) in renames {
+        sequence.push(source.clone());
+        pending.insert(source, target);
+    }
+
+    let mut steps = Vec::new();
+    while !sequence.is_empty() {
+        let ready = sequence.iter().position(|source| {
+            pending
+                .get(source)
+                .is_some_and(|target| !pending.contains_key(target))
+        });
+
+        match ready {
+            Some(index) => {
+                let source = sequence.remove(index);
+                if let Some(target) = pending.remove(&source) {
+                    steps.push((source, target));
+                }
+            }
+            None => {
+                let source = sequence.remove(0);
+                if let Some(target) = pending.remove(&source) {
+                    let temporary = temporary_path(&source, &reserved, exists);
+                    steps.push((source, temporary.clone()));
+
+                    sequence.push(temporary.clone());
+                    pending.insert(temporary, target);
+                }
+            }
+        }
+    }
+
+    steps
+}
+
+fn temporary_path(
+    source: &Path,
+    reserved: &HashSet<PathBuf>,
+    exists: &impl Fn(&Path) -> bool,
+) -> PathBuf {
+    let name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut index = 0;
+    loop {
+        let candidate = source.with_file_name(format!(".{}.yeet-rename-{}", name, index));
+        if !reserved.contains(&candidate) && !exists(&candidate) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashSet,
+        path::{Path, PathBuf},
+    };
+
+    use super::{plan, RenameConflict};
+
+    fn existing(paths: &[&str]) -> impl Fn(&Path) -> bool {
+        let paths: HashSet<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        move |path: &Path| paths.contains(path)
+    }
+
+    fn rename(source: &str, target: &str) -> (PathBuf, PathBuf) {
+        (PathBuf::from(source), PathBuf::from(target))
+    }
+
+    fn simulate(steps: &[(PathBuf, PathBuf)], files: &[(&str, &str)]) -> Vec<(String, String)> {
+        let mut state: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+
+        for (source, target) in steps {
+            assert!(
+                !state.iter().any(|(path, _)| path == target),
+                "target {:?} exists while renaming {:?}",
+                target,
+                source
+            );
+
+            let entry = state
+                .iter_mut()
+                .find(|(path, _)| path == source)
+                .expect("source must exist");
+            entry.0 = target.clone();
+        }
+
+        let mut result: Vec<_> = state
+            .into_iter()
+            .map(|(path, content)| (path.to_string_lossy().to_string(), content))
+            .collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn plan_keeps_independent_renames() {
+        let steps = plan(
+            vec![rename("/d/a", "/d/x"), rename("/d/b", "/d/y")],
+            existing(&["/d/a", "/d/b"]),
+        )
+        .expect("plan must succeed");
+
+        assert_eq!(steps.len(), 2);
+        assert!(steps.contains(&rename("/d/a", "/d/x")));
+        assert!(steps.contains(&rename("/d/b", "/d/y")));
+    }
+
+    #[test]
+    fn plan_skips_identity_renames() {
+        let steps =
+            plan(vec![rename("/d/a", "/d/a")], existing(&["/d/a"])).expect("plan must succeed");
+
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn plan_orders_rename_chains() {
+        let steps = plan(
+            vec![rename("/d/a", "/d/b"), rename("/d/b", "/d/c")],
+            existing(&["/d/a", "/d/b"]),
+        )
+        .expect("plan must succeed");
+
+        assert_eq!(steps, vec![rename("/d/b", "/d/c"), rename("/d/a", "/d/b")]);
+    }
+
+    #[test]
+    fn plan_resolves_swaps_with_temporary_name() {
+        let steps = plan(
+            vec![rename("/d/a", "/d/b"), rename("/d/b", "/d/a")],
+            existing(&["/d/a", "/d/b"]),
+        )
+        .expect("plan must succeed");
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            simulate(&steps, &[("/d/a", "A"), ("/d/b", "B")]),
+            vec![
+                ("/d/a".to_string(), "B".to_string()),
+                ("/d/b".to_string(), "A".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_resolves_rotations() {
+        let steps = plan(
+            vec![
+                rename("/d/a", "/d/b"),
+                rename("/d/b", "/d/c"),
+                rename("/d/c", "/d/a"),
+            ],
+            existing(&["/d/a", "/d/b", "/d/c"]),
+        )
+        .expect("plan must succeed");
+
+        assert_eq!(steps.len(), 4);
+        assert_eq!(
+            simulate(&steps, &[("/d/a", "A"), ("/d/b", "B"), ("/d/c", "C")]),
+            vec![
+                ("/d/a".to_string(), "C".to_string()),
+                ("/d/b".to_string(), "A".to_string()),
+                ("/d/c".to_string(), "B".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_avoids_existing_temporary_names() {
+        let steps = plan(
+            vec![rename("/d/a", "/d/b"), rename("/d/b", "/d/a")],
+            existing(&["/d/a", "/d/b", "/d/.a.yeet-rename-0"]),
+        )
+        .expect("plan must succeed");
+
+        assert_eq!(steps[0], rename("/d/a", "/d/.a.yeet-rename-1"));
+    }
+
+    #[test]
+    fn plan_refuses_duplicate_targets() {
+        let conflicts = plan(
+            vec![rename("/d/a", "/d/x"), rename("/d/b", "/d/x")],
+            existing(&["/d/a", "/d/b"]),
+        )
+        .expect_err("plan must fail");
+
+        assert_eq!(
+            conflicts,
+            vec![RenameConflict::DuplicateTarget(
+                PathBuf::from("/d/x"),
+                vec![PathBuf::from("/d/a"), PathBuf::from("/d/b")]
+            )]
+        );
+    }
+
+    #[test]
+    fn plan_refuses_existing_targets_that_are_not_renamed() {
+        let conflicts = plan(vec![rename("/d/a", "/d/b")], existing(&["/d/a", "/d/b"]))
+            .expect_err("plan must fail");
+
+        assert_eq!(
+            conflicts,
+            vec![RenameConflict::TargetExists(
+                PathBuf::from("/d/a"),
+                PathBuf::from("/d/b")
+            )]
+        );
+    }
+
+    #[test]
+    fn plan_refuses_missing_sources() {
+        let conflicts =
+            plan(vec![rename("/d/a", "/d/b")], existing(&[])).expect_err("plan must fail");
+
+        assert_eq!(
+            conflicts,
+            vec![RenameConflict::SourceMissing(PathBuf::from("/d/a"))]
+        );
+    }
+}
@@ -1,3 +1,8 @@
+use std::{
+    mem,
+    path::{Path, PathBuf},
+};
+
 use yeet_buffer::{
     message::BufferMessage,
     model::{
@@ -6,33 +11,74 @@ use yeet_buffer::{
         BufferResult, Mode,
     },
 };
+use yeet_keymap::message::{KeymapMessage, PrintContent, QuitMode};
 
 use crate::{
-    action::Action,
+    action::{self, Action},
     error::AppError,
-    model::{junkyard::JunkYard, App, Buffer, Contents, DirectoryBuffer, Window},
+    event::Message,
+    model::{junkyard::JunkYard, App, Buffer, Contents, DirectoryBuffer, EditBuffer, Window},
     task::Task,
 };
 
-use super::{app, junkyard::trash_to_junkyard};
+use super::{app, junkyard::trash_paths, rename};
+
+#[derive(Debug, PartialEq)]
+pub enum Operation {
+    Add(PathBuf),
+    Delete(PathBuf),
+    Rename(PathBuf, PathBuf),
+    Trash(PathBuf),
+}
 
 #[tracing::instrument(skip(app))]
 pub fn current(app: &mut App, junk: &mut JunkYard, mode: &Mode) -> Result<Vec<Action>, AppError> {
     let (window, contents) = app.current_window_and_contents_mut()?;
     let (vp, buffer) = match app::get_focused_current_mut(window, contents)? {
         (vp, Buffer::Directory(it)) => (vp, it),
+        (_vp, Buffer::Edit(it)) => return Ok(save_edit_buffer(it)),
         (_vp, Buffer::Image(_))
         | (_vp, Buffer::Content(_))
         | (_vp, Buffer::PathReference(_))
         | (_vp, Buffer::Tasks(_))
         | (_vp, Buffer::QuickFix(_))
-        | (_vp, Buffer::Help(_))
+        | (_vp, Buffer::Diff(_) | Buffer::Help(_))
         | (_vp, Buffer::Empty) => return Ok(Vec::new()),
     };
 
     Ok(save_directory_buffer(Some(vp), buffer, junk, mode))
 }
 
+pub fn current_and_close(
+    app: &mut App,
+    junk: &mut JunkYard,
+    mode: &Mode,
+) -> Result<Vec<Action>, AppError> {
+    let actions = current(app, junk, mode)?;
+
+    // NOTE: the window is closed once the write succeeded, see complete
+    let (window, contents) = app.current_window_and_contents_mut()?;
+    if let (_, Buffer::Edit(it)) = app::get_focused_current_mut(window, contents)? {
+        it.close_after_save = it.saving.is_some();
+    }
+
+    Ok(actions)
+}
+
+pub fn buffer(
+    window: &mut Window,
+    contents: &mut Contents,
+    junk: &mut JunkYard,
+    mode: &Mode,
+    buffer_id: usize,
+) -> Vec<Action> {
+    let vp = app::get_viewport_by_buffer_id_mut(window, buffer_id);
+    match contents.buffers.get_mut(&buffer_id) {
+        Some(Buffer::Directory(dir)) => save_directory_buffer(vp, dir, junk, mode),
+        _ => Vec::new(),
+    }
+}
+
 pub fn all(
     window: &mut Window,
     contents: &mut Contents,
@@ -55,12 +101,109 @@ pub fn all(
     actions
 }
 
+fn save_edit_buffer(buffer: &mut EditBuffer) -> Vec<Action> {
+    let content = buffer.to_file_content();
+    buffer.saving = Some(content.clone());
+
+    vec![Action::Task(Task::SaveEditContent(
+        buffer.path.clone(),
+        content,
+    ))]
+}
+
+pub fn complete(
+    contents: &mut Contents,
+    mode: &Mode,
+    identifier: &str,
+    succeeded: bool,
+) -> Vec<usize> {
+    let mut closing = Vec::new();
+    for (id, buffer) in contents.buffers.iter_mut() {
+        let buffer = match buffer {
+            Buffer::Edit(it) => it,
+            _ => continue,
+        };
+
+        let is_saved_by_task = buffer.saving.as_ref().is_some_and(|content| {
+            Task::SaveEditContent(buffer.path.clone(), content.clone()).to_string() == identifier
+        });
+        if !is_saved_by_task {
+            continue;
+        }
+
+        let content = buffer.saving.take();
+        let close_after_save = mem::take(&mut buffer.close_after_save);
+        if succeeded && content.is_some_and(|content| content == buffer.to_file_content()) {
+            yeet_buffer::update(
+                None,
+                mode,
+                &mut buffer.buffer,
+                std::slice::from_ref(&BufferMessage::SaveBuffer),
+            );
+
+            if close_after_save {
+                closing.push(*id);
+            }
+        }
+    }
+    closing
+}
+
+pub fn close(app: &mut App, mode: &Mode, buffer_ids: Vec<usize>) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for buffer_id in buffer_ids {
+        let current_tab_id = app.current_tab_id;
+        let Some((tab_id, window)) = app
+            .tabs
+            .iter_mut()
+            .find(|(_, window)| window.buffer_ids().contains(&buffer_id))
+        else {
+            continue;
+        };
+
+        let is_focused =
+            *tab_id == current_tab_id && window.focused_viewport().buffer_id == buffer_id;
+
+        match mem::take(window).close_buffer(buffer_id) {
+            Ok((kept, _)) => {
+                *window = kept;
+
+                // NOTE: the mode may have changed while saving, keep e.g. a started command line
+                if is_focused && matches!(mode, Mode::Insert | Mode::Normal) {
+                    actions.push(Action::EmitMessages(vec![Message::Keymap(
+                        KeymapMessage::Buffer(BufferMessage::ChangeMode(
+                            mode.clone(),
+                            Mode::Navigation,
+                        )),
+                    )]));
+                }
+            }
+            Err(leaf) => {
+                *window = leaf;
+                actions.push(action::emit_keymap(KeymapMessage::Quit(
+                    QuitMode::FailOnRunningTasks,
+                )));
+            }
+        }
+    }
+    actions
+}
+
 fn save_directory_buffer(
     viewport: Option<&mut ViewPort>,
     buffer: &mut DirectoryBuffer,
     junk: &mut JunkYard,
     mode: &Mode,
 ) -> Vec<Action> {
+    let changes = consolidate_modifications(&buffer.buffer.uncommitted_changes());
+    let operations = get_operations(&buffer.path, &changes);
+    let mut renames = match rename::plan(get_renames(&operations), |path| {
+        path.symlink_metadata().is_ok()
+    }) {
+        Ok(renames) => renames,
+        Err(conflicts) => return print_conflicts(&buffer.path, conflicts),
+    };
+
     let selection = viewport
         .as_deref()
         .map(|vp| vp.cursor.vertical_index)
@@ -106,47 +249,179 @@ fn save_directory_buffer(
     let mut actions = Vec::new();
     for br in result {
         if let BufferResult::Changes(modifications) = br {
-            let path = &buffer.path;
+            let changes = consolidate_modifications(&modifications);
             let mut trashes = Vec::new();
-            for modification in consolidate_modifications(&modifications) {
-                match modification {
-                    BufferChanged::LineAdded(_, name) => {
-                        if !name.is_empty() {
-                            actions.push(Action::Task(Task::AddPath(
-                                path.join(name.to_stripped_string()),
-                            )))
-                        }
-                    }
-                    BufferChanged::LineRemoved(_, name) => {
-                        trashes.push(path.join(name.to_stripped_string()));
-                    }
-                    BufferChanged::Content(_, old_name, new_name) => {
-                        let task = if new_name.is_empty() {
-                            Task::DeletePath(path.join(old_name.to_stripped_string()))
-                        } else {
-                            Task::RenamePath(
-                                path.join(old_name.to_stripped_string()),
-                                path.join(new_name.to_stripped_string()),
-                            )
-                        };
-                        actions.push(Action::Task(task));
-                    }
+            for operation in get_operations(&buffer.path, &changes) {
+                match operation {
+                    Operation::Add(path) => actions.push(Action::Task(Task::AddPath(path))),
+                    Operation::Delete(path) => actions.push(Action::Task(Task::DeletePath(path))),
+                    Operation::Rename(_, _) => {}
+                    Operation::Trash(path) => trashes.push(path),
                 }
             }
 
+            if !renames.is_empty() {
+                actions.push(Action::Task(Task::RenamePaths(std::mem::take(
+                    &mut renames,
+                ))));
+            }
+
             if !trashes.is_empty() {
-                let (transaction, obsolete) = trash_to_junkyard(junk, trashes);
-                for entry in transaction.entries {
-                    actions.push(Action::Task(Task::TrashPath(entry)));
-                }
+                actions.extend(trash_paths(junk, trashes));
+            }
+        }
+    }
+    actions
+}
 
-                if let Some(obsolete) = obsolete {
-                    for entry in obsolete.entries {
-                        actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
-                    }
+pub fn get_operations(path: &Path, changes: &[BufferChanged]) -> Vec<Operation> {
+    let mut operations = Vec::new();
+    for change in changes {
+        match change {
+            BufferChanged::LineAdded(_, name) => {
+                if !name.is_empty() {
+                    operations.push(Operation::Add(path.join(name.to_stripped_string())));
+                }
+            }
+            BufferChanged::LineRemoved(_, name) => {
+                operations.push(Operation::Trash(path.join(name.to_stripped_string())));
+            }
+            BufferChanged::Content(_, old_name, new_name) => {
+                let old_path = path.join(old_name.to_stripped_string());
+                if new_name.is_empty() {
+                    operations.push(Operation::Delete(old_path));
+                } else {
+                    operations.push(Operation::Rename(
+                        old_path,
+                        path.join(new_name.to_stripped_string()),
+                    ));
                 }
             }
         }
     }
-    actions
+    operations
+}
+
+pub fn get_renames(operations: &[Operation]) -> Vec<(PathBuf, PathBuf)> {
+    operations
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::Rename(source, target) => Some((source.clone(), target.clone())),
+            Operation::Add(_) | Operation::Delete(_) | Operation::Trash(_) => None,
+        })
+        .collect()
+}
+
+fn print_conflicts(path: &Path, conflicts: Vec<rename::RenameConflict>) -> Vec<Action> {
+    let mut content = vec![PrintContent::Error(format!(
+        "Saving {} aborted, resolve the following rename conflicts first:",
+        path.display()
+    ))];
+    content.extend(
+        conflicts
+            .iter()
+            .map(|conflict| PrintContent::Error(conflict.to_string())),
+    );
+
+    vec![action::emit_keymap(KeymapMessage::Print(content))]
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use yeet_buffer::{
+        message::{BufferMessage, LineDirection, TextModification},
+        model::{viewport::ViewPort, BufferLine, Mode, TextBuffer},
+    };
+
+    use crate::{
+        action::Action,
+        model::{Buffer, Contents, EditBuffer},
+        task::Task,
+    };
+
+    use super::{complete, save_edit_buffer};
+
+    fn make_contents() -> Contents {
+        let mut buffer = TextBuffer::from_lines(vec![BufferLine::from("first")]);
+        let messages = [
+            BufferMessage::Modification(1, TextModification::InsertNewLine(LineDirection::Down)),
+            BufferMessage::Modification(1, TextModification::Insert("second".to_string())),
+        ];
+        yeet_buffer::update(
+            Some(&mut ViewPort::default()),
+            &Mode::Normal,
+            &mut buffer,
+            &messages,
+        );
+
+        Contents {
+            buffers: HashMap::from([(
+                1,
+                Buffer::Edit(EditBuffer {
+                    buffer,
+                    close_after_save: false,
+                    crlf: false,
+                    path: PathBuf::from("/tmp/a.txt"),
+                    saving: None,
+                    trailing_newline: true,
+                }),
+            )]),
+            latest_buffer_id: 1,
+        }
+    }
+
+    fn save(contents: &mut Contents) -> String {
+        match contents.buffers.get_mut(&1) {
+            Some(Buffer::Edit(it)) => match save_edit_buffer(it).as_slice() {
+                [Action::Task(task)] => task.to_string(),
+                _ => panic!("expected save task"),
+            },
+            _ => panic!("expected edit buffer"),
+        }
+    }
+
+    fn has_unsaved_changes(contents: &Contents) -> bool {
+        match contents.buffers.get(&1) {
+            Some(Buffer::Edit(it)) => it.buffer.has_unsaved_changes(),
+            _ => panic!("expected edit buffer"),
+        }
+    }
+
+    #[test]
+    fn save_edit_buffer_keeps_changes_until_the_task_succeeded() {
+        let mut contents = make_contents();
+
+        let identifier = save(&mut contents);
+        assert!(has_unsaved_changes(&contents));
+
+        complete(&mut contents, &Mode::Normal, &identifier, true);
+        assert!(!has_unsaved_changes(&contents));
+    }
+
+    #[test]
+    fn failed_save_keeps_changes() {
+        let mut contents = make_contents();
+
+        let identifier = save(&mut contents);
+        complete(&mut contents, &Mode::Normal, &identifier, false);
+
+        assert!(has_unsaved_changes(&contents));
+        assert!(matches!(
+            contents.buffers.get(&1),
+            Some(Buffer::Edit(EditBuffer { saving: None, .. }))
+        ));
+    }
+
+    #[test]
+    fn unrelated_task_does_not_mark_buffer_saved() {
+        let mut contents = make_contents();
+
+        save(&mut contents);
+        let identifier = Task::LoadEditContent(PathBuf::from("/tmp/a.txt")).to_string();
+        complete(&mut contents, &Mode::Normal, &identifier, true);
+
+        assert!(has_unsaved_changes(&contents));
+    }
 }
@@ -13,33 +13,46 @@ pub fn buffers(buffers: Vec<&mut Buffer>, search: Option<String>) {
 
     for buffer in buffers {
         let buffer = match buffer {
-            Buffer::Directory(it) => it,
+            Buffer::Directory(it) => &mut it.buffer,
+            Buffer::Edit(it) => &mut it.buffer,
             Buffer::Image(_) => continue,
             Buffer::Content(_) => continue,
             Buffer::PathReference(_) => continue,
             Buffer::Tasks(_) => continue,
             Buffer::QuickFix(_) => continue,
-            Buffer::Help(_) => continue,
+            Buffer::Diff(_) | Buffer::Help(_) => continue,
             Buffer::Empty => continue,
         };
-        set_search_char_positions(&mut buffer.buffer, search.as_str());
+        set_search_char_positions(buffer, search.as_str());
+    }
+}
+
+pub fn text_buffer(buffer: &mut TextBuffer, search: Option<&str>) {
+    match search {
+        Some(search) => set_search_char_positions(buffer, search),
+        None => {
+            for line in &mut buffer.lines {
+                line.search_char_position = None;
+            }
+        }
     }
 }
 
 pub fn clear(buffers: Vec<&mut Buffer>) {
     for buffer in buffers {
         let buffer = match buffer {
-            Buffer::Directory(it) => it,
+            Buffer::Directory(it) => &mut it.buffer,
+            Buffer::Edit(it) => &mut it.buffer,
             Buffer::Image(_) => continue,
             Buffer::Content(_) => continue,
             Buffer::PathReference(_) => continue,
             Buffer::Tasks(_) => continue,
             Buffer::QuickFix(_) => continue,
-            Buffer::Help(_) => continue,
+            Buffer::Diff(_) | Buffer::Help(_) => continue,
             Buffer::Empty => continue,
         };
 
-        for line in &mut buffer.buffer.lines {
+        for line in &mut buffer.lines {
             line.search_char_position = None;
         }
     }
@@ -4,11 +4,15 @@ use yeet_buffer::model::{BufferLine, Sign, SignIdentifier};
 
 use crate::{
     model::{
+        git::{
+            GitStatus, GIT_CONFLICTED_SIGN_ID, GIT_IGNORED_SIGN_ID, GIT_MODIFIED_SIGN_ID,
+            GIT_SIGN_IDS, GIT_STAGED_SIGN_ID, GIT_UNTRACKED_SIGN_ID,
+        },
         mark::{Marks, MARK_SIGN_ID},
         qfix::{QuickFix, QFIX_SIGN_ID},
         Buffer, DirectoryBuffer,
     },
-    theme::Theme,
+    theme::{tokens, Theme},
 };
 
 pub fn set_sign_if_qfix(qfix: &QuickFix, bl: &mut BufferLine, path: &Path, theme: &Theme) {
@@ -70,10 +74,59 @@ pub fn generate_sign(sign_id: SignIdentifier, theme: &Theme) -> Option<Sign> {
             style: theme.sign_mark_style(),
             priority: 0,
         }),
+        GIT_MODIFIED_SIGN_ID => Some(generate_git_sign(
+            sign_id,
+            'M',
+            tokens::SIGN_GIT_MODIFIED,
+            theme,
+        )),
+        GIT_STAGED_SIGN_ID => Some(generate_git_sign(
+            sign_id,
+            'S',
+            tokens::SIGN_GIT_STAGED,
+            theme,
+        )),
+        GIT_UNTRACKED_SIGN_ID => Some(generate_git_sign(
+            sign_id,
+            '?',
+            tokens::SIGN_GIT_UNTRACKED,
+            theme,
+        )),
+        GIT_IGNORED_SIGN_ID => Some(generate_git_sign(
+            sign_id,
+            '!',
+            tokens::SIGN_GIT_IGNORED,
+            theme,
+        )),
+        GIT_CONFLICTED_SIGN_ID => Some(generate_git_sign(
+            sign_id,
+            'U',
+            tokens::SIGN_GIT_CONFLICTED,
+            theme,
+        )),
         _ => None,
     }
 }
 
+fn generate_git_sign(sign_id: SignIdentifier, content: char, token: &str, theme: &Theme) -> Sign {
+    Sign {
+        id: sign_id,
+        content,
+        style: theme.sign_style(token),
+        priority: 0,
+    }
+}
+
+pub fn set_sign_if_git(status: &GitStatus, bl: &mut BufferLine, path: &Path, theme: &Theme) {
+    for sign_id in GIT_SIGN_IDS {
+        unset(bl, sign_id);
+    }
+
+    if let Some(entry) = status.entries.get(path) {
+        set(bl, entry.sign_id(), theme);
+    }
+}
+
 pub fn unset_sign_on_all_buffers(buffers: Vec<&mut Buffer>, sign_id: SignIdentifier) {
     for buffer in buffers {
         let buffer = match buffer {
@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, time::Instant};
 
 use tokio_util::sync::CancellationToken;
 
@@ -6,7 +6,8 @@ use yeet_lua::LuaConfiguration;
 
 use crate::{
     action::Action,
-    model::{Contents, CurrentTask, Tasks, Window},
+    model::{Contents, CurrentTask, TaskProgress, Tasks, Window},
+    task::TaskKind,
 };
 
 use super::command::task::refresh_tasks_buffer;
@@ -16,6 +17,7 @@ pub fn add(
     window: &mut Window,
     contents: &mut Contents,
     identifier: String,
+    kind: TaskKind,
     cancellation: CancellationToken,
     lua: Option<&LuaConfiguration>,
 ) -> Vec<Action> {
@@ -26,7 +28,9 @@ pub fn add(
         CurrentTask {
             token: cancellation,
             id,
+            kind,
             external_id: identifier,
+            progress: None,
         },
     ) {
         replaced_task.token.cancel();
@@ -60,6 +64,39 @@ fn next_id(tasks: &mut Tasks) -> u16 {
     next_id
 }
 
+pub fn progress(
+    tasks: &mut Tasks,
+    window: &mut Window,
+    contents: &mut Contents,
+    identifier: String,
+    done: u64,
+    total: u64,
+    lua: Option<&LuaConfiguration>,
+) -> Vec<Action> {
+    let task = match tasks.running.get_mut(&identifier) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    match task.progress.as_mut() {
+        Some(progress) => {
+            progress.done = done;
+            progress.total = total;
+        }
+        None => {
+            task.progress = Some(TaskProgress {
+                done,
+                started: Instant::now(),
+                total,
+            });
+        }
+    }
+
+    refresh_tasks_buffer(window, contents, tasks, lua);
+
+    Vec::new()
+}
+
 pub fn remove(
     tasks: &mut Tasks,
     window: &mut Window,
@@ -81,9 +118,10 @@ mod test {
     use tokio_util::sync::CancellationToken;
 
     use crate::model::{App, Buffer, CurrentTask, Tasks};
+    use crate::task::TaskKind;
     use crate::update::command::task::open;
 
-    use super::{add, remove};
+    use super::{add, progress, remove};
 
     fn make_tasks_2() -> Tasks {
         let mut tasks = Tasks::default();
@@ -92,6 +130,8 @@ mod test {
             CurrentTask {
                 external_id: "rg foo".to_string(),
                 id: 1,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -100,6 +140,8 @@ mod test {
             CurrentTask {
                 external_id: "fd bar".to_string(),
                 id: 5,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -113,6 +155,8 @@ mod test {
             CurrentTask {
                 external_id: "rg foo".to_string(),
                 id: 1,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -121,6 +165,8 @@ mod test {
             CurrentTask {
                 external_id: "fd bar".to_string(),
                 id: 5,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -129,6 +175,8 @@ mod test {
             CurrentTask {
                 external_id: "grep baz".to_string(),
                 id: 10,
+                kind: TaskKind::Other,
+                progress: None,
                 token: CancellationToken::new(),
             },
         );
@@ -170,6 +218,7 @@ mod test {
             window,
             contents,
             "grep-3".to_string(),
+            TaskKind::Other,
             CancellationToken::new(),
             None,
         );
@@ -195,6 +244,55 @@ mod test {
         assert_eq!(get_task_line_content(&app, 0), "5    fd bar");
     }
 
+    #[test]
+    fn progress_updates_task_and_refreshes_buffer() {
+        let mut tasks = make_tasks_2();
+        let mut app = App::default();
+        open(&mut app, None, &tasks);
+
+        let (window, contents) = app
+            .current_window_and_contents_mut()
+            .expect("test requires current tab");
+        progress(
+            &mut tasks,
+            window,
+            contents,
+            "fd-2".to_string(),
+            512,
+            1024,
+            None,
+        );
+
+        let task_progress = tasks.running["fd-2"]
+            .progress
+            .as_ref()
+            .expect("progress must be set");
+        assert_eq!(task_progress.done, 512);
+        assert_eq!(task_progress.total, 1024);
+        assert!(get_task_line_content(&app, 1).starts_with("5    fd bar 50% 512 B/1.0 KiB"));
+    }
+
+    #[test]
+    fn progress_for_unknown_task_is_ignored() {
+        let mut tasks = make_tasks_2();
+        let mut app = App::default();
+
+        let (window, contents) = app
+            .current_window_and_contents_mut()
+            .expect("test requires current tab");
+        progress(
+            &mut tasks,
+            window,
+            contents,
+            "unknown".to_string(),
+            1,
+            2,
+            None,
+        );
+
+        assert!(tasks.running.values().all(|task| task.progress.is_none()));
+    }
+
     #[test]
     fn remove_clamps_cursor_when_past_end() {
         let tasks = make_tasks_2();
@@ -236,6 +334,7 @@ mod test {
             window,
             contents,
             "rg-1".to_string(),
+            TaskKind::Other,
             CancellationToken::new(),
             None,
         );
@@ -271,6 +370,7 @@ mod test {
             window,
             contents,
             "grep-3".to_string(),
+            TaskKind::Other,
             CancellationToken::new(),
             None,
         );
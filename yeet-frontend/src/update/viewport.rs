@@ -59,6 +59,24 @@ pub fn relocate(
             );
             Ok(Vec::new())
         }
+        Buffer::Diff(diff_buf) => {
+            yeet_buffer::update(
+                Some(vp),
+                mode,
+                &mut diff_buf.buffer,
+                std::slice::from_ref(&msg),
+            );
+            Ok(Vec::new())
+        }
+        Buffer::Edit(edit_buf) => {
+            yeet_buffer::update(
+                Some(vp),
+                mode,
+                &mut edit_buf.buffer,
+                std::slice::from_ref(&msg),
+            );
+            Ok(Vec::new())
+        }
         Buffer::Help(help_buf) => {
             yeet_buffer::update(
                 Some(vp),
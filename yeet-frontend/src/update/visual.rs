@@ -0,0 +1,189 @@
+use std::path::PathBuf;
+
+use yeet_buffer::model::Mode;
+use yeet_lua::LuaConfiguration;
+
+use crate::{
+    action::Action,
+    error::AppError,
+    model::{App, State},
+    theme::Theme,
+};
+
+use super::{junkyard, mode, qfix, range};
+
+pub fn get_selected_paths(app: &App) -> Option<Vec<PathBuf>> {
+    let window = app.current_window().ok()?;
+    let (start, end) = window.focused_viewport().get_visual_range()?;
+
+    range::get_paths(app, start, end)
+}
+
+pub fn trash(
+    app: &mut App,
+    state: &mut State,
+    theme: &Theme,
+    lua: Option<&LuaConfiguration>,
+) -> Result<Vec<Action>, AppError> {
+    let window = app.current_window()?;
+    match window.focused_viewport().get_visual_range() {
+        Some(selection) => range::trash(app, state, &Mode::Visual, selection, theme, lua),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn yank(
+    app: &mut App,
+    state: &mut State,
+    theme: &Theme,
+    lua: Option<&LuaConfiguration>,
+) -> Result<Vec<Action>, AppError> {
+    let paths = get_selected_paths(app).unwrap_or_default();
+
+    let mut actions = mode::change(app, state, &Mode::Visual, &Mode::Navigation, theme, lua)?;
+    actions.extend(junkyard::yank_paths(&mut state.junk, paths));
+
+    Ok(actions)
+}
+
+pub fn toggle_quickfix(
+    app: &mut App,
+    state: &mut State,
+    theme: &Theme,
+    lua: Option<&LuaConfiguration>,
+) -> Result<Vec<Action>, AppError> {
+    let paths = get_selected_paths(app).unwrap_or_default();
+
+    let mut actions = mode::change(app, state, &Mode::Visual, &Mode::Navigation, theme, lua)?;
+    actions.extend(qfix::toggle_paths(app, &mut state.qfix, paths, theme));
+
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yeet_buffer::model::{BufferLine, Mode, TextBuffer};
+
+    use crate::{
+        action::Action,
+        model::{App, Buffer, DirectoryBuffer, State},
+        task::Task,
+        theme::Theme,
+    };
+
+    use super::{get_selected_paths, trash, yank};
+
+    fn make_app(anchor: Option<usize>, cursor: usize) -> App {
+        let mut app = App::default();
+        let window = app.current_window().expect("test requires current tab");
+        let (_, current_id, _) =
+            crate::update::app::get_focused_directory_buffer_ids(window).unwrap();
+
+        let lines = ["a", "b", "c", "d"].map(BufferLine::from).to_vec();
+        app.contents.buffers.insert(
+            current_id,
+            Buffer::Directory(DirectoryBuffer {
+                path: PathBuf::from("/visual"),
+                buffer: TextBuffer::from_lines(lines),
+                ..Default::default()
+            }),
+        );
+
+        let viewport = app
+            .current_window_mut()
+            .expect("test requires current tab")
+            .focused_viewport_mut();
+        viewport.visual_anchor = anchor;
+        viewport.cursor.vertical_index = cursor;
+
+        app
+    }
+
+    fn current_lines(app: &App) -> Vec<String> {
+        let window = app.current_window().expect("test requires current tab");
+        match app
+            .contents
+            .buffers
+            .get(&window.focused_viewport().buffer_id)
+        {
+            Some(Buffer::Directory(it)) => it
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn selected_paths_cover_range_in_both_directions() {
+        let app = make_app(Some(2), 1);
+        assert_eq!(
+            get_selected_paths(&app),
+            Some(vec![PathBuf::from("/visual/b"), PathBuf::from("/visual/c")])
+        );
+
+        let app = make_app(Some(1), 3);
+        assert_eq!(
+            get_selected_paths(&app),
+            Some(vec![
+                PathBuf::from("/visual/b"),
+                PathBuf::from("/visual/c"),
+                PathBuf::from("/visual/d")
+            ])
+        );
+    }
+
+    #[test]
+    fn selected_paths_none_without_anchor() {
+        let app = make_app(None, 1);
+        assert_eq!(get_selected_paths(&app), None);
+    }
+
+    #[test]
+    fn trash_removes_range_and_enters_normal() {
+        let mut app = make_app(Some(2), 1);
+        let mut state = State::default();
+        state.modes.current = Mode::Visual;
+
+        trash(&mut app, &mut state, &Theme::default(), None).expect("trash must succeed");
+
+        assert_eq!(state.modes.current, Mode::Normal);
+        assert_eq!(current_lines(&app), vec!["a", "d"]);
+
+        let window = app.current_window().expect("test requires current tab");
+        assert_eq!(window.focused_viewport().visual_anchor, None);
+    }
+
+    #[test]
+    fn yank_range_returns_to_navigation() {
+        let mut app = make_app(Some(0), 2);
+        let mut state = State::default();
+        state.modes.current = Mode::Visual;
+
+        let actions =
+            yank(&mut app, &mut state, &Theme::default(), None).expect("yank must succeed");
+
+        let yanked: Vec<_> = actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Task(Task::YankPath(entry)) => Some(entry.target.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            yanked,
+            vec![
+                PathBuf::from("/visual/a"),
+                PathBuf::from("/visual/b"),
+                PathBuf::from("/visual/c")
+            ]
+        );
+        assert_eq!(state.modes.current, Mode::Navigation);
+        assert_eq!(current_lines(&app), vec!["a", "b", "c", "d"]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use ratatui::{layout::Rect, Frame};
+use ratatui::{layout::Rect, widgets::Paragraph, Frame};
 use ratatui_image::Image;
 use yeet_buffer::{
     model::{viewport::ViewPort, Mode},
@@ -8,13 +8,16 @@ use yeet_buffer::{
 };
 
 use crate::{
-    model::{App, Buffer, DirectoryBuffer, SplitFocus, Window},
+    model::{
+        get_image_metadata_lines, get_image_panel_height, App, Buffer, DirectoryBuffer, SplitFocus,
+        Tasks, Window,
+    },
     theme::{tokens, Theme},
 };
 
 use super::statusline;
 
-pub fn view(mode: &Mode, app: &App, theme: &Theme, frame: &mut Frame) {
+pub fn view(mode: &Mode, app: &App, tasks: &Tasks, theme: &Theme, frame: &mut Frame) {
     let context = RenderContext {
         draw_borders: None,
         is_focused: true,
@@ -24,7 +27,15 @@ pub fn view(mode: &Mode, app: &App, theme: &Theme, frame: &mut Frame) {
         Ok(window) => window,
         Err(_) => return,
     };
-    render_window(mode, window, &app.contents.buffers, theme, frame, context);
+    render_window(
+        mode,
+        window,
+        &app.contents.buffers,
+        tasks,
+        theme,
+        frame,
+        context,
+    );
 }
 
 #[derive(Clone)]
@@ -38,6 +49,7 @@ fn render_window(
     mode: &Mode,
     window: &Window,
     buffers: &HashMap<usize, Buffer>,
+    tasks: &Tasks,
     theme: &Theme,
     frame: &mut Frame,
     context: RenderContext,
@@ -52,6 +64,7 @@ fn render_window(
                 mode,
                 first,
                 buffers,
+                tasks,
                 theme,
                 frame,
                 RenderContext {
@@ -63,6 +76,7 @@ fn render_window(
                 mode,
                 second,
                 buffers,
+                tasks,
                 theme,
                 frame,
                 RenderContext {
@@ -80,6 +94,7 @@ fn render_window(
                 mode,
                 first,
                 buffers,
+                tasks,
                 theme,
                 frame,
                 RenderContext {
@@ -92,6 +107,7 @@ fn render_window(
                 mode,
                 second,
                 buffers,
+                tasks,
                 theme,
                 frame,
                 RenderContext {
@@ -155,6 +171,7 @@ fn render_window(
 
                 statusline::view(
                     buffer,
+                    tasks,
                     &statusline_vp,
                     frame,
                     statusline_rect,
@@ -163,7 +180,11 @@ fn render_window(
                 );
             }
         }
-        Window::Tasks(vp) | Window::QuickFix(vp) | Window::Help(vp) => {
+        Window::Tasks(vp)
+        | Window::QuickFix(vp)
+        | Window::Diff(vp)
+        | Window::Edit(vp)
+        | Window::Help(vp) => {
             render_buffer_slot(
                 mode,
                 frame,
@@ -186,6 +207,7 @@ fn render_window(
 
                 statusline::view(
                     buffer,
+                    tasks,
                     &statusline_vp,
                     frame,
                     statusline_rect,
@@ -238,6 +260,12 @@ fn render_buffer_slot(
         Some(Buffer::QuickFix(qfix_buf)) => {
             buffer_view(&effective_vp, mode, &qfix_buf.buffer, &buffer_theme, frame);
         }
+        Some(Buffer::Diff(diff_buf)) => {
+            buffer_view(&effective_vp, mode, &diff_buf.buffer, &buffer_theme, frame);
+        }
+        Some(Buffer::Edit(buf)) => {
+            buffer_view(&effective_vp, mode, &buf.buffer, &buffer_theme, frame);
+        }
         Some(Buffer::Help(buf)) => {
             buffer_view(&effective_vp, mode, &buf.buffer, &buffer_theme, frame);
         }
@@ -249,14 +277,29 @@ fn render_buffer_slot(
             render_directory_buffer(mode, frame, &vp, &Default::default(), &buffer_theme);
         }
         Some(Buffer::Image(buffer)) => {
+            let panel_height = get_image_panel_height(buffer.image_metadata.as_deref());
             let rect = Rect {
                 x: viewport.x,
                 y: viewport.y,
                 width: viewport.width,
-                height: viewport.height,
+                height: viewport.height.saturating_sub(panel_height),
             };
 
             frame.render_widget(Image::new(&buffer.protocol), rect);
+
+            if let Some(metadata) = &buffer.image_metadata {
+                let panel_height = panel_height.min(viewport.height);
+                let rect = Rect {
+                    x: viewport.x,
+                    y: viewport.y + viewport.height - panel_height,
+                    width: viewport.width,
+                    height: panel_height,
+                };
+
+                let mut lines = vec![String::new()];
+                lines.extend(get_image_metadata_lines(metadata));
+                frame.render_widget(Paragraph::new(lines.join("\n")), rect);
+            }
         }
     }
 }
@@ -395,6 +438,7 @@ mod test {
                     &yeet_buffer::model::Mode::Navigation,
                     &window,
                     &buffers,
+                    &Tasks::default(),
                     &theme,
                     frame,
                     context,
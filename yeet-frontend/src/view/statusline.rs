@@ -10,12 +10,13 @@ use ratatui::{
 use yeet_buffer::model::{undo, undo::BufferChanged, viewport::ViewPort};
 
 use crate::{
-    model::{self, Buffer, DirectoryBuffer},
+    model::{self, Buffer, DirectoryBuffer, Tasks},
     theme::{tokens, Theme},
 };
 
 pub fn view(
     current: &Buffer,
+    tasks: &Tasks,
     viewport: &ViewPort,
     frame: &mut Frame,
     rect: Rect,
@@ -39,7 +40,7 @@ pub fn view(
     match current {
         Buffer::Directory(it) => {
             if is_focused {
-                filetree_status(it, viewport, frame, rect, theme)
+                filetree_status(it, tasks, viewport, frame, rect, theme)
             } else {
                 filetree_status_unfocused(it, frame, rect, theme)
             }
@@ -47,7 +48,8 @@ pub fn view(
         Buffer::Tasks(it) => {
             let count = it.buffer.lines.len();
             if is_focused {
-                label_status("Tasks", count, viewport, frame, rect, theme)
+                let progress = get_progress_content(tasks, theme);
+                label_status("Tasks", count, progress, viewport, frame, rect, theme)
             } else {
                 label_status_unfocused("Tasks", frame, rect, theme)
             }
@@ -55,15 +57,41 @@ pub fn view(
         Buffer::QuickFix(it) => {
             let count = it.buffer.lines.len();
             if is_focused {
-                label_status("QuickFix", count, viewport, frame, rect, theme)
+                label_status(
+                    "QuickFix",
+                    count,
+                    Line::default(),
+                    viewport,
+                    frame,
+                    rect,
+                    theme,
+                )
             } else {
                 label_status_unfocused("QuickFix", frame, rect, theme)
             }
         }
+        Buffer::Diff(it) => {
+            let count = it.buffer.lines.len();
+            if is_focused {
+                label_status("Diff", count, Line::default(), viewport, frame, rect, theme)
+            } else {
+                label_status_unfocused("Diff", frame, rect, theme)
+            }
+        }
+        Buffer::Edit(it) => {
+            let path = it.path.to_str().unwrap_or("");
+            let count = it.buffer.lines.len();
+            if is_focused {
+                let changes = get_unsaved_content(it.buffer.has_unsaved_changes(), theme);
+                label_status(path, count, changes, viewport, frame, rect, theme)
+            } else {
+                label_status_unfocused(path, frame, rect, theme)
+            }
+        }
         Buffer::Help(it) => {
             let count = it.buffer.lines.len();
             if is_focused {
-                label_status("Help", count, viewport, frame, rect, theme)
+                label_status("Help", count, Line::default(), viewport, frame, rect, theme)
             } else {
                 label_status_unfocused("Help", frame, rect, theme)
             }
@@ -75,6 +103,7 @@ pub fn view(
 fn label_status(
     label: &str,
     line_count: usize,
+    details: Line,
     viewport: &ViewPort,
     frame: &mut Frame,
     rect: Rect,
@@ -107,6 +136,8 @@ fn label_status(
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Length(label.width() as u16),
+            Constraint::Length(3),
+            Constraint::Length(details.width() as u16),
             Constraint::Min(3),
             Constraint::Length(position_line.width() as u16),
         ])
@@ -118,7 +149,8 @@ fn label_status(
     );
 
     frame.render_widget(Paragraph::new(label), layout[0]);
-    frame.render_widget(Paragraph::new(position_line), layout[2]);
+    frame.render_widget(Paragraph::new(details), layout[2]);
+    frame.render_widget(Paragraph::new(position_line), layout[4]);
 }
 
 fn label_status_unfocused(label: &str, frame: &mut Frame, rect: Rect, theme: &Theme) {
@@ -136,6 +168,7 @@ fn label_status_unfocused(label: &str, frame: &mut Frame, rect: Rect, theme: &Th
 
 fn filetree_status(
     buffer: &DirectoryBuffer,
+    tasks: &Tasks,
     viewport: &ViewPort,
     frame: &mut Frame,
     rect: Rect,
@@ -146,14 +179,24 @@ fn filetree_status(
         get_permissions(&selected).patch_style(theme.style_fg(tokens::STATUSLINE_PERMISSIONS_FG));
 
     let changes = get_changes_content(buffer, theme);
+    let progress = get_progress_content(tasks, theme);
     let position = get_position_content(buffer, viewport, theme);
 
-    let path = Line::from(Span::styled(
+    let mut path = vec![Span::styled(
         buffer.path.to_str().unwrap_or(""),
         theme
             .style_fg(tokens::STATUSLINE_FOCUSED_FG)
             .add_modifier(Modifier::BOLD),
-    ));
+    )];
+
+    if let Some(branch) = &buffer.git.branch {
+        path.push(Span::styled(
+            format!("  {}", branch),
+            theme.style_fg(tokens::STATUSLINE_GIT_BRANCH_FG),
+        ));
+    }
+
+    let path = Line::from(path);
 
     let layout = Layout::default()
         .direction(Direction::Horizontal)
@@ -164,6 +207,8 @@ fn filetree_status(
             Constraint::Length(3),
             Constraint::Length(changes.width() as u16),
             Constraint::Min(3),
+            Constraint::Length(progress.width() as u16),
+            Constraint::Length(3),
             Constraint::Length(position.width() as u16),
         ])
         .split(rect);
@@ -176,7 +221,8 @@ fn filetree_status(
     frame.render_widget(Paragraph::new(path), layout[0]);
     frame.render_widget(Paragraph::new(permissions), layout[2]);
     frame.render_widget(Paragraph::new(changes), layout[4]);
-    frame.render_widget(Paragraph::new(position), layout[6]);
+    frame.render_widget(Paragraph::new(progress), layout[6]);
+    frame.render_widget(Paragraph::new(position), layout[8]);
 }
 
 fn filetree_status_unfocused(
@@ -222,6 +268,24 @@ fn get_position_content<'a>(
     Line::from(content)
 }
 
+fn get_progress_content<'a>(tasks: &Tasks, theme: &Theme) -> Line<'a> {
+    match tasks.progress() {
+        Some(progress) => Line::from(Span::styled(
+            progress.to_string(),
+            theme.style_fg(tokens::STATUSLINE_PROGRESS_FG),
+        )),
+        None => Line::default(),
+    }
+}
+
+fn get_unsaved_content<'a>(has_unsaved_changes: bool, theme: &Theme) -> Line<'a> {
+    if has_unsaved_changes {
+        Line::from(Span::styled("[+]", theme.style_fg(tokens::DIFF_MODIFIED)))
+    } else {
+        Line::default()
+    }
+}
+
 fn get_changes_content<'a>(buffer: &'a DirectoryBuffer, theme: &Theme) -> Line<'a> {
     let modifications = buffer.buffer.uncommitted_changes();
     let changes = undo::consolidate_modifications(&modifications);
@@ -6,7 +6,13 @@ use super::{buffer, tabbar};
 
 pub fn view(model: &Model, theme: &Theme, frame: &mut Frame) -> Result<(), AppError> {
     tabbar::render(&model.app, theme, frame);
-    buffer::view(&model.state.modes.current, &model.app, theme, frame);
+    buffer::view(
+        &model.state.modes.current,
+        &model.app,
+        &model.state.tasks,
+        theme,
+        frame,
+    );
 
     Ok(())
 }
@@ -2,8 +2,7 @@ use std::collections::{HashSet, VecDeque};
 
 use buffer::KeyBuffer;
 use key::{Key, KeyCode};
-use map::KeyMap;
-use message::{Binding, BindingKind, KeySequence, KeymapMessage};
+use message::{Binding, BindingKind, KeySequence, KeymapMessage, PrintContent};
 use tree::KeyTree;
 use yeet_buffer::{
     message::{BufferMessage, CursorDirection, TextModification},
@@ -17,10 +16,16 @@ mod map;
 pub mod message;
 mod tree;
 
+pub use map::KeyMap;
+
+const MAX_MAPPING_DEPTH: usize = 100;
+
 #[derive(Debug, thiserror::Error, PartialEq)]
 enum KeyMapError {
     #[error("Key sequence is incomplete.")]
     KeySequenceIncomplete,
+    #[error("Mapping conflicts with an existing binding.")]
+    MappingConflict,
     #[error("Failed to add mapping for mode {0}.")]
     ModeUnresolvable(String),
     #[error("Failed to resolve valid binding.")]
@@ -31,6 +36,7 @@ enum KeyMapError {
 
 pub struct MessageResolver {
     buffer: KeyBuffer,
+    depth: usize,
     pub mode: Mode,
     toggle: HashSet<String>,
     tree: KeyTree,
@@ -38,16 +44,21 @@ pub struct MessageResolver {
 
 impl Default for MessageResolver {
     fn default() -> Self {
+        Self::new(KeyMap::default())
+    }
+}
+
+impl MessageResolver {
+    pub fn new(keymap: KeyMap) -> Self {
         Self {
             buffer: KeyBuffer::default(),
+            depth: 0,
             mode: Mode::default(),
             toggle: HashSet::new(),
-            tree: KeyMap::default().into_tree(),
+            tree: keymap.into_tree(),
         }
     }
-}
 
-impl MessageResolver {
     pub fn add_keys(
         &mut self,
         mut keys: VecDeque<Key>,
@@ -55,13 +66,34 @@ impl MessageResolver {
         while let Some(key) = keys.pop_front() {
             let mut result = self.add_key(key);
             if matches!(result.1, KeySequence::Completed(_)) {
+                if let Some(sequence) = get_mapped_sequence(&result.0) {
+                    self.depth += 1;
+                    if self.depth > MAX_MAPPING_DEPTH {
+                        tracing::error!("key mapping recursion too deep: {}", sequence);
+
+                        self.depth = 0;
+                        let message = KeymapMessage::Print(vec![PrintContent::Error(format!(
+                            "Key mapping recursion exceeded {} levels",
+                            MAX_MAPPING_DEPTH
+                        ))]);
+                        return Some((vec![message], result.1));
+                    }
+
+                    let mut expanded = conversion::from_keycode_string(&sequence);
+                    expanded.extend(keys);
+                    keys = expanded;
+                    continue;
+                }
+
                 let remaining_sequence = keys
                     .iter()
                     .map(|key| key.to_keycode_string())
                     .collect::<Vec<_>>()
                     .join("");
 
-                if !remaining_sequence.is_empty() {
+                if remaining_sequence.is_empty() {
+                    self.depth = 0;
+                } else {
                     result
                         .0
                         .insert(0, KeymapMessage::ExecuteKeySequence(remaining_sequence));
@@ -70,6 +102,8 @@ impl MessageResolver {
                 return Some(result);
             }
         }
+
+        self.depth = 0;
         None
     }
 
@@ -123,6 +157,21 @@ impl MessageResolver {
     }
 }
 
+fn get_mapped_sequence(messages: &[KeymapMessage]) -> Option<String> {
+    if messages.is_empty() {
+        return None;
+    }
+
+    messages
+        .iter()
+        .map(|message| match message {
+            KeymapMessage::ExecuteKeySequence(sequence) => Some(sequence.as_str()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|sequences| sequences.concat())
+}
+
 fn resolve_binding(
     tree: &KeyTree,
     mode: &Mode,
@@ -139,6 +188,12 @@ fn resolve_binding(
     }
 
     let (mut binding, unused_keys) = get_binding_by_keys(before, tree, mode, keys)?;
+    if let Some(message::NextBindingKind::Motion) = before.and_then(|it| it.expects.as_ref()) {
+        if !matches!(binding.kind, BindingKind::Motion(_) | BindingKind::Repeat) {
+            return Err(KeyMapError::NoValidBindingFound);
+        }
+    }
+
     if let Some((identifier, kind)) = &binding.toggle {
         if toggle.remove(identifier) {
             return Ok(Binding {
@@ -276,6 +331,20 @@ fn combine(current: &Binding, next: &Binding) -> Result<BindingKind, KeyMapError
 
             Ok(BindingKind::Modification(modification))
         }
+        (BindingKind::Message(msg), BindingKind::Motion(mtn)) => {
+            let repeat = next.repeat.unwrap_or(1);
+            let message = match msg {
+                KeymapMessage::TrashMotionToJunkYard(_, _) => {
+                    KeymapMessage::TrashMotionToJunkYard(repeat, mtn.clone())
+                }
+                KeymapMessage::YankMotionToJunkYard(_, _) => {
+                    KeymapMessage::YankMotionToJunkYard(repeat, mtn.clone())
+                }
+                _ => return Err(KeyMapError::NoValidBindingFound),
+            };
+
+            Ok(BindingKind::Message(message))
+        }
         (_, _) => Err(KeyMapError::NoValidBindingFound),
     }
 }
@@ -338,6 +407,12 @@ fn get_messages_from_binding(mode: &Mode, binding: Binding) -> Vec<KeymapMessage
 fn get_repeated_message(repeat: usize, msg: &KeymapMessage) -> Vec<KeymapMessage> {
     let mut messages = Vec::new();
     match msg {
+        KeymapMessage::TrashMotionToJunkYard(count, mtn) => messages.push(
+            KeymapMessage::TrashMotionToJunkYard(count * repeat, mtn.clone()),
+        ),
+        KeymapMessage::YankMotionToJunkYard(count, mtn) => messages.push(
+            KeymapMessage::YankMotionToJunkYard(count * repeat, mtn.clone()),
+        ),
         KeymapMessage::YankToJunkYard(_) => messages.push(KeymapMessage::YankToJunkYard(repeat)),
         _ => {
             for _ in 0..repeat {
@@ -352,6 +427,6 @@ fn get_repeated_message(repeat: usize, msg: &KeymapMessage) -> Vec<KeymapMessage
 fn get_passthrough_by_mode(mode: &Mode) -> bool {
     match mode {
         Mode::Command(_) | Mode::Insert => true,
-        Mode::Navigation | Mode::Normal => false,
+        Mode::Navigation | Mode::Normal | Mode::Visual => false,
     }
 }
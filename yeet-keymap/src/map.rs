@@ -10,7 +10,7 @@ use yeet_buffer::{
 
 use crate::{
     key::{Key, KeyCode, KeyModifier},
-    message::{Binding, BindingKind, FocusDirection, KeymapMessage, NextBindingKind},
+    message::{Binding, BindingKind, FocusDirection, KeymapMessage, LinkKind, NextBindingKind},
     tree::KeyTree,
 };
 
@@ -19,12 +19,34 @@ pub struct KeyMap {
 }
 
 impl KeyMap {
-    pub fn into_tree(self) -> KeyTree {
+    pub fn set(&mut self, mode: Mode, keys: Vec<Key>, binding: Binding) {
+        if keys.is_empty() {
+            return;
+        }
+
+        let mappings = self.mappings.entry(mode).or_default();
+        mappings.retain(|(existing, existing_binding)| {
+            let is_prefix_binding =
+                existing_binding.expects.is_none() && keys.starts_with(existing);
+            !existing.starts_with(&keys) && !is_prefix_binding
+        });
+        mappings.push((keys, binding));
+    }
+
+    pub fn delete(&mut self, mode: &Mode, keys: &[Key]) {
+        if let Some(mappings) = self.mappings.get_mut(mode) {
+            mappings.retain(|(existing, _)| existing != keys);
+        }
+    }
+
+    pub(crate) fn into_tree(self) -> KeyTree {
         let mut tree = KeyTree::default();
         for (mode, mappings) in self.mappings {
             for (keys, message) in mappings {
-                tree.add_mapping(&mode, keys, message)
-                    .expect("Default mappings must form a valid tree.");
+                let sequence: String = keys.iter().map(Key::to_keycode_string).collect();
+                if let Err(err) = tree.add_mapping(&mode, keys, message) {
+                    tracing::error!("skipping mapping '{}' for mode {}: {}", sequence, mode, err);
+                }
             }
         }
         tree
@@ -94,6 +116,18 @@ impl Default for KeyMap {
             ],
         );
 
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Command(CommandMode::Command)],
+            vec![(
+                vec![Key::new(KeyCode::Tab, vec![])],
+                Binding {
+                    kind: BindingKind::Message(KeymapMessage::CompleteCommand),
+                    ..Default::default()
+                },
+            )],
+        );
+
         add_mapping(
             &mut mappings,
             vec![Mode::Navigation],
@@ -116,6 +150,35 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('e'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ScrollPreview(
+                            ViewPortDirection::LineDown,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ScrollPreview(
+                            ViewPortDirection::LineUp,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                        Key::new(KeyCode::from_char('m'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ToggleMarkdownPreview),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('g'), vec![]),
@@ -166,6 +229,31 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char(','), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::LastFindBackward),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char(';'), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::LastFindForward),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('d'), vec![])],
+                    Binding {
+                        expects: Some(NextBindingKind::Motion),
+                        kind: BindingKind::Message(KeymapMessage::TrashMotionToJunkYard(
+                            0,
+                            CursorDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('d'), vec![]),
@@ -177,6 +265,22 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('f'), vec![])],
+                    Binding {
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Motion(CursorDirection::FindForward('_')),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('f'), vec![KeyModifier::Shift])],
+                    Binding {
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Motion(CursorDirection::FindBackward('_')),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('g'), vec![]),
@@ -277,6 +381,14 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                    Binding {
+                        force: Some(Mode::Visual),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![Key::new(KeyCode::from_char('p'), vec![])],
                     Binding {
@@ -284,6 +396,56 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('p'), vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::PasteLinkFromJunkYard(
+                            '"',
+                            LinkKind::Absolute,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                        Key::new(KeyCode::from_char('p'), vec![KeyModifier::Shift]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::PasteLinkFromJunkYard(
+                            '"',
+                            LinkKind::Relative,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('t'), vec![])],
+                    Binding {
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Motion(CursorDirection::TillForward('_')),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('t'), vec![KeyModifier::Shift])],
+                    Binding {
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Motion(CursorDirection::TillBackward('_')),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![])],
+                    Binding {
+                        expects: Some(NextBindingKind::Motion),
+                        kind: BindingKind::Message(KeymapMessage::YankMotionToJunkYard(
+                            0,
+                            CursorDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('y'), vec![]),
@@ -617,6 +779,238 @@ impl Default for KeyMap {
             ],
         );
 
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Visual],
+            vec![
+                (
+                    vec![Key::new(KeyCode::Space, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ToggleQuickFix),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::Esc, vec![])],
+                    Binding {
+                        force: Some(Mode::Navigation),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                    Binding {
+                        force: Some(Mode::Navigation),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('0'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(0),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('1'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(1),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('2'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(2),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('3'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(3),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('4'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(4),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('5'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(5),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('6'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(6),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('7'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(7),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('8'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(8),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('9'), vec![])],
+                    Binding {
+                        kind: BindingKind::Repeat,
+                        repeat: Some(9),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char(':'), vec![])],
+                    Binding {
+                        force: Some(Mode::Command(CommandMode::Command)),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('d'), vec![])],
+                    Binding {
+                        kind: BindingKind::Modification(TextModification::DeleteLine),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('d'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown),
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Bottom),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Top),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('j'), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Down),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('k'), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Up),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('n'), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Next)),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Previous)),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('u'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageUp),
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::YankToJunkYard(0)),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                        Key::new(KeyCode::from_char('b'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::BottomOnCursor),
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                        Key::new(KeyCode::from_char('t'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::TopOnCursor),
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::CenterOnCursor),
+                        )),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
         add_mapping(
             &mut mappings,
             vec![Mode::Normal],
@@ -5,7 +5,7 @@ use std::{
 
 use regex::Regex;
 use yeet_buffer::{
-    message::{BufferMessage, CursorDirection, TextModification},
+    message::{BufferMessage, CursorDirection, TextModification, ViewPortDirection},
     model::Mode,
 };
 
@@ -101,11 +101,13 @@ pub enum FocusDirection {
 pub enum KeymapMessage {
     Buffer(BufferMessage),
     ClearSearchHighlight,
+    CompleteCommand,
     FocusDirection(FocusDirection),
     DeleteMarks(Vec<char>),
     ExecuteCommand,
     ExecuteCommandString(String),
     ExecuteKeySequence(String),
+    ExecuteLuaFunction(usize),
     ExecuteRegister(char),
     LeaveCommandMode,
     NavigateToMark(char),
@@ -115,18 +117,28 @@ pub enum KeymapMessage {
     NavigateToSelected,
     OpenSelected,
     PasteFromJunkYard(char),
+    PasteLinkFromJunkYard(char, LinkKind),
     Print(Vec<PrintContent>),
     ReplayMacro(char),
+    ScrollPreview(ViewPortDirection),
     SetMark(char),
     StartMacro(char),
     StopMacro,
+    ToggleMarkdownPreview,
     ToggleQuickFix,
+    TrashMotionToJunkYard(usize, CursorDirection),
     Quit(QuitMode),
+    YankMotionToJunkYard(usize, CursorDirection),
     YankPathToClipboard,
-    // TODO: yank to junk with motion
     YankToJunkYard(usize),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LinkKind {
+    Absolute,
+    Relative,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum QuitMode {
     FailOnRunningTasks,
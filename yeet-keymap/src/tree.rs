@@ -27,13 +27,13 @@ impl KeyTree {
             self.modes.insert(mode.clone(), Node::Key(HashMap::new()));
         }
 
-        let max_index = keys.len() - 1;
+        let Some(max_index) = keys.len().checked_sub(1) else {
+            return Err(KeyMapError::NoKeysRemaining);
+        };
+
         let mut iter = keys.iter().enumerate();
         match self.modes.get_mut(mode) {
-            Some(node) => {
-                add_mapping_node(&max_index, &mut iter, node, binding);
-                Ok(())
-            }
+            Some(node) => add_mapping_node(&max_index, &mut iter, node, binding),
             None => Err(KeyMapError::ModeUnresolvable(mode.to_string())),
         }
     }
@@ -63,41 +63,41 @@ fn add_mapping_node(
     iter: &mut Enumerate<Iter<'_, Key>>,
     node: &mut Node,
     binding: Binding,
-) {
-    if let Some((index, key)) = iter.next() {
-        if &index == max_index {
-            match node {
-                Node::Binding(_) => unreachable!(),
-                Node::ExpectsOr(_, map) | Node::Key(map) => {
-                    if binding.expects.is_some() {
-                        if let Some(node) = map.remove(key) {
-                            match node {
-                                Node::Binding(_) | Node::ExpectsOr(_, _) => unreachable!(),
-                                Node::Key(m) => {
-                                    map.insert(key.clone(), Node::ExpectsOr(binding, m.clone()));
-                                }
-                            }
-                        } else {
-                            map.insert(key.clone(), Node::ExpectsOr(binding, HashMap::new()));
-                        }
-                    } else if map.insert(key.clone(), Node::Binding(binding)).is_some() {
-                        panic!("This should not happen");
-                    }
-                }
-            }
-        } else {
-            match node {
-                Node::Binding(_) => unreachable!(),
-                Node::ExpectsOr(_, map) | Node::Key(map) => {
-                    if !map.contains_key(key) {
-                        map.insert(key.clone(), Node::Key(HashMap::new()));
-                    }
-                    let node = map.get_mut(key).expect("Must exist");
-                    add_mapping_node(max_index, iter, node, binding);
-                }
+) -> Result<(), KeyMapError> {
+    let Some((index, key)) = iter.next() else {
+        return Ok(());
+    };
+
+    let map = match node {
+        Node::Binding(_) => return Err(KeyMapError::MappingConflict),
+        Node::ExpectsOr(_, map) | Node::Key(map) => map,
+    };
+
+    if &index != max_index {
+        let node = map
+            .entry(key.clone())
+            .or_insert_with(|| Node::Key(HashMap::new()));
+
+        return add_mapping_node(max_index, iter, node, binding);
+    }
+
+    if binding.expects.is_some() {
+        let children = match map.remove(key) {
+            Some(Node::Key(children)) => children,
+            Some(node) => {
+                map.insert(key.clone(), node);
+                return Err(KeyMapError::MappingConflict);
             }
-        }
+            None => HashMap::new(),
+        };
+        map.insert(key.clone(), Node::ExpectsOr(binding, children));
+    } else if map.contains_key(key) {
+        return Err(KeyMapError::MappingConflict);
+    } else {
+        map.insert(key.clone(), Node::Binding(binding));
     }
+
+    Ok(())
 }
 
 fn get_bindings_from_node(node: &Node, iter: &mut Iter<'_, Key>) -> Result<Node, KeyMapError> {
@@ -125,3 +125,42 @@ fn get_bindings_from_node(node: &Node, iter: &mut Iter<'_, Key>) -> Result<Node,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key::KeyCode, message::BindingKind};
+
+    fn keys(chars: &str) -> Vec<Key> {
+        chars
+            .chars()
+            .map(|c| Key::new(KeyCode::from_char(c), vec![]))
+            .collect()
+    }
+
+    #[test]
+    fn add_mapping_rejects_conflicting_bindings() {
+        let mut tree = KeyTree::default();
+        let binding = Binding {
+            kind: BindingKind::None,
+            ..Default::default()
+        };
+
+        assert!(tree
+            .add_mapping(&Mode::Normal, keys("gt"), binding.clone())
+            .is_ok());
+        assert_eq!(
+            tree.add_mapping(&Mode::Normal, keys("g"), binding.clone()),
+            Err(KeyMapError::MappingConflict)
+        );
+        assert_eq!(
+            tree.add_mapping(&Mode::Normal, keys("gtx"), binding.clone()),
+            Err(KeyMapError::MappingConflict)
+        );
+        assert_eq!(
+            tree.add_mapping(&Mode::Normal, Vec::new(), binding),
+            Err(KeyMapError::NoKeysRemaining)
+        );
+        assert!(tree.get_binding(&Mode::Normal, &keys("gt")).is_ok());
+    }
+}
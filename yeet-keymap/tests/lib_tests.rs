@@ -1,11 +1,14 @@
 use yeet_buffer::{
-    message::{BufferMessage, CursorDirection, TextModification},
+    message::{BufferMessage, CursorDirection, Search, TextModification, ViewPortDirection},
     model::{CommandMode, Mode},
 };
 use yeet_keymap::{
+    conversion,
     key::{Key, KeyCode, KeyModifier},
-    message::{FocusDirection, KeySequence, KeymapMessage},
-    MessageResolver,
+    message::{
+        Binding, BindingKind, FocusDirection, KeySequence, KeymapMessage, LinkKind, PrintContent,
+    },
+    KeyMap, MessageResolver,
 };
 
 #[test]
@@ -231,6 +234,97 @@ fn add_and_resolve_key_navigation_yy() {
     assert_eq!(1, result.0.len());
 }
 
+#[test]
+fn add_and_resolve_key_navigation_yj() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('y'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('j'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::YankMotionToJunkYard(
+            1,
+            CursorDirection::Down
+        )),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("yj".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_2y3gg() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('2'), vec![]));
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('y'), vec![]));
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('3'), vec![]));
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::YankMotionToJunkYard(
+            6,
+            CursorDirection::Top
+        )),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_yfa() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('y'), vec![]));
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('f'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('a'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::YankMotionToJunkYard(
+            1,
+            CursorDirection::FindForward('a')
+        )),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_dn() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('n'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::TrashMotionToJunkYard(
+            1,
+            CursorDirection::Search(Search::Next)
+        )),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("dn".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_dshiftg() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift]));
+
+    assert_eq!(
+        Some(&KeymapMessage::TrashMotionToJunkYard(
+            1,
+            CursorDirection::Bottom
+        )),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
 #[test]
 fn add_and_resolve_key_navigation_10yy() {
     let mut resolver = MessageResolver::default();
@@ -375,6 +469,20 @@ fn add_and_resolve_key_navigation_ctrl_j() {
     assert_eq!(1, result.0.len());
 }
 
+#[test]
+fn add_and_resolve_key_navigation_ctrl_e() {
+    let mut resolver = MessageResolver::default();
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('e'), vec![KeyModifier::Ctrl]));
+
+    assert_eq!(
+        Some(&KeymapMessage::ScrollPreview(ViewPortDirection::LineDown)),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("<C-e>".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
 #[test]
 fn add_and_resolve_key_navigation_ctrl_w_ctrl_s() {
     let mut resolver = MessageResolver::default();
@@ -435,6 +543,40 @@ fn add_and_resolve_key_navigation_g_shift_t() {
     assert_eq!(1, result.0.len());
 }
 
+#[test]
+fn add_and_resolve_key_navigation_shift_p() {
+    let mut resolver = MessageResolver::default();
+    let result = resolver.add_key(Key::new(KeyCode::from_char('p'), vec![KeyModifier::Shift]));
+
+    assert_eq!(
+        Some(&KeymapMessage::PasteLinkFromJunkYard(
+            '"',
+            LinkKind::Absolute
+        )),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("P".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_g_shift_p() {
+    let mut resolver = MessageResolver::default();
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('p'), vec![KeyModifier::Shift]));
+
+    assert_eq!(
+        Some(&KeymapMessage::PasteLinkFromJunkYard(
+            '"',
+            LinkKind::Relative
+        )),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("gP".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
 #[test]
 fn add_and_resolve_key_normal_ctrl_k() {
     let mut resolver = MessageResolver::default();
@@ -445,3 +587,178 @@ fn add_and_resolve_key_normal_ctrl_k() {
     assert_eq!(None, result.0.first());
     assert!(result.0.is_empty());
 }
+
+#[test]
+fn add_and_resolve_key_navigation_shift_v() {
+    let mut resolver = MessageResolver::default();
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            Mode::Navigation,
+            Mode::Visual
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("V".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_d() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::Modification(
+            1,
+            TextModification::DeleteLine
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("d".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_y() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('y'), vec![]));
+
+    assert_eq!(Some(&KeymapMessage::YankToJunkYard(1)), result.0.first());
+    assert_eq!(KeySequence::Completed("y".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_3j() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('3'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('j'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            3,
+            CursorDirection::Down
+        ))),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_esc() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let result = resolver.add_key(Key::new(KeyCode::Esc, vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            Mode::Visual,
+            Mode::Navigation
+        ))),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_overridden_prefix() {
+    let mut keymap = KeyMap::default();
+    keymap.set(
+        Mode::Navigation,
+        vec![Key::new(KeyCode::from_char('g'), vec![])],
+        Binding {
+            kind: BindingKind::Message(KeymapMessage::ExecuteCommandString("tabn".to_string())),
+            ..Default::default()
+        },
+    );
+
+    let mut resolver = MessageResolver::new(keymap);
+    let result = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+
+    assert_eq!(
+        Some(&KeymapMessage::ExecuteCommandString("tabn".to_string())),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("g".to_string()), result.1);
+}
+
+#[test]
+fn add_and_resolve_key_navigation_deleted_gt() {
+    let mut keymap = KeyMap::default();
+    keymap.delete(
+        &Mode::Navigation,
+        &[
+            Key::new(KeyCode::from_char('g'), vec![]),
+            Key::new(KeyCode::from_char('t'), vec![]),
+        ],
+    );
+
+    let mut resolver = MessageResolver::new(keymap);
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('g'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('t'), vec![]));
+
+    assert_eq!(KeySequence::Completed("gt".to_string()), result.1);
+    assert!(result.0.is_empty());
+}
+
+#[test]
+fn add_keys_expands_nested_key_sequence_mappings() {
+    let mut keymap = KeyMap::default();
+    for (key, sequence) in [("H", "L"), ("L", "gg")] {
+        keymap.set(
+            Mode::Navigation,
+            conversion::from_keycode_string(key).into(),
+            Binding {
+                kind: BindingKind::Message(KeymapMessage::ExecuteKeySequence(sequence.to_string())),
+                ..Default::default()
+            },
+        );
+    }
+
+    let mut resolver = MessageResolver::new(keymap);
+    let result = resolver.add_keys(conversion::from_keycode_string("Hj"));
+
+    let mut expected = MessageResolver::default();
+    let expected = expected.add_keys(conversion::from_keycode_string("gg"));
+
+    let (mut messages, sequence) = result.unwrap();
+    assert_eq!(
+        messages.remove(0),
+        KeymapMessage::ExecuteKeySequence("j".to_string())
+    );
+    assert_eq!(Some((messages, sequence)), expected);
+}
+
+#[test]
+fn add_keys_stops_recursive_key_sequence_mappings() {
+    let mut keymap = KeyMap::default();
+    keymap.set(
+        Mode::Navigation,
+        vec![Key::new(KeyCode::from_char('j'), vec![])],
+        Binding {
+            kind: BindingKind::Message(KeymapMessage::ExecuteKeySequence("2j".to_string())),
+            ..Default::default()
+        },
+    );
+
+    let mut resolver = MessageResolver::new(keymap);
+    let (messages, sequence) = resolver
+        .add_keys(conversion::from_keycode_string("j"))
+        .unwrap();
+
+    assert!(matches!(
+        messages.as_slice(),
+        [KeymapMessage::Print(content)] if matches!(content.as_slice(), [PrintContent::Error(_)])
+    ));
+    assert!(matches!(sequence, KeySequence::Completed(_)));
+}
@@ -0,0 +1,227 @@
+use std::path::{Path, PathBuf};
+
+use mlua::prelude::*;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApiState {
+    pub mode: String,
+    pub current_path: Option<PathBuf>,
+    pub selection: Option<PathBuf>,
+    pub qfix: Vec<PathBuf>,
+    pub qfix_index: usize,
+    pub marks: Vec<(char, PathBuf)>,
+    pub junkyard: Vec<ApiJunk>,
+    pub tabs: Vec<ApiTab>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiRequest {
+    Execute(String),
+    FeedKeys(String),
+    Navigate(PathBuf),
+    Print(String),
+    QuickFixAdd(Vec<PathBuf>),
+}
+
+#[derive(Default)]
+struct ApiQueue(Vec<ApiRequest>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiJunk {
+    pub register: char,
+    pub paths: Vec<PathBuf>,
+    pub ready: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiTab {
+    pub id: usize,
+    pub path: Option<PathBuf>,
+    pub current: bool,
+}
+
+pub fn set_api_state(lua: &Lua, state: ApiState) {
+    lua.set_app_data(state);
+    lua.set_app_data(ApiQueue::default());
+}
+
+pub fn take_api_requests(lua: &Lua) -> Vec<ApiRequest> {
+    match lua.app_data_mut::<ApiQueue>() {
+        Some(mut queue) => std::mem::take(&mut queue.0),
+        None => Vec::new(),
+    }
+}
+
+pub fn clear_api_state(lua: &Lua) {
+    lua.remove_app_data::<ApiState>();
+    lua.remove_app_data::<ApiQueue>();
+}
+
+fn push_request(lua: &Lua, function: &str, request: ApiRequest) {
+    match lua.app_data_mut::<ApiQueue>() {
+        Some(mut queue) => queue.0.push(request),
+        None => tracing::warn!(
+            "y.api.{}() is only available in keymap functions and commands, ignoring",
+            function
+        ),
+    }
+}
+
+pub fn create_api_table(lua: &Lua) -> LuaResult<LuaTable> {
+    let api_table = lua.create_table()?;
+
+    api_table.set(
+        "get_mode",
+        lua.create_function(|lua, ()| Ok(get_state(lua).map(|state| state.mode)))?,
+    )?;
+
+    api_table.set(
+        "get_current_path",
+        lua.create_function(|lua, ()| {
+            Ok(get_state(lua)
+                .and_then(|state| state.current_path)
+                .map(to_string))
+        })?,
+    )?;
+
+    api_table.set(
+        "get_selection",
+        lua.create_function(|lua, ()| {
+            Ok(get_state(lua)
+                .and_then(|state| state.selection)
+                .map(to_string))
+        })?,
+    )?;
+
+    api_table.set(
+        "get_qfix",
+        lua.create_function(|lua, ()| {
+            let state = get_state(lua).unwrap_or_default();
+            let qfix = lua.create_table()?;
+            qfix.set(
+                "entries",
+                lua.create_sequence_from(state.qfix.iter().map(to_string))?,
+            )?;
+            if !state.qfix.is_empty() {
+                qfix.set("current", state.qfix_index + 1)?;
+            }
+            Ok(qfix)
+        })?,
+    )?;
+
+    api_table.set(
+        "get_marks",
+        lua.create_function(|lua, ()| {
+            let state = get_state(lua).unwrap_or_default();
+            lua.create_table_from(
+                state
+                    .marks
+                    .iter()
+                    .map(|(mark, path)| (mark.to_string(), to_string(path))),
+            )
+        })?,
+    )?;
+
+    api_table.set(
+        "get_junkyard",
+        lua.create_function(|lua, ()| {
+            let state = get_state(lua).unwrap_or_default();
+            let junkyard = lua.create_table()?;
+            for (index, junk) in state.junkyard.iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("register", junk.register.to_string())?;
+                entry.set(
+                    "paths",
+                    lua.create_sequence_from(junk.paths.iter().map(to_string))?,
+                )?;
+                entry.set("ready", junk.ready)?;
+                junkyard.set(index + 1, entry)?;
+            }
+            Ok(junkyard)
+        })?,
+    )?;
+
+    api_table.set(
+        "get_tabs",
+        lua.create_function(|lua, ()| {
+            let state = get_state(lua).unwrap_or_default();
+            let tabs = lua.create_table()?;
+            for (index, tab) in state.tabs.iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("id", tab.id)?;
+                entry.set("path", tab.path.as_deref().map(to_string))?;
+                entry.set("current", tab.current)?;
+                tabs.set(index + 1, entry)?;
+            }
+            Ok(tabs)
+        })?,
+    )?;
+
+    api_table.set(
+        "exec",
+        lua.create_function(|lua, command: String| {
+            let command = command.strip_prefix(':').unwrap_or(&command).to_string();
+            push_request(lua, "exec", ApiRequest::Execute(command));
+            Ok(())
+        })?,
+    )?;
+
+    api_table.set(
+        "feedkeys",
+        lua.create_function(|lua, keys: String| {
+            push_request(lua, "feedkeys", ApiRequest::FeedKeys(keys));
+            Ok(())
+        })?,
+    )?;
+
+    api_table.set(
+        "navigate",
+        lua.create_function(|lua, path: String| {
+            push_request(lua, "navigate", ApiRequest::Navigate(PathBuf::from(path)));
+            Ok(())
+        })?,
+    )?;
+
+    api_table.set(
+        "print",
+        lua.create_function(|lua, message: String| {
+            push_request(lua, "print", ApiRequest::Print(message));
+            Ok(())
+        })?,
+    )?;
+
+    let qfix_table = lua.create_table()?;
+    qfix_table.set(
+        "add",
+        lua.create_function(|lua, paths: LuaValue| {
+            let paths = match paths {
+                LuaValue::String(path) => vec![PathBuf::from(path.to_str()?.to_string())],
+                LuaValue::Table(paths) => paths
+                    .sequence_values::<String>()
+                    .map(|path| path.map(PathBuf::from))
+                    .collect::<LuaResult<_>>()?,
+                _ => {
+                    tracing::error!(
+                        "y.api.qfix.add() called with {}, expected string or table",
+                        paths.type_name()
+                    );
+                    return Ok(());
+                }
+            };
+
+            push_request(lua, "qfix.add", ApiRequest::QuickFixAdd(paths));
+            Ok(())
+        })?,
+    )?;
+    api_table.set("qfix", qfix_table)?;
+
+    Ok(api_table)
+}
+
+fn get_state(lua: &Lua) -> Option<ApiState> {
+    lua.app_data_ref::<ApiState>().map(|state| state.clone())
+}
+
+fn to_string(path: impl AsRef<Path>) -> String {
+    path.as_ref().to_string_lossy().to_string()
+}
@@ -0,0 +1,222 @@
+use mlua::prelude::*;
+
+pub const BUILTIN_COMMANDS: &[&str] = &[
+    "cN",
+    "cdo",
+    "cfirst",
+    "chmod",
+    "chown",
+    "cl",
+    "clearcl",
+    "cn",
+    "copen",
+    "cp",
+    "d",
+    "delm",
+    "delt",
+    "e",
+    "fd",
+    "fsredo",
+    "fsundo",
+    "help",
+    "info",
+    "invertcl",
+    "junk",
+    "ln",
+    "marks",
+    "mv",
+    "noh",
+    "pluginlist",
+    "pluginsync",
+    "pluginupdate",
+    "q",
+    "qa",
+    "reg",
+    "rg",
+    "set",
+    "split",
+    "tabc",
+    "tabfir",
+    "tabl",
+    "tabn",
+    "tabnew",
+    "tabo",
+    "tabp",
+    "tabs",
+    "tl",
+    "topen",
+    "vsplit",
+    "w",
+    "wdiff",
+    "wq",
+    "z",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandSpec {
+    pub name: String,
+    pub bang: bool,
+    pub description: Option<String>,
+}
+
+pub fn create_command_table(lua: &Lua) -> LuaResult<LuaTable> {
+    let command_table = lua.create_table()?;
+
+    let commands_list = lua.create_table()?;
+    command_table.set("_commands", commands_list)?;
+
+    let register_fn = lua.create_function(
+        |lua, (name, callback, opts): (String, LuaValue, Option<LuaTable>)| {
+            if !is_valid_name(&name) {
+                tracing::error!(
+                    "y.command.register() called with invalid name '{}', expected letters, digits, '-' or '_'",
+                    name
+                );
+                return Ok(());
+            }
+
+            if BUILTIN_COMMANDS.contains(&name.as_str()) {
+                tracing::error!(
+                    "y.command.register() called with '{}', which is a built-in command",
+                    name
+                );
+                return Ok(());
+            }
+
+            if !matches!(callback, LuaValue::Function(_)) {
+                tracing::error!(
+                    "y.command.register() called with {} for '{}', expected function",
+                    callback.type_name(),
+                    name
+                );
+                return Ok(());
+            }
+
+            let entry = lua.create_table()?;
+            entry.set("callback", callback)?;
+            if let Some(opts) = opts {
+                entry.set("bang", opts.get::<Option<bool>>("bang")?.unwrap_or(false))?;
+                entry.set("desc", opts.get::<Option<String>>("desc")?)?;
+                match opts.get::<LuaValue>("complete")? {
+                    complete @ (LuaValue::Table(_) | LuaValue::Function(_)) => {
+                        entry.set("complete", complete)?;
+                    }
+                    LuaValue::Nil => {}
+                    other => {
+                        tracing::warn!(
+                            "y.command.register() complete for '{}' expected table or function, got {}",
+                            name,
+                            other.type_name()
+                        );
+                    }
+                }
+            }
+
+            get_commands(lua)?.set(name, entry)
+        },
+    )?;
+
+    command_table.set("register", register_fn)?;
+
+    Ok(command_table)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn get_commands(lua: &Lua) -> LuaResult<LuaTable> {
+    let y: LuaTable = lua.globals().get("y")?;
+    let command: LuaTable = y.get("command")?;
+    command.get("_commands")
+}
+
+pub fn read_commands(lua: &Lua) -> Vec<CommandSpec> {
+    let Ok(commands) = get_commands(lua) else {
+        return Vec::new();
+    };
+
+    let mut specs: Vec<_> = commands
+        .pairs::<String, LuaTable>()
+        .flatten()
+        .map(|(name, entry)| CommandSpec {
+            name,
+            bang: entry
+                .get::<Option<bool>>("bang")
+                .ok()
+                .flatten()
+                .unwrap_or(false),
+            description: entry.get::<Option<String>>("desc").ok().flatten(),
+        })
+        .collect();
+
+    specs.sort_by(|a, b| a.name.cmp(&b.name));
+    specs
+}
+
+pub fn get_command(lua: &Lua, name: &str) -> Option<CommandSpec> {
+    let (name, bang) = match name.strip_suffix('!') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+
+    read_commands(lua)
+        .into_iter()
+        .find(|spec| spec.name == name && (spec.bang || !bang))
+}
+
+pub fn invoke_command(lua: &Lua, name: &str, args: &str) -> Result<(), String> {
+    try_invoke_command(lua, name, args).map_err(|err| {
+        tracing::error!("error in y.command '{}': {:?}", name, err);
+        err.to_string()
+    })
+}
+
+fn try_invoke_command(lua: &Lua, name: &str, args: &str) -> LuaResult<()> {
+    let (name, bang) = match name.strip_suffix('!') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+
+    let entry: LuaTable = get_commands(lua)?.get(name)?;
+    let callback: LuaFunction = entry.get("callback")?;
+
+    let ctx = lua.create_table()?;
+    ctx.set("name", name)?;
+    ctx.set("args", args)?;
+    ctx.set("fargs", lua.create_sequence_from(args.split_whitespace())?)?;
+    ctx.set("bang", bang)?;
+
+    callback.call::<()>(ctx)
+}
+
+pub fn complete_command(lua: &Lua, name: &str, lead: &str) -> Vec<String> {
+    match try_complete_command(lua, name.trim_end_matches('!'), lead) {
+        Ok(candidates) => candidates,
+        Err(err) => {
+            tracing::error!("error in y.command '{}' completion: {:?}", name, err);
+            Vec::new()
+        }
+    }
+}
+
+fn try_complete_command(lua: &Lua, name: &str, lead: &str) -> LuaResult<Vec<String>> {
+    let Some(entry) = get_commands(lua)?.get::<Option<LuaTable>>(name)? else {
+        return Ok(Vec::new());
+    };
+
+    let candidates: LuaTable = match entry.get::<LuaValue>("complete")? {
+        LuaValue::Table(candidates) => candidates,
+        LuaValue::Function(complete) => complete.call(lead)?,
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(candidates
+        .sequence_values::<String>()
+        .flatten()
+        .filter(|candidate| candidate.starts_with(lead))
+        .collect())
+}
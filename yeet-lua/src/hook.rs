@@ -31,6 +31,21 @@ impl BufferType {
     }
 }
 
+/// Image properties exposed to `y.hook.on_window_change` as `ctx.preview.image`
+/// when the preview shows an image.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageMetadata {
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub color_type: String,
+    pub bits_per_pixel: u16,
+    pub size: u64,
+    pub camera: Option<String>,
+    pub date: Option<String>,
+    pub orientation: Option<String>,
+}
+
 pub fn invoke_on_window_create(
     lua: &crate::LuaConfiguration,
     window_type: &str,
@@ -113,6 +128,20 @@ fn build_context(
     Ok(ctx)
 }
 
+fn image_to_table(lua: &Lua, image: &ImageMetadata) -> LuaResult<LuaTable> {
+    let table = lua.create_table()?;
+    table.set("width", image.width)?;
+    table.set("height", image.height)?;
+    table.set("format", image.format.as_str())?;
+    table.set("color_type", image.color_type.as_str())?;
+    table.set("bits_per_pixel", image.bits_per_pixel)?;
+    table.set("size", image.size)?;
+    table.set("camera", image.camera.as_deref())?;
+    table.set("date", image.date.as_deref())?;
+    table.set("orientation", image.orientation.as_deref())?;
+    Ok(table)
+}
+
 fn read_back_context(ctx: &LuaTable, window_type: &str, viewports: &mut [&mut ViewPort]) {
     match window_type {
         "directory" => {
@@ -143,8 +172,11 @@ pub fn invoke_on_window_change(
     viewport_paths: [Option<&Path>; 3],
     viewports: &mut [&mut ViewPort],
     buffer_types: [Option<&str>; 3],
+    preview_image: Option<&ImageMetadata>,
 ) {
-    if let Err(err) = try_invoke_on_window_change(lua, viewport_paths, viewports, buffer_types) {
+    if let Err(err) =
+        try_invoke_on_window_change(lua, viewport_paths, viewports, buffer_types, preview_image)
+    {
         tracing::error!("error in y.hook.on_window_change: {:?}", err);
     }
 }
@@ -154,6 +186,7 @@ fn try_invoke_on_window_change(
     viewport_paths: [Option<&Path>; 3],
     viewports: &mut [&mut ViewPort],
     buffer_types: [Option<&str>; 3],
+    preview_image: Option<&ImageMetadata>,
 ) -> LuaResult<()> {
     let y: LuaTable = lua.globals().get("y")?;
     let hook: LuaTable = y.get("hook")?;
@@ -178,6 +211,10 @@ fn try_invoke_on_window_change(
         }
     }
 
+    if let (Some(image), Ok(preview)) = (preview_image, ctx.get::<LuaTable>("preview")) {
+        preview.set("image", image_to_table(lua, image)?)?;
+    }
+
     for i in 1..=len {
         let func: LuaValue = hook_table.raw_get(i)?;
         match func {
@@ -209,6 +246,7 @@ fn try_invoke_on_window_change(
 ///     (e.g., "directory", "content", "help", "quickfix", "tasks")
 ///   - `path`: the associated path (string) — only set for buffer types with an associated path
 ///     (directory, content); absent/nil for help, quickfix, tasks
+///   - `mime`: the detected file type (string) — only set for content buffers
 /// - `prefix`: the bufferline prefix (string or nil), mutable
 /// - `content`: the bufferline content as string, mutable
 ///
@@ -220,8 +258,9 @@ pub fn invoke_on_bufferline_mutate(
     bl: &mut BufferLine,
     buffer_type: BufferType,
     path: Option<&Path>,
+    mime: Option<&str>,
 ) {
-    if let Err(err) = try_invoke_on_bufferline_mutate(lua, bl, buffer_type, path) {
+    if let Err(err) = try_invoke_on_bufferline_mutate(lua, bl, buffer_type, path, mime) {
         tracing::error!("error in y.hook.on_bufferline_mutate: {:?}", err);
     }
 }
@@ -231,6 +270,7 @@ fn try_invoke_on_bufferline_mutate(
     bl: &mut BufferLine,
     buffer_type: BufferType,
     path: Option<&Path>,
+    mime: Option<&str>,
 ) -> LuaResult<()> {
     let y: LuaTable = lua.globals().get("y")?;
     let hook: LuaTable = y.get("hook")?;
@@ -248,6 +288,9 @@ fn try_invoke_on_bufferline_mutate(
     if let Some(p) = path {
         buffer_meta.set("path", p.to_string_lossy().to_string())?;
     }
+    if let Some(mime) = mime {
+        buffer_meta.set("mime", mime)?;
+    }
     ctx.set("buffer", buffer_meta)?;
 
     if let Some(prefix) = &bl.prefix {
@@ -289,6 +332,129 @@ fn try_invoke_on_bufferline_mutate(
     Ok(())
 }
 
+/// Program returned by a `y.hook.on_open` callback to open a file instead of
+/// the system default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub terminal: bool,
+}
+
+/// Returns true if at least one `y.hook.on_open` callback is registered, so
+/// the file type only has to be detected when a callback can use it.
+pub fn has_on_open(lua: &crate::LuaConfiguration) -> bool {
+    match get_on_open(lua) {
+        Ok(hook_table) => hook_table.is_some(),
+        Err(err) => {
+            tracing::error!("error in y.hook.on_open: {:?}", err);
+            false
+        }
+    }
+}
+
+fn get_on_open(lua: &Lua) -> LuaResult<Option<LuaTable>> {
+    let y: LuaTable = lua.globals().get("y")?;
+    let hook: LuaTable = y.get("hook")?;
+    let hook_table = hook.get::<Option<LuaTable>>("on_open")?;
+
+    Ok(hook_table.filter(|hook_table| hook_table.raw_len() > 0))
+}
+
+/// Invokes all `y.hook.on_open` callbacks with a context table containing:
+/// - `path`: the file to open (string)
+/// - `mime`: the detected file type (string or nil)
+/// - `extension`: the file extension without dot (string or nil)
+///
+/// Callbacks return nil to decline, or a table `{ cmd, args, terminal }`.
+/// The first callback returning a table wins. `args` defaults to `{ ctx.path }`.
+pub fn invoke_on_open(
+    lua: &crate::LuaConfiguration,
+    path: &Path,
+    mime: Option<&str>,
+) -> Option<OpenCommand> {
+    match try_invoke_on_open(lua, path, mime) {
+        Ok(command) => command,
+        Err(err) => {
+            tracing::error!("error in y.hook.on_open: {:?}", err);
+            None
+        }
+    }
+}
+
+fn try_invoke_on_open(
+    lua: &Lua,
+    path: &Path,
+    mime: Option<&str>,
+) -> LuaResult<Option<OpenCommand>> {
+    let Some(hook_table) = get_on_open(lua)? else {
+        return Ok(None);
+    };
+
+    let len = hook_table.raw_len();
+
+    let path_string = path.to_string_lossy().to_string();
+    let ctx = lua.create_table()?;
+    ctx.set("path", path_string.as_str())?;
+    ctx.set("mime", mime)?;
+    ctx.set(
+        "extension",
+        path.extension()
+            .map(|extension| extension.to_string_lossy().to_string()),
+    )?;
+
+    for i in 1..=len {
+        let func: LuaValue = hook_table.raw_get(i)?;
+        let result = match func {
+            LuaValue::Function(f) => match f.call::<LuaValue>(ctx.clone()) {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::error!("error in y.hook.on_open callback {}: {:?}", i, err);
+                    continue;
+                }
+            },
+            _ => {
+                tracing::warn!(
+                    "y.hook.on_open[{}] is not a function, got {:?}",
+                    i,
+                    func.type_name()
+                );
+                continue;
+            }
+        };
+
+        match result {
+            LuaValue::Nil | LuaValue::Boolean(false) => {}
+            LuaValue::Table(command) => {
+                let Some(program) = command.get::<Option<String>>("cmd")? else {
+                    tracing::warn!("y.hook.on_open callback {} returned table without cmd", i);
+                    continue;
+                };
+
+                let args = match command.get::<Option<Vec<String>>>("args")? {
+                    Some(args) => args,
+                    None => vec![path_string.clone()],
+                };
+
+                return Ok(Some(OpenCommand {
+                    program,
+                    args,
+                    terminal: command.get::<Option<bool>>("terminal")?.unwrap_or(false),
+                }));
+            }
+            other => {
+                tracing::warn!(
+                    "y.hook.on_open callback {} returned {}, expected table or nil",
+                    i,
+                    other.type_name()
+                );
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +494,32 @@ mod tests {
         lua
     }
 
+    #[test]
+    fn bufferline_hook_receives_mime_for_content() {
+        let lua = create_lua_with_hook(
+            r#"
+            y.hook.on_bufferline_mutate = setmetatable({}, getmetatable(y.hook.on_window_create))
+            y.hook.on_bufferline_mutate:add(function(ctx)
+                ctx.prefix = ctx.buffer.mime
+            end)
+            "#,
+        );
+
+        let mut line = BufferLine::default();
+        invoke_on_bufferline_mutate(
+            &lua,
+            &mut line,
+            BufferType::Content,
+            Some(Path::new("/dir/run")),
+            Some("text/x-python"),
+        );
+        assert_eq!(line.prefix.as_deref(), Some("text/x-python"));
+
+        let mut line = BufferLine::default();
+        invoke_on_bufferline_mutate(&lua, &mut line, BufferType::Help, None, None);
+        assert_eq!(line.prefix, None);
+    }
+
     #[test]
     fn directory_hook_sets_preview_wrap() {
         let lua = create_lua_with_hook(
@@ -824,6 +1016,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert!(
@@ -852,6 +1045,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert_eq!(preview.prefix_column_width, 2);
@@ -881,6 +1075,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert_eq!(
@@ -915,6 +1110,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("content")],
+            None,
         );
 
         assert_eq!(
@@ -945,6 +1141,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert!(
@@ -969,6 +1166,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert_eq!(
@@ -1003,6 +1201,7 @@ mod tests {
             ],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         let globals = lua.globals();
@@ -1042,6 +1241,7 @@ mod tests {
             [None, None, None],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("content")],
+            None,
         );
 
         let globals = lua.globals();
@@ -1056,6 +1256,51 @@ mod tests {
         assert_eq!(globals.get::<String>("test_preview_bt").unwrap(), "content");
     }
 
+    #[test]
+    fn on_window_change_preview_image_metadata() {
+        let lua = create_lua_with_hook(
+            r#"
+            y.hook.on_window_change:add(function(ctx)
+                _G.test_image = ctx.preview.image
+                _G.test_parent_image = ctx.parent.image
+            end)
+            "#,
+        );
+
+        let mut parent = ViewPort::default();
+        let mut current = ViewPort::default();
+        let mut preview = ViewPort::default();
+        let image = ImageMetadata {
+            width: 640,
+            height: 480,
+            format: "JPEG".to_string(),
+            color_type: "Rgb8".to_string(),
+            bits_per_pixel: 24,
+            size: 1024,
+            camera: Some("Canon EOS R6".to_string()),
+            date: Some("2024-05-01 12:30:00".to_string()),
+            orientation: None,
+        };
+
+        invoke_on_window_change(
+            &lua,
+            [None, None, None],
+            &mut [&mut parent, &mut current, &mut preview],
+            [Some("directory"), Some("directory"), Some("image")],
+            Some(&image),
+        );
+
+        let globals = lua.globals();
+        let table: LuaTable = globals.get("test_image").unwrap();
+        assert_eq!(table.get::<u32>("width").unwrap(), 640);
+        assert_eq!(table.get::<u32>("height").unwrap(), 480);
+        assert_eq!(table.get::<String>("format").unwrap(), "JPEG");
+        assert_eq!(table.get::<u16>("bits_per_pixel").unwrap(), 24);
+        assert_eq!(table.get::<String>("camera").unwrap(), "Canon EOS R6");
+        assert!(table.get::<LuaValue>("orientation").unwrap() == LuaValue::Nil);
+        assert!(globals.get::<LuaValue>("test_parent_image").unwrap() == LuaValue::Nil);
+    }
+
     #[test]
     fn on_window_change_via_real_init() {
         let mut tmp = NamedTempFile::new().unwrap();
@@ -1089,6 +1334,7 @@ mod tests {
             ],
             &mut [&mut parent, &mut current, &mut preview],
             [Some("directory"), Some("directory"), Some("directory")],
+            None,
         );
 
         assert_eq!(preview.prefix_column_width, 2);
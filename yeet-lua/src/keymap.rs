@@ -0,0 +1,147 @@
+use mlua::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeymapAction {
+    Command(String),
+    Function(usize),
+    KeySequence(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeymapChange {
+    Delete(String, String),
+    Set(String, String, KeymapAction),
+}
+
+pub fn create_keymap_table(lua: &Lua) -> LuaResult<LuaTable> {
+    let keymap_table = lua.create_table()?;
+
+    let mappings_list = lua.create_table()?;
+    keymap_table.set("_mappings", mappings_list)?;
+
+    let set_fn =
+        lua.create_function(|lua, (mode, keys, action): (LuaValue, String, LuaValue)| {
+            match action {
+                LuaValue::String(_) | LuaValue::Function(_) => {}
+                _ => {
+                    tracing::error!(
+                        "y.keymap.set() called with {} action, expected string or function",
+                        action.type_name()
+                    );
+                    return Ok(());
+                }
+            }
+
+            for mode in get_modes("y.keymap.set()", mode)? {
+                let entry = lua.create_table()?;
+                entry.set("mode", mode)?;
+                entry.set("keys", keys.clone())?;
+                entry.set("action", action.clone())?;
+                push_mapping(lua, entry)?;
+            }
+
+            Ok(())
+        })?;
+
+    let del_fn = lua.create_function(|lua, (mode, keys): (LuaValue, String)| {
+        for mode in get_modes("y.keymap.del()", mode)? {
+            let entry = lua.create_table()?;
+            entry.set("mode", mode)?;
+            entry.set("keys", keys.clone())?;
+            push_mapping(lua, entry)?;
+        }
+
+        Ok(())
+    })?;
+
+    keymap_table.set("set", set_fn)?;
+    keymap_table.set("del", del_fn)?;
+
+    Ok(keymap_table)
+}
+
+fn get_modes(function: &str, mode: LuaValue) -> LuaResult<Vec<String>> {
+    match mode {
+        LuaValue::String(mode) => Ok(vec![mode.to_str()?.to_string()]),
+        LuaValue::Table(modes) => modes.sequence_values::<String>().collect(),
+        _ => {
+            tracing::error!(
+                "{} called with {} mode, expected string or table",
+                function,
+                mode.type_name()
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn push_mapping(lua: &Lua, entry: LuaTable) -> LuaResult<()> {
+    let y: LuaTable = lua.globals().get("y")?;
+    let keymap: LuaTable = y.get("keymap")?;
+    let mappings: LuaTable = keymap.get("_mappings")?;
+    let len = mappings.raw_len();
+    mappings.raw_set(len + 1, entry)
+}
+
+fn get_mappings(lua: &Lua) -> LuaResult<LuaTable> {
+    let y: LuaTable = lua.globals().get("y")?;
+    let keymap: LuaTable = y.get("keymap")?;
+    keymap.get("_mappings")
+}
+
+pub fn read_keymap_changes(lua: &Lua) -> Vec<KeymapChange> {
+    let Ok(mappings) = get_mappings(lua) else {
+        return Vec::new();
+    };
+
+    let mut changes = Vec::new();
+    for (index, entry) in mappings.sequence_values::<LuaTable>().flatten().enumerate() {
+        let (Ok(mode), Ok(keys)) = (entry.get::<String>("mode"), entry.get::<String>("keys"))
+        else {
+            continue;
+        };
+
+        let change = match entry.get::<LuaValue>("action") {
+            Ok(LuaValue::String(action)) => {
+                let Ok(action) = action.to_str() else {
+                    continue;
+                };
+
+                let action = match action.strip_prefix(':') {
+                    Some(command) => KeymapAction::Command(command.to_string()),
+                    None => KeymapAction::KeySequence(action.to_string()),
+                };
+                KeymapChange::Set(mode, keys, action)
+            }
+            Ok(LuaValue::Function(_)) => {
+                KeymapChange::Set(mode, keys, KeymapAction::Function(index + 1))
+            }
+            _ => KeymapChange::Delete(mode, keys),
+        };
+        changes.push(change);
+    }
+
+    changes
+}
+
+pub fn invoke_keymap_function(lua: &Lua, id: usize) {
+    if let Err(err) = try_invoke_keymap_function(lua, id) {
+        tracing::error!("error in y.keymap function {}: {:?}", id, err);
+    }
+}
+
+fn try_invoke_keymap_function(lua: &Lua, id: usize) -> LuaResult<()> {
+    let mappings = get_mappings(lua)?;
+    let entry: LuaTable = mappings.raw_get(id)?;
+    match entry.get::<LuaValue>("action")? {
+        LuaValue::Function(function) => function.call::<()>(()),
+        other => {
+            tracing::warn!(
+                "y.keymap mapping {} is not a function, got {}",
+                id,
+                other.type_name()
+            );
+            Ok(())
+        }
+    }
+}
@@ -1,12 +1,26 @@
+mod api;
+mod command;
 mod hook;
+mod keymap;
 mod loading;
 mod plugin;
 mod viewport;
 
+pub use api::{
+    clear_api_state, set_api_state, take_api_requests, ApiJunk, ApiRequest, ApiState, ApiTab,
+};
+pub use command::{
+    complete_command, get_command, invoke_command, read_commands, CommandSpec, BUILTIN_COMMANDS,
+};
+pub use hook::has_on_open;
 pub use hook::invoke_on_bufferline_mutate;
+pub use hook::invoke_on_open;
 pub use hook::invoke_on_window_change;
 pub use hook::invoke_on_window_create;
 pub use hook::BufferType;
+pub use hook::ImageMetadata;
+pub use hook::OpenCommand;
+pub use keymap::{invoke_keymap_function, read_keymap_changes, KeymapAction, KeymapChange};
 pub use loading::load_plugins;
 pub use mlua::Lua;
 pub use plugin::{read_plugin_concurrency, read_plugin_data_path, read_plugin_specs};
@@ -42,10 +56,30 @@ pub fn init() -> Option<Lua> {
     }
 }
 
+pub fn init_from_script(script: &str) -> LuaResult<Lua> {
+    let lua = Lua::new();
+    setup(&lua)?;
+    lua.load(script).exec()?;
+
+    Ok(lua)
+}
+
 fn setup_and_execute(lua: &Lua, config_path: &PathBuf) -> LuaResult<()> {
+    setup(lua)?;
+
+    let content = std::fs::read_to_string(config_path).map_err(LuaError::external)?;
+    lua.load(&content)
+        .set_name(config_path.to_string_lossy())
+        .exec()?;
+
+    Ok(())
+}
+
+fn setup(lua: &Lua) -> LuaResult<()> {
     let y_table = lua.create_table()?;
     let theme_table = lua.create_table()?;
     let hook_table = lua.create_table()?;
+    let preview_table = lua.create_table()?;
 
     let hook_mt = create_hook_metatable(lua)?;
     let on_window_create = lua.create_table()?;
@@ -57,10 +91,17 @@ fn setup_and_execute(lua: &Lua, config_path: &PathBuf) -> LuaResult<()> {
     hook_table.set("on_window_change", on_window_change)?;
 
     let on_bufferline_mutate = lua.create_table()?;
-    let _ = on_bufferline_mutate.set_metatable(Some(hook_mt));
+    let _ = on_bufferline_mutate.set_metatable(Some(hook_mt.clone()));
     hook_table.set("on_bufferline_mutate", on_bufferline_mutate)?;
 
+    let on_open = lua.create_table()?;
+    let _ = on_open.set_metatable(Some(hook_mt));
+    hook_table.set("on_open", on_open)?;
+
     let plugin_table = plugin::create_plugin_table(lua)?;
+    let keymap_table = keymap::create_keymap_table(lua)?;
+    let command_table = command::create_command_table(lua)?;
+    let api_table = api::create_api_table(lua)?;
 
     if let Some(data_path) = yeet_plugin::resolve_plugin_data_path() {
         plugin_table.set("_data_path", data_path.to_string_lossy().to_string())?;
@@ -69,16 +110,13 @@ fn setup_and_execute(lua: &Lua, config_path: &PathBuf) -> LuaResult<()> {
     y_table.set("theme", theme_table)?;
     y_table.set("hook", hook_table)?;
     y_table.set("plugin", plugin_table)?;
+    y_table.set("preview", preview_table)?;
+    y_table.set("keymap", keymap_table)?;
+    y_table.set("command", command_table)?;
+    y_table.set("api", api_table)?;
 
     protect_y_table(lua, y_table)?;
-    install_plugin_searcher(lua)?;
-
-    let content = std::fs::read_to_string(config_path).map_err(LuaError::external)?;
-    lua.load(&content)
-        .set_name(config_path.to_string_lossy())
-        .exec()?;
-
-    Ok(())
+    install_plugin_searcher(lua)
 }
 
 fn protect_y_table(lua: &Lua, y_table: LuaTable) -> LuaResult<()> {
@@ -229,6 +267,38 @@ fn resolve_config_path() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join(".config").join("yeet").join("init.lua"))
 }
 
+pub fn read_conflict_policy(lua: &LuaConfiguration) -> Option<String> {
+    let y = lua.globals().get::<LuaTable>("y").ok()?;
+    match y.get::<LuaValue>("conflict") {
+        Ok(LuaValue::String(value)) => value.to_str().ok().map(|it| it.to_string()),
+        Ok(LuaValue::Nil) | Err(_) => None,
+        Ok(other) => {
+            tracing::warn!(
+                "y.conflict expected string, got {}, using default",
+                other.type_name()
+            );
+            None
+        }
+    }
+}
+
+pub fn read_preview_limit(lua: &LuaConfiguration, key: &str) -> Option<usize> {
+    let y = lua.globals().get::<LuaTable>("y").ok()?;
+    let preview = y.get::<LuaTable>("preview").ok()?;
+    match preview.get::<LuaValue>(key) {
+        Ok(LuaValue::Integer(value)) if value > 0 => Some(value as usize),
+        Ok(LuaValue::Nil) | Err(_) => None,
+        Ok(other) => {
+            tracing::warn!(
+                "y.preview.{} expected positive integer, got {}, using default",
+                key,
+                other.type_name()
+            );
+            None
+        }
+    }
+}
+
 pub fn read_theme_pairs(lua: &LuaConfiguration) -> Vec<(String, LuaValue)> {
     let mut pairs = Vec::new();
     let Ok(y) = lua.globals().get::<LuaTable>("y") else {
@@ -247,6 +317,7 @@ pub fn read_theme_pairs(lua: &LuaConfiguration) -> Vec<(String, LuaValue)> {
 mod tests {
     use super::*;
     use std::io::Write;
+    use std::path::Path;
     use tempfile::NamedTempFile;
 
     fn create_lua_from_script(script: &str) -> Lua {
@@ -294,6 +365,85 @@ mod tests {
         assert_eq!(owc.raw_len(), 3);
     }
 
+    #[test]
+    fn on_open_returns_first_matching_rule() {
+        let lua = create_lua_from_script(
+            r#"
+            local rules = {
+                { pattern = "%.md$", cmd = "nvim", terminal = true },
+                { mime = "^image/", cmd = "imv" },
+            }
+
+            y.hook.on_open:add(function(ctx)
+                if ctx.extension == "log" then
+                    return { cmd = "less", args = { "+G", ctx.path }, terminal = true }
+                end
+            end)
+            y.hook.on_open:add(function(ctx)
+                for _, rule in ipairs(rules) do
+                    if (rule.pattern and ctx.path:match(rule.pattern))
+                        or (rule.mime and ctx.mime and ctx.mime:match(rule.mime)) then
+                        return { cmd = rule.cmd, terminal = rule.terminal }
+                    end
+                end
+            end)
+            "#,
+        );
+
+        assert_eq!(
+            invoke_on_open(&lua, Path::new("/home/README.md"), Some("text/markdown")),
+            Some(OpenCommand {
+                program: "nvim".to_string(),
+                args: vec!["/home/README.md".to_string()],
+                terminal: true,
+            })
+        );
+        assert_eq!(
+            invoke_on_open(&lua, Path::new("/home/a.png"), Some("image/png")),
+            Some(OpenCommand {
+                program: "imv".to_string(),
+                args: vec!["/home/a.png".to_string()],
+                terminal: false,
+            })
+        );
+        assert_eq!(
+            invoke_on_open(&lua, Path::new("/var/app.log"), None),
+            Some(OpenCommand {
+                program: "less".to_string(),
+                args: vec!["+G".to_string(), "/var/app.log".to_string()],
+                terminal: true,
+            })
+        );
+    }
+
+    #[test]
+    fn on_open_declines_without_matching_callback() {
+        let lua = create_lua_from_script(
+            r#"
+            y.hook.on_open:add(function(ctx) error("broken") end)
+            y.hook.on_open:add(function(ctx) return { args = { ctx.path } } end)
+            y.hook.on_open:add(function(ctx) return nil end)
+            "#,
+        );
+
+        assert_eq!(invoke_on_open(&lua, Path::new("/home/a.pdf"), None), None);
+        assert_eq!(
+            invoke_on_open(&create_lua_from_script(""), Path::new("/a"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn has_on_open_is_false_without_callbacks() {
+        assert!(!has_on_open(&create_lua_from_script("")));
+        assert!(!has_on_open(&create_lua_from_script(
+            r#"y.hook.on_open:add("not a function")"#
+        )));
+        assert!(has_on_open(&create_lua_from_script(
+            "y.hook.on_open:add(function(ctx) end)"
+        )));
+    }
+
     #[test]
     fn add_non_function_is_ignored() {
         let lua = create_lua_from_script(
@@ -562,4 +712,305 @@ mod tests {
         let specs = read_plugin_specs(&lua);
         assert_eq!(specs.len(), 1);
     }
+
+    #[test]
+    fn conflict_policy_default() {
+        let lua = create_lua_from_script("");
+        assert_eq!(read_conflict_policy(&lua), None);
+    }
+
+    #[test]
+    fn conflict_policy_custom() {
+        let lua = create_lua_from_script(r#"y.conflict = "rename""#);
+        assert_eq!(read_conflict_policy(&lua).as_deref(), Some("rename"));
+    }
+
+    #[test]
+    fn conflict_policy_invalid_type_is_ignored() {
+        let lua = create_lua_from_script("y.conflict = 1");
+        assert_eq!(read_conflict_policy(&lua), None);
+    }
+
+    #[test]
+    fn preview_limit_default() {
+        let lua = create_lua_from_script("");
+        assert_eq!(read_preview_limit(&lua, "max_lines"), None);
+    }
+
+    #[test]
+    fn preview_limit_custom() {
+        let lua = create_lua_from_script("y.preview.max_lines = 200");
+        assert_eq!(read_preview_limit(&lua, "max_lines"), Some(200));
+    }
+
+    #[test]
+    fn preview_limit_invalid_value_is_ignored() {
+        let lua = create_lua_from_script("y.preview.max_bytes = -1\ny.preview.max_lines = 'all'");
+        assert_eq!(read_preview_limit(&lua, "max_bytes"), None);
+        assert_eq!(read_preview_limit(&lua, "max_lines"), None);
+    }
+
+    #[test]
+    fn keymap_set_and_del_are_read_in_order() {
+        let lua = create_lua_from_script(
+            r#"
+            y.keymap.set("navigation", "<C-s>", ":split")
+            y.keymap.set({ "normal", "visual" }, "H", "0")
+            y.keymap.del("navigation", "gt")
+            "#,
+        );
+        assert_eq!(
+            read_keymap_changes(&lua),
+            vec![
+                KeymapChange::Set(
+                    "navigation".to_string(),
+                    "<C-s>".to_string(),
+                    KeymapAction::Command("split".to_string())
+                ),
+                KeymapChange::Set(
+                    "normal".to_string(),
+                    "H".to_string(),
+                    KeymapAction::KeySequence("0".to_string())
+                ),
+                KeymapChange::Set(
+                    "visual".to_string(),
+                    "H".to_string(),
+                    KeymapAction::KeySequence("0".to_string())
+                ),
+                KeymapChange::Delete("navigation".to_string(), "gt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keymap_set_with_invalid_action_is_ignored() {
+        let lua = create_lua_from_script(r#"y.keymap.set("navigation", "x", 42)"#);
+        assert!(read_keymap_changes(&lua).is_empty());
+    }
+
+    #[test]
+    fn keymap_function_is_invoked_by_id() {
+        let lua = create_lua_from_script(
+            r#"
+            called = 0
+            y.keymap.set("navigation", "x", ":quit")
+            y.keymap.set("navigation", "<C-x>", function() called = called + 1 end)
+            "#,
+        );
+        let changes = read_keymap_changes(&lua);
+        let id = match &changes[1] {
+            KeymapChange::Set(_, _, KeymapAction::Function(id)) => *id,
+            _ => panic!("expected function mapping"),
+        };
+
+        invoke_keymap_function(&lua, id);
+
+        let called: i64 = lua.globals().get("called").unwrap();
+        assert_eq!(called, 1);
+    }
+
+    #[test]
+    fn command_register_is_read_sorted() {
+        let lua = create_lua_from_script(
+            r#"
+            y.command.register("deploy-artifacts", function(ctx) end, { bang = true, desc = "Deploy" })
+            y.command.register("build", function(ctx) end)
+            "#,
+        );
+        assert_eq!(
+            read_commands(&lua),
+            vec![
+                CommandSpec {
+                    name: "build".to_string(),
+                    bang: false,
+                    description: None,
+                },
+                CommandSpec {
+                    name: "deploy-artifacts".to_string(),
+                    bang: true,
+                    description: Some("Deploy".to_string()),
+                },
+            ]
+        );
+        assert!(get_command(&lua, "deploy-artifacts!").is_some());
+        assert!(get_command(&lua, "build!").is_none());
+    }
+
+    #[test]
+    fn command_register_with_invalid_or_builtin_name_or_callback_is_ignored() {
+        let lua = create_lua_from_script(
+            r#"
+            y.command.register("has space", function(ctx) end)
+            y.command.register("", function(ctx) end)
+            y.command.register("split", function(ctx) end)
+            y.command.register("build", "not a function")
+            "#,
+        );
+        assert!(read_commands(&lua).is_empty());
+    }
+
+    #[test]
+    fn command_invoke_passes_args_and_bang() {
+        let lua = create_lua_from_script(
+            r#"
+            y.command.register("deploy", function(ctx)
+                result = ctx.name .. ":" .. ctx.args .. ":" .. #ctx.fargs .. ":" .. tostring(ctx.bang)
+            end, { bang = true })
+            y.command.register("fail", function(ctx) error("failed") end)
+            "#,
+        );
+
+        assert!(invoke_command(&lua, "deploy!", "prod  eu").is_ok());
+        let result: String = lua.globals().get("result").unwrap();
+        assert_eq!(result, "deploy:prod  eu:2:true");
+
+        assert!(invoke_command(&lua, "fail", "").is_err());
+    }
+
+    #[test]
+    fn command_complete_filters_candidates_by_lead() {
+        let lua = create_lua_from_script(
+            r#"
+            y.command.register("deploy", function(ctx) end, { complete = { "prod", "preview", "dev" } })
+            y.command.register("env", function(ctx) end, {
+                complete = function(lead) return { lead .. "1", "other" } end,
+            })
+            "#,
+        );
+        assert_eq!(
+            complete_command(&lua, "deploy", "pr"),
+            vec!["prod", "preview"]
+        );
+        assert_eq!(complete_command(&lua, "env", "x"), vec!["x1"]);
+        assert!(complete_command(&lua, "unknown", "").is_empty());
+    }
+
+    #[test]
+    fn api_getters_return_nil_and_empty_tables_without_state() {
+        let lua = create_lua_from_script(
+            r#"
+            mode = y.api.get_mode()
+            marks = y.api.get_marks()
+            tabs = y.api.get_tabs()
+            "#,
+        );
+        assert_eq!(
+            lua.globals().get::<LuaValue>("mode").unwrap(),
+            LuaValue::Nil
+        );
+        assert_eq!(lua.globals().get::<LuaTable>("marks").unwrap().raw_len(), 0);
+        assert_eq!(lua.globals().get::<LuaTable>("tabs").unwrap().raw_len(), 0);
+    }
+
+    #[test]
+    fn api_getters_read_state() {
+        let lua = create_lua_from_script("");
+        set_api_state(
+            &lua,
+            ApiState {
+                mode: "navigation".to_string(),
+                current_path: Some(PathBuf::from("/home")),
+                selection: Some(PathBuf::from("/home/a.txt")),
+                qfix: vec![PathBuf::from("/home/a.txt"), PathBuf::from("/home/b.txt")],
+                qfix_index: 1,
+                marks: vec![('a', PathBuf::from("/tmp"))],
+                junkyard: vec![ApiJunk {
+                    register: '"',
+                    paths: vec![PathBuf::from("/home/c.txt")],
+                    ready: true,
+                }],
+                tabs: vec![ApiTab {
+                    id: 2,
+                    path: Some(PathBuf::from("/home")),
+                    current: true,
+                }],
+            },
+        );
+
+        let result: String = lua
+            .load(
+                r#"
+                local qfix = y.api.get_qfix()
+                local junk = y.api.get_junkyard()[1]
+                local tab = y.api.get_tabs()[1]
+                return table.concat({
+                    y.api.get_mode(),
+                    y.api.get_current_path(),
+                    y.api.get_selection(),
+                    qfix.entries[qfix.current],
+                    y.api.get_marks().a,
+                    junk.register .. junk.paths[1] .. tostring(junk.ready),
+                    tab.id .. tab.path .. tostring(tab.current),
+                }, ",")
+                "#,
+            )
+            .eval()
+            .unwrap();
+
+        assert_eq!(
+            result,
+            "navigation,/home,/home/a.txt,/home/b.txt,/tmp,\"/home/c.txttrue,2/hometrue"
+        );
+    }
+
+    #[test]
+    fn api_actions_are_queued_while_state_is_set() {
+        let lua = create_lua_from_script("y.api.navigate('/ignored')");
+        assert!(take_api_requests(&lua).is_empty());
+
+        set_api_state(&lua, ApiState::default());
+        lua.load(
+            r#"
+            y.api.exec(":split /tmp")
+            y.api.feedkeys("gg")
+            y.api.navigate("/home")
+            y.api.print("done")
+            y.api.qfix.add({ "/a", "/b" })
+            y.api.qfix.add("/c")
+            "#,
+        )
+        .exec()
+        .unwrap();
+
+        assert_eq!(
+            take_api_requests(&lua),
+            vec![
+                ApiRequest::Execute("split /tmp".to_string()),
+                ApiRequest::FeedKeys("gg".to_string()),
+                ApiRequest::Navigate(PathBuf::from("/home")),
+                ApiRequest::Print("done".to_string()),
+                ApiRequest::QuickFixAdd(vec![PathBuf::from("/a"), PathBuf::from("/b")]),
+                ApiRequest::QuickFixAdd(vec![PathBuf::from("/c")]),
+            ]
+        );
+        assert!(take_api_requests(&lua).is_empty());
+    }
+
+    #[test]
+    fn api_state_is_kept_until_cleared() {
+        let lua = create_lua_from_script("");
+        set_api_state(
+            &lua,
+            ApiState {
+                mode: "normal".to_string(),
+                ..Default::default()
+            },
+        );
+
+        lua.load("y.api.print('first')").exec().unwrap();
+        assert_eq!(
+            take_api_requests(&lua),
+            vec![ApiRequest::Print("first".to_string())]
+        );
+
+        let mode: String = lua.load("return y.api.get_mode()").eval().unwrap();
+        assert_eq!(mode, "normal");
+
+        clear_api_state(&lua);
+        lua.load("y.api.print('ignored')").exec().unwrap();
+
+        let mode: LuaValue = lua.load("return y.api.get_mode()").eval().unwrap();
+        assert_eq!(mode, LuaValue::Nil);
+        assert!(take_api_requests(&lua).is_empty());
+    }
 }
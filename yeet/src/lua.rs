@@ -1,9 +1,14 @@
 use mlua::prelude::*;
-use yeet_frontend::theme::{parse_hex_color, Theme};
+use yeet_frontend::{
+    settings::{ConflictPolicy, PreviewLimit},
+    theme::{parse_hex_color, Theme},
+};
 use yeet_lua::LuaConfiguration;
 use yeet_plugin::PluginState;
 
 pub struct LuaInit {
+    pub conflict_policy: ConflictPolicy,
+    pub preview_limit: PreviewLimit,
     pub theme: Theme,
     pub lua: Option<LuaConfiguration>,
     pub plugin_states: Vec<PluginState>,
@@ -17,6 +22,8 @@ pub fn init() -> LuaInit {
         Some(lua) => lua,
         None => {
             return LuaInit {
+                conflict_policy: ConflictPolicy::default(),
+                preview_limit: PreviewLimit::default(),
                 theme,
                 lua: None,
                 plugin_states: Vec::new(),
@@ -28,6 +35,8 @@ pub fn init() -> LuaInit {
     read_theme_values(&lua, &mut theme);
 
     let plugin_concurrency = yeet_lua::read_plugin_concurrency(&lua);
+    let conflict_policy = read_conflict_policy(&lua);
+    let preview_limit = read_preview_limit(&lua);
 
     let plugin_states = match yeet_plugin::resolve_plugin_data_path() {
         Some(data_path) => yeet_lua::load_plugins(&lua, &data_path),
@@ -40,6 +49,8 @@ pub fn init() -> LuaInit {
     read_theme_values(&lua, &mut theme);
 
     LuaInit {
+        conflict_policy,
+        preview_limit,
         theme,
         lua: Some(lua),
         plugin_states,
@@ -47,6 +58,25 @@ pub fn init() -> LuaInit {
     }
 }
 
+fn read_conflict_policy(lua: &LuaConfiguration) -> ConflictPolicy {
+    match yeet_lua::read_conflict_policy(lua).map(|value| value.parse()) {
+        Some(Ok(policy)) => policy,
+        Some(Err(err)) => {
+            tracing::error!("{}, using default", err);
+            ConflictPolicy::default()
+        }
+        None => ConflictPolicy::default(),
+    }
+}
+
+fn read_preview_limit(lua: &LuaConfiguration) -> PreviewLimit {
+    let default = PreviewLimit::default();
+    PreviewLimit {
+        bytes: yeet_lua::read_preview_limit(lua, "max_bytes").unwrap_or(default.bytes),
+        lines: yeet_lua::read_preview_limit(lua, "max_lines").unwrap_or(default.lines),
+    }
+}
+
 fn read_theme_values(lua: &LuaConfiguration, theme: &mut Theme) {
     for (key, value) in yeet_lua::read_theme_pairs(lua) {
         if key == "syntax" {
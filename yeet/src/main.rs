@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use thiserror::Error;
 use tracing::Level;
-use yeet_frontend::settings::Settings;
+use yeet_frontend::settings::{ConflictPolicy, PreviewLimit, Settings};
 
 mod lua;
 
@@ -50,7 +50,13 @@ async fn main() {
     );
 
     match yeet_frontend::run(
-        get_settings(&cli, lua_init.theme, lua_init.plugin_concurrency),
+        get_settings(
+            &cli,
+            lua_init.theme,
+            lua_init.plugin_concurrency,
+            lua_init.conflict_policy,
+            lua_init.preview_limit,
+        ),
         lua_init.lua,
         lua_init.plugin_states,
     )
@@ -125,9 +131,13 @@ fn get_settings(
     args: &ArgMatches,
     theme: yeet_frontend::theme::Theme,
     plugin_concurrency: usize,
+    conflict_policy: ConflictPolicy,
+    preview_limit: PreviewLimit,
 ) -> Settings {
     Settings {
+        conflict_policy,
         plugin_concurrency,
+        preview_limit,
         selection_to_file_on_open: args.get_one("selection-to-file-on-open").cloned(),
         selection_to_stdout_on_open: args.get_flag("selection-to-stdout-on-open"),
         startup_path: expand_startup_path(args.get_one("path").cloned()),